@@ -1,10 +1,26 @@
 // RIFTS Governance Program - Token-based voting system
 use anchor_lang::prelude::*;
-use anchor_spl::token::{TokenAccount, Mint};
+use anchor_spl::token::{self, CloseAccount, Token, TokenAccount, Mint, Transfer};
 // **SECURITY FIX**: Import removed as it's now used inline in constraint
 
 declare_id!("DtBfLYvkXebsCxf49ZubJej9dMc9sNXUx2fctB3oeYtK");
 
+/// Maximum delegation records a delegate can count in a single cast_vote
+pub const MAX_DELEGATIONS_PER_VOTE: usize = 10;
+
+/// Window after proposal creation in which voters can snapshot; voting opens when it closes
+pub const SNAPSHOT_GRACE_PERIOD: i64 = 6 * 3600;
+
+/// How long past voting_end + min_execution_delay a finished proposal is kept before it can be closed
+pub const PROPOSAL_CLOSE_DELAY: i64 = 30 * 24 * 3600;
+
+/// Default supermajority for proposals flagged requires_supermajority (2/3)
+pub const DEFAULT_SUPERMAJORITY_BPS: u16 = 6667;
+
+/// Minimum time between two proposals from the same proposer (default and upper bound)
+pub const DEFAULT_PROPOSAL_COOLDOWN: i64 = 3600;
+pub const MAX_PROPOSAL_COOLDOWN: i64 = 7 * 24 * 3600;
+
 #[program]
 pub mod governance {
     use super::*;
@@ -15,12 +31,29 @@ pub mod governance {
         rifts_mint: Pubkey,
         min_voting_period: i64,
         min_execution_delay: i64,
+        params: InitGovernanceParams,
     ) -> Result<()> {
         let governance = &mut ctx.accounts.governance;
         
         // **SECURITY FIX**: Enforce minimum timeframes for secure governance
         require!(min_voting_period >= 86400, GovernanceError::InvalidVotingPeriod); // At least 24 hours
         require!(min_execution_delay >= 21600, GovernanceError::InvalidExecutionDelay); // At least 6 hours
+
+        // Per-type delays default to the global minimum and may only be longer
+        let parameter_change_delay = params.parameter_change_delay.unwrap_or(min_execution_delay);
+        let treasury_spend_delay = params.treasury_spend_delay.unwrap_or(min_execution_delay);
+        let protocol_upgrade_delay = params.protocol_upgrade_delay.unwrap_or(min_execution_delay);
+        let emergency_action_delay = params.emergency_action_delay.unwrap_or(min_execution_delay);
+        for delay in [parameter_change_delay, treasury_spend_delay, protocol_upgrade_delay, emergency_action_delay] {
+            require!(delay >= min_execution_delay, GovernanceError::InvalidExecutionDelay);
+        }
+
+        // Supermajority must be strictly more than a simple majority
+        let supermajority_bps = params.supermajority_bps.unwrap_or(DEFAULT_SUPERMAJORITY_BPS);
+        require!(
+            (5001..=10000).contains(&supermajority_bps),
+            GovernanceError::InvalidSupermajority
+        );
         
         governance.authority = ctx.accounts.authority.key();
         // **SECURITY FIX**: Initialize with single signature by default, can be upgraded to multisig
@@ -29,6 +62,12 @@ pub mod governance {
         governance.rifts_mint = rifts_mint;
         governance.min_voting_period = min_voting_period;
         governance.min_execution_delay = min_execution_delay;
+        governance.parameter_change_delay = parameter_change_delay;
+        governance.treasury_spend_delay = treasury_spend_delay;
+        governance.protocol_upgrade_delay = protocol_upgrade_delay;
+        governance.emergency_action_delay = emergency_action_delay;
+        governance.supermajority_bps = supermajority_bps;
+        governance.proposal_cooldown = DEFAULT_PROPOSAL_COOLDOWN;
         governance.total_proposals = 0;
         governance.total_executed = 0;
         
@@ -43,6 +82,8 @@ pub mod governance {
         governance.assets_frozen = false;
         governance.freeze_initiated_at = 0;
         governance.upgrade_ready_timestamp = 0;
+        governance.frozen_seconds_total = 0;
+        governance.frozen_since = 0;
         
         // Initialize pending execution states
         governance.pending_parameter_changes = None;
@@ -72,6 +113,7 @@ pub mod governance {
         description: String,
         proposal_type: ProposalType,
         execution_data: Vec<u8>,
+        requires_supermajority: bool,
     ) -> Result<()> {
         let proposal = &mut ctx.accounts.proposal;
         let governance = &mut ctx.accounts.governance;
@@ -97,6 +139,17 @@ pub mod governance {
             ctx.accounts.proposer_rifts_account.amount >= min_proposal_tokens,
             GovernanceError::InsufficientTokensToPropose
         );
+
+        // Rate-limit proposals per proposer to prevent proposal flooding
+        let current_time = Clock::get()?.unix_timestamp;
+        let proposer_record = &mut ctx.accounts.proposer_record;
+        require!(
+            current_time >= proposer_record.last_proposal_at.saturating_add(governance.proposal_cooldown),
+            GovernanceError::ProposalCooldownActive
+        );
+        proposer_record.proposer = ctx.accounts.proposer.key();
+        proposer_record.last_proposal_at = current_time;
+        proposer_record.bump = ctx.bumps.proposer_record;
         
         proposal.id = governance.total_proposals;
         proposal.proposer = ctx.accounts.proposer.key();
@@ -104,6 +157,7 @@ pub mod governance {
         proposal.description = description;
         proposal.proposal_type = proposal_type;
         proposal.execution_data = execution_data;
+        proposal.requires_supermajority = requires_supermajority;
         proposal.voting_start = Clock::get()?.unix_timestamp;
         proposal.voting_end = proposal.voting_start + governance.min_voting_period;
         proposal.votes_for = 0;
@@ -115,6 +169,9 @@ pub mod governance {
         // Set snapshot fields to prevent flash loan attacks
         proposal.snapshot_taken_at = proposal.created_at;
         proposal.snapshot_slot = Clock::get()?.slot;
+        proposal.snapshot_block_deadline = proposal.created_at
+            .checked_add(SNAPSHOT_GRACE_PERIOD)
+            .ok_or(GovernanceError::MathOverflow)?;
         // **CRITICAL FIX**: Require minimum 20% participation based on total supply percentage
         let total_supply = rifts_mint.supply;
         
@@ -157,10 +214,14 @@ pub mod governance {
         Ok(())
     }
     
-    /// Create vote snapshot for a voter when proposal is created
+    /// Create vote snapshot for a voter during the proposal's snapshot window
+    /// Open from proposal creation until snapshot_block_deadline, before any vote can be cast
+    /// `amount` RIFTS are escrowed under the snapshot until voting ends, so the same tokens
+    /// cannot be snapshotted again from another wallet or borrowed for the snapshot
     pub fn create_vote_snapshot(
         ctx: Context<CreateVoteSnapshot>,
         proposal_id: u64,
+        amount: u64,
     ) -> Result<()> {
         let vote_snapshot = &mut ctx.accounts.vote_snapshot;
         let proposal = &ctx.accounts.proposal;
@@ -171,9 +232,9 @@ pub mod governance {
         // Validate proposal ID matches
         require!(proposal.id == proposal_id, GovernanceError::InvalidSnapshot);
         
-        // **CRITICAL FIX**: Prevent snapshot creation after voting starts
+        // **CRITICAL FIX**: Snapshots close at the deadline, before any votes are tallied
         require!(
-            current_time < proposal.voting_start,
+            current_time < proposal.snapshot_block_deadline && proposal.total_voters == 0,
             GovernanceError::SnapshotTooLate
         );
         
@@ -183,56 +244,134 @@ pub mod governance {
             GovernanceError::SnapshotAlreadyExists
         );
         
-        let voter_balance = ctx.accounts.voter_rifts_account.amount;
+        require!(
+            amount > 0 && amount <= ctx.accounts.voter_rifts_account.amount,
+            GovernanceError::InvalidSnapshotAmount
+        );
+
+        // Power is the escrowed amount, locked until the proposal's voting ends
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.voter_rifts_account.to_account_info(),
+                    to: ctx.accounts.snapshot_escrow.to_account_info(),
+                    authority: ctx.accounts.voter.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
         
         // Record snapshot data
         vote_snapshot.proposal_id = proposal_id;
         vote_snapshot.voter = ctx.accounts.voter.key();
-        vote_snapshot.snapshot_power = voter_balance;
+        vote_snapshot.snapshot_power = amount;
         vote_snapshot.snapshot_taken_at = Clock::get()?.unix_timestamp;
+        vote_snapshot.proposal = proposal.key();
+        vote_snapshot.unlock_at = proposal.voting_end;
+        vote_snapshot.bump = ctx.bumps.vote_snapshot;
         
         emit!(VoteSnapshotCreated {
             proposal_id,
             voter: ctx.accounts.voter.key(),
-            snapshot_power: voter_balance,
+            snapshot_power: amount,
             timestamp: vote_snapshot.snapshot_taken_at,
         });
         
         Ok(())
     }
     
+    /// Return a snapshot's escrowed RIFTS to the voter once the proposal's voting has ended
+    /// The escrow token account is closed; the snapshot itself stays for rage-quit records
+    pub fn release_vote_snapshot(
+        ctx: Context<ReleaseVoteSnapshot>,
+    ) -> Result<()> {
+        let vote_snapshot = &ctx.accounts.vote_snapshot;
+        let current_time = Clock::get()?.unix_timestamp;
+
+        require!(current_time > vote_snapshot.unlock_at, GovernanceError::SnapshotStillLocked);
+
+        let voter_key = vote_snapshot.voter;
+        let bump = [vote_snapshot.bump];
+        let snapshot_seeds: &[&[u8]] = &[
+            b"vote_snapshot",
+            vote_snapshot.proposal.as_ref(),
+            voter_key.as_ref(),
+            &bump,
+        ];
+        let released = ctx.accounts.snapshot_escrow.amount;
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.snapshot_escrow.to_account_info(),
+                    to: ctx.accounts.voter_rifts_account.to_account_info(),
+                    authority: vote_snapshot.to_account_info(),
+                },
+                &[snapshot_seeds],
+            ),
+            released,
+        )?;
+        token::close_account(CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            CloseAccount {
+                account: ctx.accounts.snapshot_escrow.to_account_info(),
+                destination: ctx.accounts.voter.to_account_info(),
+                authority: vote_snapshot.to_account_info(),
+            },
+            &[snapshot_seeds],
+        ))?;
+
+        emit!(VoteSnapshotReleased {
+            proposal_id: vote_snapshot.proposal_id,
+            voter: voter_key,
+            amount: released,
+            released_at: current_time,
+        });
+
+        Ok(())
+    }
+    
     /// Cast a vote on a proposal
-    pub fn cast_vote(
-        ctx: Context<CastVote>,
+    /// Cast a vote using the voter's snapshot power
+    /// Delegates may pass `(VoteDelegation, delegator VoteSnapshot)` pairs in
+    /// remaining_accounts to add each delegator's snapshotted power to their vote
+    pub fn cast_vote<'info>(
+        ctx: Context<'_, '_, 'info, 'info, CastVote<'info>>,
         vote: VoteChoice,
     ) -> Result<()> {
+        let voter_key = ctx.accounts.voter.key();
         let proposal = &mut ctx.accounts.proposal;
         let vote_record = &mut ctx.accounts.vote_record;
         let vote_snapshot = &mut ctx.accounts.vote_snapshot;
         let current_time = Clock::get()?.unix_timestamp;
+
+        // A snapshot already counted through a delegate cannot vote again
+        require!(!vote_snapshot.consumed, GovernanceError::SnapshotAlreadyCounted);
         
         // **CRITICAL FIX**: Use actual snapshot-based voting instead of current balance
         // Check if snapshot exists for this voter and proposal
         let voting_power = if vote_snapshot.proposal_id == proposal.id && 
                               vote_snapshot.voter == ctx.accounts.voter.key() {
             // Use pre-recorded snapshot power
-            vote_snapshot.snapshot_power
+            vote_snapshot.effective_voting_power()?
         } else {
             // This should not happen if snapshots are properly created
             return Err(GovernanceError::SnapshotNotFound.into());
         };
         
-        // **CRITICAL SECURITY FIX**: Prevent flash loan attacks by requiring snapshots BEFORE proposal
-        // Snapshot must be taken BEFORE proposal creation (not after)
+        // **CRITICAL SECURITY FIX**: Snapshot must fall inside the proposal's snapshot window
         require!(
-            vote_snapshot.snapshot_taken_at < proposal.created_at, // Snapshot MUST be before proposal
+            vote_snapshot.snapshot_taken_at >= proposal.created_at
+                && vote_snapshot.snapshot_taken_at < proposal.snapshot_block_deadline,
             GovernanceError::InsufficientHoldingPeriod
         );
 
-        // Additional check: Snapshot must be recent enough (within 7 days)
+        // Votes are only tallied once the snapshot window has closed
         require!(
-            vote_snapshot.snapshot_taken_at >= proposal.created_at - (7 * 86400), // Within 7 days before proposal
-            GovernanceError::InsufficientHoldingPeriod
+            current_time >= proposal.snapshot_block_deadline,
+            GovernanceError::SnapshotWindowOpen
         );
         
         // **SECURITY**: Snapshot timing validation complete - prevents flash loan attacks
@@ -251,6 +390,21 @@ pub mod governance {
             proposal.status == ProposalStatus::Active,
             GovernanceError::ProposalNotActive
         );
+
+        // Sum delegated snapshot power passed as (delegation, snapshot) pairs
+        let (delegated_power, delegators) = tally_delegated_power(
+            ctx.remaining_accounts,
+            &voter_key,
+            &proposal.key(),
+            proposal.id,
+            ctx.program_id,
+            true,
+        )?;
+
+        vote_snapshot.consumed = true;
+        let voting_power = voting_power
+            .checked_add(delegated_power)
+            .ok_or(GovernanceError::VoteOverflow)?;
         
         // **CRITICAL FIX**: Use governance mint decimals instead of hardcoded 9
         let _governance = &ctx.accounts.governance;
@@ -299,10 +453,98 @@ pub mod governance {
             vote,
             voting_power: voting_power, // Use snapshot power
         });
+
+        if !delegators.is_empty() {
+            emit!(DelegatedVotesCounted {
+                proposal_id: proposal.id,
+                delegate: voter_key,
+                delegators: delegators.len() as u32,
+                delegated_power,
+            });
+        }
         
         Ok(())
     }
+
+    /// Delegate future voting power to another address
+    /// Only snapshots taken after delegation can be counted by the delegate
+    pub fn delegate_votes(
+        ctx: Context<DelegateVotes>,
+        delegate: Pubkey,
+    ) -> Result<()> {
+        let delegator = ctx.accounts.delegator.key();
+        require!(
+            delegate != Pubkey::default() && delegate != delegator,
+            GovernanceError::InvalidDelegation
+        );
+
+        let delegation = &mut ctx.accounts.vote_delegation;
+        delegation.delegator = delegator;
+        delegation.delegate = delegate;
+        delegation.delegated_at = Clock::get()?.unix_timestamp;
+        delegation.bump = ctx.bumps.vote_delegation;
+
+        emit!(VotesDelegated {
+            delegator,
+            delegate,
+            timestamp: delegation.delegated_at,
+        });
+
+        Ok(())
+    }
+
+    /// Revoke an active delegation and reclaim its rent
+    pub fn revoke_delegation(
+        ctx: Context<RevokeDelegation>,
+    ) -> Result<()> {
+        emit!(DelegationRevoked {
+            delegator: ctx.accounts.delegator.key(),
+            delegate: ctx.accounts.vote_delegation.delegate,
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
     
+    /// Record on-chain dissent from an executed treasury spend the voter voted against
+    /// Creates a RageQuitRecord carrying the voter's snapshot power (no token redemption)
+    pub fn record_rage_quit(
+        ctx: Context<RecordRageQuit>,
+    ) -> Result<()> {
+        let proposal = &ctx.accounts.proposal;
+        let vote_record = &ctx.accounts.vote_record;
+
+        require!(
+            proposal.proposal_type == ProposalType::TreasurySpend,
+            GovernanceError::NotTreasurySpendProposal
+        );
+        require!(
+            proposal.status == ProposalStatus::Executed,
+            GovernanceError::ProposalNotExecuted
+        );
+        require!(
+            vote_record.vote == VoteChoice::Against,
+            GovernanceError::NotAgainstVote
+        );
+
+        let rage_quit_record = &mut ctx.accounts.rage_quit_record;
+        rage_quit_record.voter = ctx.accounts.voter.key();
+        rage_quit_record.proposal = proposal.key();
+        rage_quit_record.proposal_id = proposal.id;
+        rage_quit_record.snapshot_power = ctx.accounts.vote_snapshot.snapshot_power;
+        rage_quit_record.recorded_at = Clock::get()?.unix_timestamp;
+        rage_quit_record.bump = ctx.bumps.rage_quit_record;
+
+        emit!(RageQuitRecorded {
+            proposal_id: proposal.id,
+            voter: rage_quit_record.voter,
+            snapshot_power: rage_quit_record.snapshot_power,
+            recorded_at: rage_quit_record.recorded_at,
+        });
+
+        Ok(())
+    }
+
     /// Execute a passed proposal
     pub fn execute_proposal(
         ctx: Context<ExecuteProposal>,
@@ -370,9 +612,9 @@ pub mod governance {
             GovernanceError::VotingStillActive
         );
         
-        // Check if execution delay has passed
+        // Check if the type-specific execution delay has passed
         require!(
-            current_time >= proposal.voting_end + governance.min_execution_delay,
+            current_time >= proposal.voting_end + governance.execution_delay_for(&proposal.proposal_type),
             GovernanceError::ExecutionDelayNotMet
         );
         
@@ -398,28 +640,11 @@ pub mod governance {
             GovernanceError::InsufficientParticipation
         );
         
-        // **SECURITY FIX**: Check if proposal passed (supermajority required for emergency actions)
-        match proposal.proposal_type {
-            ProposalType::EmergencyAction => {
-                // Emergency proposals require supermajority (2/3 of total votes)
-                let required_supermajority = total_votes
-                    .checked_mul(2)
-                    .and_then(|doubled| doubled.checked_div(3))
-                    .ok_or(GovernanceError::MathOverflow)?;
-                
-                require!(
-                    proposal.votes_for >= required_supermajority,
-                    GovernanceError::ProposalDidNotPass
-                );
-            },
-            _ => {
-                // Regular proposals require simple majority
-                require!(
-                    proposal.votes_for > proposal.votes_against,
-                    GovernanceError::ProposalDidNotPass
-                );
-            }
-        }
+        // **SECURITY FIX**: Check if proposal passed (supermajority for emergency actions and flagged proposals)
+        require!(
+            proposal.majority_met(total_votes, governance.supermajority_bps)?,
+            GovernanceError::ProposalDidNotPass
+        );
         
         // Execute based on proposal type with real implementation
         match proposal.proposal_type {
@@ -444,12 +669,40 @@ pub mod governance {
                         // Validate it's not zero address
                         require!(new_jupiter_id != Pubkey::default(), GovernanceError::InvalidParameterValue);
                     }
+                    if let Some(new_admin) = param_changes.protocol_admin {
+                        require!(new_admin != Pubkey::default(), GovernanceError::InvalidParameterValue);
+                    }
+                    if let Some(new_ratio) = param_changes.backing_ratio {
+                        require!(new_ratio > 0, GovernanceError::InvalidParameterValue);
+                        require!(
+                            param_changes.backing_ratio_rift.is_some(),
+                            GovernanceError::InvalidParameterValue
+                        );
+                    }
+                    if let Some(new_cooldown) = param_changes.proposal_cooldown {
+                        require!(
+                            (0..=MAX_PROPOSAL_COOLDOWN).contains(&new_cooldown),
+                            GovernanceError::InvalidParameterValue
+                        );
+                    }
+                    if param_changes.unstake_cooldown.is_some() {
+                        require!(
+                            param_changes.unstake_cooldown_pool.is_some(),
+                            GovernanceError::InvalidParameterValue
+                        );
+                    }
                     
                     // Apply Jupiter program ID change immediately
                     if let Some(new_jupiter_id) = param_changes.jupiter_program_id {
                         governance.jupiter_program_id = Some(new_jupiter_id);
                         msg!("🔄 Jupiter program ID updated to: {}", new_jupiter_id);
                     }
+
+                    // Apply proposal cooldown change immediately
+                    if let Some(new_cooldown) = param_changes.proposal_cooldown {
+                        governance.proposal_cooldown = new_cooldown;
+                        msg!("Proposal cooldown updated to: {}s", new_cooldown);
+                    }
                     
                     // Store execution data for the rift program to read
                     governance.pending_parameter_changes = Some(param_changes.clone());
@@ -531,6 +784,8 @@ pub mod governance {
                     let emergency_action = EmergencyActionInstruction::try_from_slice(&proposal.execution_data)
                         .map_err(|_| GovernanceError::InvalidExecutionData)?;
                     
+                    let was_frozen = governance.is_frozen();
+
                     // Validate emergency action type
                     match emergency_action.action_type {
                         EmergencyActionType::PauseProtocol => {
@@ -558,6 +813,8 @@ pub mod governance {
                             governance.freeze_initiated_at = 0;
                         },
                     }
+
+                    governance.record_frozen_transition(was_frozen, current_time)?;
                     
                     emit!(EmergencyActionExecuted {
                         proposal_id: proposal.id,
@@ -628,6 +885,125 @@ pub mod governance {
         Ok(())
     }
 
+    /// Close an Executed or Cancelled proposal once it is PROPOSAL_CLOSE_DELAY past its timelock
+    /// Callable by the proposer or governance authority; rent always goes back to the proposer.
+    /// The proposal's MultisigSignatureState, if one was created, is closed first.
+    pub fn close_proposal(
+        ctx: Context<CloseProposal>,
+    ) -> Result<()> {
+        let proposal = &ctx.accounts.proposal;
+        let governance = &ctx.accounts.governance;
+        let current_time = Clock::get()?.unix_timestamp;
+
+        require!(
+            ctx.accounts.closer.key() == proposal.proposer ||
+            ctx.accounts.closer.key() == governance.authority,
+            GovernanceError::UnauthorizedClose
+        );
+
+        require!(
+            proposal.status == ProposalStatus::Executed ||
+            proposal.status == ProposalStatus::Cancelled,
+            GovernanceError::ProposalNotClosable
+        );
+
+        let closable_at = proposal.voting_end
+            .checked_add(governance.min_execution_delay)
+            .and_then(|t| t.checked_add(PROPOSAL_CLOSE_DELAY))
+            .ok_or(GovernanceError::MathOverflow)?;
+        require!(current_time >= closable_at, GovernanceError::ProposalCloseTooEarly);
+
+        // Signature state was paid for by the proposer; never leave it orphaned
+        let multisig_state = &ctx.accounts.multisig_signature_state;
+        if multisig_state.owner == &crate::ID && multisig_state.lamports() > 0 {
+            let multisig_info = multisig_state.to_account_info();
+            let proposer_info = ctx.accounts.proposer.to_account_info();
+            let refund = multisig_info.lamports();
+            **proposer_info.try_borrow_mut_lamports()? = proposer_info
+                .lamports()
+                .checked_add(refund)
+                .ok_or(GovernanceError::MathOverflow)?;
+            **multisig_info.try_borrow_mut_lamports()? = 0;
+            multisig_info.assign(&System::id());
+            multisig_info.resize(0)?;
+        }
+
+        emit!(ProposalClosed {
+            proposal_id: proposal.id,
+            closed_by: ctx.accounts.closer.key(),
+            rent_recipient: proposal.proposer,
+            closed_at: current_time,
+        });
+
+        Ok(())
+    }
+
+    /// Pay out the TreasurySpend approved by proposal `proposal_id` (permissionless crank)
+    /// Funds come from a treasury vault owned by the ["treasury", governance] PDA; the
+    /// recipient account must be the approved recipient or be owned by it.
+    pub fn execute_treasury_spend(
+        ctx: Context<ExecuteTreasurySpend>,
+        proposal_id: u64,
+    ) -> Result<()> {
+        let governance = &mut ctx.accounts.governance;
+
+        let spend = governance.pending_treasury_spend
+            .clone()
+            .ok_or(GovernanceError::NoPendingTreasurySpend)?;
+        require!(
+            proposal_id == governance.treasury_spend_proposal_id,
+            GovernanceError::TreasurySpendMismatch
+        );
+
+        // Re-validate against current limits; max_treasury_spend may have been lowered since approval
+        require!(spend.amount > 0, GovernanceError::InvalidSpendAmount);
+        require!(spend.amount <= governance.max_treasury_spend, GovernanceError::ExceedsMaxSpend);
+
+        let recipient_account = &ctx.accounts.recipient_token_account;
+        require!(
+            recipient_account.key() == spend.recipient || recipient_account.owner == spend.recipient,
+            GovernanceError::InvalidRecipient
+        );
+        require!(
+            recipient_account.mint == spend.token_mint
+                && ctx.accounts.treasury_vault.mint == spend.token_mint,
+            GovernanceError::TreasurySpendMismatch
+        );
+        require!(
+            ctx.accounts.treasury_vault.amount >= spend.amount,
+            GovernanceError::InsufficientTreasuryBalance
+        );
+
+        let governance_key = governance.key();
+        let bump = [ctx.bumps.treasury_authority];
+        let treasury_seeds: &[&[u8]] = &[b"treasury", governance_key.as_ref(), &bump];
+
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.treasury_vault.to_account_info(),
+                    to: recipient_account.to_account_info(),
+                    authority: ctx.accounts.treasury_authority.to_account_info(),
+                },
+                &[treasury_seeds],
+            ),
+            spend.amount,
+        )?;
+
+        governance.pending_treasury_spend = None;
+
+        emit!(TreasurySpendDisbursed {
+            proposal_id,
+            recipient: recipient_account.key(),
+            amount: spend.amount,
+            token_mint: spend.token_mint,
+            executor: ctx.accounts.executor.key(),
+        });
+
+        Ok(())
+    }
+
     /// **MULTISIG GOVERNANCE**: Add a new authority to the multisig
     pub fn add_multisig_authority(
         ctx: Context<ModifyMultisigAuthority>,
@@ -847,9 +1223,239 @@ pub mod governance {
         Ok(())
     }
 
+    /// Query a voter's effective voting power for a proposal (read-only)
+    /// Returns the same power cast_vote will count, via return data
+    /// Delegated power is counted from the same (VoteDelegation, VoteSnapshot) remaining_accounts
+    /// pairs cast_vote takes; snapshots already counted by a vote contribute 0
+    pub fn get_voting_power<'info>(
+        ctx: Context<'_, '_, 'info, 'info, GetVotingPower<'info>>,
+    ) -> Result<()> {
+        let vote_snapshot = &ctx.accounts.vote_snapshot;
+
+        let own_power = if vote_snapshot.consumed {
+            0
+        } else {
+            vote_snapshot.effective_voting_power()?
+        };
+        let (delegated_power, _) = tally_delegated_power(
+            ctx.remaining_accounts,
+            &ctx.accounts.voter.key(),
+            &ctx.accounts.proposal.key(),
+            ctx.accounts.proposal.id,
+            ctx.program_id,
+            false,
+        )?;
+        let effective_power = own_power
+            .checked_add(delegated_power)
+            .ok_or(GovernanceError::VoteOverflow)?;
+
+        msg!("Voting power for {} on proposal {}: snapshot={}, delegated={}, effective={}",
+             vote_snapshot.voter, vote_snapshot.proposal_id,
+             vote_snapshot.snapshot_power, delegated_power, effective_power);
+
+        anchor_lang::solana_program::program::set_return_data(&effective_power.try_to_vec()?);
+
+        Ok(())
+    }
+
+    /// Report whether a proposal currently passes, using execute_proposal's quorum and majority rules
+    /// Returns a borsh-encoded ProposalOutcome via return data; Pending while voting is active
+    pub fn proposal_outcome(
+        ctx: Context<GetProposalOutcome>,
+    ) -> Result<()> {
+        let proposal = &ctx.accounts.proposal;
+        let governance = &ctx.accounts.governance;
+        let current_time = Clock::get()?.unix_timestamp;
+
+        let total_votes = proposal.votes_for
+            .checked_add(proposal.votes_against)
+            .ok_or(GovernanceError::VoteOverflow)?;
+        let voting_ended = current_time > proposal.voting_end;
+        let participation_met = total_votes >= u128::from(proposal.min_participation_required);
+        let majority_met = proposal.majority_met(total_votes, governance.supermajority_bps)?;
+
+        let status = if !voting_ended {
+            OutcomeStatus::Pending
+        } else if participation_met && majority_met {
+            OutcomeStatus::Passing
+        } else {
+            OutcomeStatus::Failing
+        };
+
+        let outcome = ProposalOutcome {
+            proposal_id: proposal.id,
+            status,
+            voting_ended,
+            participation_met,
+            majority_met,
+            total_votes,
+            votes_for: proposal.votes_for,
+            votes_against: proposal.votes_against,
+            executable_at: proposal.voting_end
+                .saturating_add(governance.execution_delay_for(&proposal.proposal_type)),
+        };
+
+        msg!("Proposal {} outcome: {:?} (votes {}/{}, participation met: {})",
+             proposal.id, status, proposal.votes_for, total_votes, participation_met);
+
+        anchor_lang::solana_program::program::set_return_data(&outcome.try_to_vec()?);
+
+        Ok(())
+    }
+
+    /// Rewrite a Governance account created before the per-type delays, frozen-time tracking,
+    /// supermajority and proposal cooldown fields into the current layout
+    /// The account is grown to Governance::INIT_SPACE (authority tops up rent); new fields take
+    /// the same defaults initialize_governance would, and an open freeze is timed from now.
+    pub fn migrate_governance(
+        ctx: Context<MigrateGovernance>,
+    ) -> Result<()> {
+        let governance_info = ctx.accounts.governance.to_account_info();
+        let current_time = Clock::get()?.unix_timestamp;
+
+        // Current-layout accounts are always allocated at INIT_SPACE
+        require!(
+            governance_info.data_len() < Governance::INIT_SPACE,
+            GovernanceError::GovernanceAlreadyMigrated
+        );
+
+        let legacy = {
+            let data = governance_info.try_borrow_data()?;
+            require!(
+                data.len() >= 8 && data[..8] == *Governance::DISCRIMINATOR,
+                GovernanceError::InvalidLegacyGovernance
+            );
+            LegacyGovernance::deserialize(&mut &data[8..])
+                .map_err(|_| GovernanceError::InvalidLegacyGovernance)?
+        };
+        require!(
+            ctx.accounts.authority.key() == legacy.authority,
+            GovernanceError::UnauthorizedCancel
+        );
+
+        let was_frozen = legacy.assets_frozen || legacy.emergency_pause_active;
+        let migrated = Governance {
+            authority: legacy.authority,
+            additional_authorities: legacy.additional_authorities,
+            required_signatures: legacy.required_signatures,
+            rifts_mint: legacy.rifts_mint,
+            min_voting_period: legacy.min_voting_period,
+            min_execution_delay: legacy.min_execution_delay,
+            total_proposals: legacy.total_proposals,
+            total_executed: legacy.total_executed,
+            max_treasury_spend: legacy.max_treasury_spend,
+            emergency_pause_active: legacy.emergency_pause_active,
+            pause_initiated_at: legacy.pause_initiated_at,
+            pause_duration: legacy.pause_duration,
+            assets_frozen: legacy.assets_frozen,
+            freeze_initiated_at: legacy.freeze_initiated_at,
+            upgrade_ready_timestamp: legacy.upgrade_ready_timestamp,
+            pending_parameter_changes: legacy.pending_parameter_changes.map(Into::into),
+            parameter_change_proposal_id: legacy.parameter_change_proposal_id,
+            pending_treasury_spend: legacy.pending_treasury_spend,
+            treasury_spend_proposal_id: legacy.treasury_spend_proposal_id,
+            pending_protocol_upgrade: legacy.pending_protocol_upgrade,
+            protocol_upgrade_proposal_id: legacy.protocol_upgrade_proposal_id,
+            pending_oracle_updates: legacy.pending_oracle_updates,
+            oracle_update_proposal_id: legacy.oracle_update_proposal_id,
+            treasury_fee_bps: legacy.treasury_fee_bps,
+            jupiter_program_id: legacy.jupiter_program_id,
+            parameter_change_delay: legacy.min_execution_delay,
+            treasury_spend_delay: legacy.min_execution_delay,
+            protocol_upgrade_delay: legacy.min_execution_delay,
+            emergency_action_delay: legacy.min_execution_delay,
+            frozen_seconds_total: 0,
+            frozen_since: if was_frozen { current_time } else { 0 },
+            supermajority_bps: DEFAULT_SUPERMAJORITY_BPS,
+            proposal_cooldown: DEFAULT_PROPOSAL_COOLDOWN,
+        };
+
+        grow_account(
+            &governance_info,
+            &ctx.accounts.authority,
+            &ctx.accounts.system_program,
+            Governance::INIT_SPACE,
+        )?;
+
+        let mut data = governance_info.try_borrow_mut_data()?;
+        data.fill(0);
+        migrated.try_serialize(&mut &mut data[..])?;
+
+        msg!("Governance {} migrated to the current layout", governance_info.key());
+
+        Ok(())
+    }
+
+    /// Grow a Proposal created before snapshot_block_deadline and requires_supermajority existed
+    /// The snapshot window is restored to SNAPSHOT_GRACE_PERIOD after creation, as create_proposal
+    /// sets it; without it the zeroed deadline would close snapshots (and so voting) for good.
+    /// Permissionless: the payer only tops up rent.
+    pub fn migrate_proposal(
+        ctx: Context<MigrateProposal>,
+    ) -> Result<()> {
+        let proposal_info = ctx.accounts.proposal.to_account_info();
+
+        // Current-layout accounts are always allocated at INIT_SPACE
+        require!(
+            proposal_info.data_len() < Proposal::INIT_SPACE,
+            GovernanceError::ProposalAlreadyMigrated
+        );
+        require!(
+            proposal_info.try_borrow_data()?[..8] == *Proposal::DISCRIMINATOR,
+            GovernanceError::InvalidProposal
+        );
+
+        grow_account(
+            &proposal_info,
+            &ctx.accounts.payer,
+            &ctx.accounts.system_program,
+            Proposal::INIT_SPACE,
+        )?;
+
+        // Both new fields were appended, so the legacy prefix decodes as-is
+        let mut proposal = Proposal::try_deserialize(&mut &proposal_info.try_borrow_data()?[..])?;
+        proposal.snapshot_block_deadline = proposal.created_at
+            .checked_add(SNAPSHOT_GRACE_PERIOD)
+            .ok_or(GovernanceError::MathOverflow)?;
+        proposal.requires_supermajority = false;
+
+        let mut data = proposal_info.try_borrow_mut_data()?;
+        data.fill(0);
+        proposal.try_serialize(&mut &mut data[..])?;
+
+        msg!("Proposal {} migrated to the current layout", proposal.id);
+
+        Ok(())
+    }
+
 }
 
 // Account structures
+#[derive(Accounts)]
+pub struct MigrateProposal<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Pre-migration Proposal; ownership is checked here, discriminator and size in the handler
+    #[account(mut, owner = crate::ID @ GovernanceError::InvalidProposal)]
+    pub proposal: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateGovernance<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+
+    /// CHECK: Pre-migration Governance that no longer deserializes as the current layout;
+    /// ownership is checked here, discriminator and authority in the handler
+    #[account(mut, owner = crate::ID @ GovernanceError::InvalidLegacyGovernance)]
+    pub governance: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
 #[derive(Accounts)]
 pub struct InitializeGovernance<'info> {
     #[account(mut)]
@@ -890,6 +1496,16 @@ pub struct CreateProposal<'info> {
         constraint = proposer.lamports() >= Rent::get()?.minimum_balance(Proposal::INIT_SPACE) @ GovernanceError::InsufficientRentExemption
     )]
     pub proposal: Account<'info, Proposal>,
+
+    /// Per-proposer rate-limit state
+    #[account(
+        init_if_needed,
+        payer = proposer,
+        space = ProposerRecord::INIT_SPACE,
+        seeds = [b"proposer_record", governance.key().as_ref(), proposer.key().as_ref()],
+        bump
+    )]
+    pub proposer_record: Account<'info, ProposerRecord>,
     
     #[account(
         constraint = proposer_rifts_account.owner == proposer.key()
@@ -917,10 +1533,26 @@ pub struct CreateVoteSnapshot<'info> {
         constraint = voter.lamports() >= Rent::get()?.minimum_balance(VoteSnapshot::INIT_SPACE) @ GovernanceError::InsufficientRentExemption
     )]
     pub vote_snapshot: Account<'info, VoteSnapshot>,
+
+    /// Escrow holding the snapshotted RIFTS until voting ends
+    #[account(
+        init,
+        payer = voter,
+        seeds = [b"snapshot_escrow", vote_snapshot.key().as_ref()],
+        bump,
+        token::mint = rifts_mint,
+        token::authority = vote_snapshot
+    )]
+    pub snapshot_escrow: Account<'info, TokenAccount>,
     
+    #[account(
+        seeds = [b"proposal", governance.key().as_ref(), &proposal.id.to_le_bytes()],
+        bump
+    )]
     pub proposal: Account<'info, Proposal>,
     
     #[account(
+        mut,
         constraint = voter_rifts_account.owner == voter.key() @ GovernanceError::InvalidTokenOwner,
         constraint = voter_rifts_account.mint == governance.rifts_mint @ GovernanceError::InvalidRiftsMint,
         // **SECURITY FIX**: Enforce canonical associated token account to prevent vote delegation
@@ -929,8 +1561,42 @@ pub struct CreateVoteSnapshot<'info> {
     pub voter_rifts_account: Account<'info, TokenAccount>,
     
     pub governance: Account<'info, Governance>,
+
+    #[account(address = governance.rifts_mint @ GovernanceError::InvalidRiftsMint)]
+    pub rifts_mint: Account<'info, Mint>,
     
+    pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct ReleaseVoteSnapshot<'info> {
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    #[account(
+        seeds = [b"vote_snapshot", vote_snapshot.proposal.as_ref(), voter.key().as_ref()],
+        bump = vote_snapshot.bump,
+        constraint = vote_snapshot.voter == voter.key() @ GovernanceError::InvalidSnapshot
+    )]
+    pub vote_snapshot: Account<'info, VoteSnapshot>,
+
+    #[account(
+        mut,
+        seeds = [b"snapshot_escrow", vote_snapshot.key().as_ref()],
+        bump
+    )]
+    pub snapshot_escrow: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = voter_rifts_account.owner == voter.key() @ GovernanceError::InvalidTokenOwner,
+        constraint = voter_rifts_account.mint == snapshot_escrow.mint @ GovernanceError::InvalidRiftsMint
+    )]
+    pub voter_rifts_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
 }
 
 #[derive(Accounts)]
@@ -962,6 +1628,7 @@ pub struct CastVote<'info> {
     
     /// **CRITICAL FIX**: Vote snapshot for flash loan protection
     #[account(
+        mut,
         constraint = vote_snapshot.voter == voter.key() @ GovernanceError::InvalidSnapshot,
         constraint = vote_snapshot.proposal_id == proposal.id @ GovernanceError::InvalidSnapshot
     )]
@@ -975,6 +1642,73 @@ pub struct CastVote<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+pub struct DelegateVotes<'info> {
+    #[account(mut)]
+    pub delegator: Signer<'info>,
+
+    #[account(
+        init,
+        payer = delegator,
+        space = VoteDelegation::INIT_SPACE,
+        seeds = [b"vote_delegation", delegator.key().as_ref()],
+        bump
+    )]
+    pub vote_delegation: Account<'info, VoteDelegation>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RevokeDelegation<'info> {
+    #[account(mut)]
+    pub delegator: Signer<'info>,
+
+    #[account(
+        mut,
+        close = delegator,
+        seeds = [b"vote_delegation", delegator.key().as_ref()],
+        bump = vote_delegation.bump,
+        constraint = vote_delegation.delegator == delegator.key() @ GovernanceError::InvalidDelegation
+    )]
+    pub vote_delegation: Account<'info, VoteDelegation>,
+}
+
+#[derive(Accounts)]
+pub struct RecordRageQuit<'info> {
+    #[account(mut)]
+    pub voter: Signer<'info>,
+
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        seeds = [b"vote", proposal.key().as_ref(), voter.key().as_ref()],
+        bump,
+        constraint = vote_record.voter == voter.key() @ GovernanceError::InvalidProposal,
+        constraint = vote_record.proposal == proposal.key() @ GovernanceError::InvalidProposal
+    )]
+    pub vote_record: Account<'info, VoteRecord>,
+
+    #[account(
+        seeds = [b"vote_snapshot", proposal.key().as_ref(), voter.key().as_ref()],
+        bump,
+        constraint = vote_snapshot.voter == voter.key() @ GovernanceError::InvalidSnapshot,
+        constraint = vote_snapshot.proposal_id == proposal.id @ GovernanceError::InvalidSnapshot
+    )]
+    pub vote_snapshot: Account<'info, VoteSnapshot>,
+
+    #[account(
+        init,
+        payer = voter,
+        space = RageQuitRecord::INIT_SPACE,
+        seeds = [b"rage_quit", proposal.key().as_ref(), voter.key().as_ref()],
+        bump
+    )]
+    pub rage_quit_record: Account<'info, RageQuitRecord>,
+
+    pub system_program: Program<'info, System>,
+}
+
 #[derive(Accounts)]
 pub struct ExecuteProposal<'info> {
     #[account(mut)]
@@ -1005,6 +1739,63 @@ pub struct CancelProposal<'info> {
     pub proposal: Account<'info, Proposal>,
 }
 
+#[derive(Accounts)]
+pub struct CloseProposal<'info> {
+    pub closer: Signer<'info>,
+
+    pub governance: Account<'info, Governance>,
+
+    #[account(
+        mut,
+        close = proposer,
+        seeds = [b"proposal", governance.key().as_ref(), &proposal.id.to_le_bytes()],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+
+    /// Rent recipient; must be the original proposer
+    #[account(
+        mut,
+        address = proposal.proposer @ GovernanceError::InvalidProposal
+    )]
+    pub proposer: SystemAccount<'info>,
+
+    /// CHECK: Multisig signature PDA for this proposal; closed if it exists, ignored otherwise
+    #[account(
+        mut,
+        seeds = [b"multisig", proposal.key().as_ref()],
+        bump
+    )]
+    pub multisig_signature_state: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteTreasurySpend<'info> {
+    pub executor: Signer<'info>,
+
+    #[account(mut)]
+    pub governance: Account<'info, Governance>,
+
+    /// CHECK: Treasury authority PDA - validated by seeds constraint
+    #[account(
+        seeds = [b"treasury", governance.key().as_ref()],
+        bump
+    )]
+    pub treasury_authority: UncheckedAccount<'info>,
+
+    /// Treasury vault holding protocol funds
+    #[account(
+        mut,
+        token::authority = treasury_authority
+    )]
+    pub treasury_vault: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub recipient_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
 #[derive(Accounts)]
 pub struct ModifyMultisigAuthority<'info> {
     #[account(mut)]
@@ -1049,6 +1840,132 @@ pub struct InitializeMultisigProposal<'info> {
     pub system_program: Program<'info, System>,
 }
 
+#[derive(Accounts)]
+pub struct GetVotingPower<'info> {
+    /// CHECK: Voter whose power is being queried; only used for PDA derivation
+    pub voter: UncheckedAccount<'info>,
+
+    pub proposal: Account<'info, Proposal>,
+
+    #[account(
+        seeds = [b"vote_snapshot", proposal.key().as_ref(), voter.key().as_ref()],
+        bump,
+        constraint = vote_snapshot.voter == voter.key() @ GovernanceError::InvalidSnapshot,
+        constraint = vote_snapshot.proposal_id == proposal.id @ GovernanceError::InvalidSnapshot
+    )]
+    pub vote_snapshot: Account<'info, VoteSnapshot>,
+}
+
+/// Read-only account struct for proposal_outcome
+#[derive(Accounts)]
+pub struct GetProposalOutcome<'info> {
+    pub governance: Account<'info, Governance>,
+
+    #[account(
+        seeds = [b"proposal", governance.key().as_ref(), &proposal.id.to_le_bytes()],
+        bump
+    )]
+    pub proposal: Account<'info, Proposal>,
+}
+
+/// Grow a program-owned account to `new_len`, topping up rent from `payer`; new bytes are zeroed
+pub fn grow_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    new_len: usize,
+) -> Result<()> {
+    let rent_needed = Rent::get()?
+        .minimum_balance(new_len)
+        .saturating_sub(account.lamports());
+    if rent_needed > 0 {
+        anchor_lang::system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                anchor_lang::system_program::Transfer {
+                    from: payer.to_account_info(),
+                    to: account.clone(),
+                },
+            ),
+            rent_needed,
+        )?;
+    }
+    account.resize(new_len)?;
+    Ok(())
+}
+
+/// Sum the snapshot power of (VoteDelegation, delegator VoteSnapshot) pairs delegated to `delegate`
+/// With `consume`, counted snapshots are marked consumed and already-consumed ones are rejected
+/// (cast_vote); without it, consumed snapshots contribute 0 (read-only queries)
+pub fn tally_delegated_power<'info>(
+    remaining: &'info [AccountInfo<'info>],
+    delegate: &Pubkey,
+    proposal_key: &Pubkey,
+    proposal_id: u64,
+    program_id: &Pubkey,
+    consume: bool,
+) -> Result<(u64, Vec<Pubkey>)> {
+    require!(remaining.len() % 2 == 0, GovernanceError::InvalidDelegation);
+    require!(
+        remaining.len() / 2 <= MAX_DELEGATIONS_PER_VOTE,
+        GovernanceError::TooManyDelegations
+    );
+
+    let mut delegated_power: u64 = 0;
+    let mut delegators: Vec<Pubkey> = Vec::with_capacity(remaining.len() / 2);
+    for pair in remaining.chunks(2) {
+        let delegation: Account<VoteDelegation> = Account::try_from(&pair[0])?;
+        let (expected_delegation, _) = Pubkey::find_program_address(
+            &[b"vote_delegation", delegation.delegator.as_ref()],
+            program_id,
+        );
+        require!(
+            pair[0].key() == expected_delegation && delegation.delegate == *delegate,
+            GovernanceError::InvalidDelegation
+        );
+        require!(
+            !delegators.contains(&delegation.delegator),
+            GovernanceError::DuplicateDelegation
+        );
+
+        let mut delegator_snapshot: Account<VoteSnapshot> = Account::try_from(&pair[1])?;
+        let (expected_snapshot, _) = Pubkey::find_program_address(
+            &[b"vote_snapshot", proposal_key.as_ref(), delegation.delegator.as_ref()],
+            program_id,
+        );
+        require!(
+            pair[1].key() == expected_snapshot
+                && delegator_snapshot.voter == delegation.delegator
+                && delegator_snapshot.proposal_id == proposal_id,
+            GovernanceError::InvalidSnapshot
+        );
+
+        // Delegation must predate the delegator's snapshot
+        require!(
+            delegation.delegated_at <= delegator_snapshot.snapshot_taken_at,
+            GovernanceError::DelegationAfterSnapshot
+        );
+        delegators.push(delegation.delegator);
+
+        // A delegator that already voted cannot be counted again
+        if delegator_snapshot.consumed {
+            require!(!consume, GovernanceError::SnapshotAlreadyCounted);
+            continue;
+        }
+
+        delegated_power = delegated_power
+            .checked_add(delegator_snapshot.effective_voting_power()?)
+            .ok_or(GovernanceError::VoteOverflow)?;
+
+        if consume {
+            delegator_snapshot.consumed = true;
+            delegator_snapshot.exit(program_id)?;
+        }
+    }
+
+    Ok((delegated_power, delegators))
+}
+
 // State accounts
 impl Governance {
     pub const INIT_SPACE: usize = 8 + // discriminator
@@ -1076,7 +1993,57 @@ impl Governance {
         1 + 4 + (32 * 10) + // pending_oracle_updates (Option + Vec with max 10 oracles)
         8 +  // oracle_update_proposal_id
         3 +  // treasury_fee_bps (Option<u16> = 1 + 2 bytes)
-        33;  // jupiter_program_id (Option<Pubkey> = 1 + 32 bytes)
+        33 + // jupiter_program_id (Option<Pubkey> = 1 + 32 bytes)
+        8 +  // parameter_change_delay
+        8 +  // treasury_spend_delay
+        8 +  // protocol_upgrade_delay
+        8 +  // emergency_action_delay
+        8 +  // frozen_seconds_total
+        8 +  // frozen_since
+        2 +  // supermajority_bps
+        8;   // proposal_cooldown
+
+    /// True while assets are frozen or the protocol is emergency-paused
+    pub fn is_frozen(&self) -> bool {
+        self.assets_frozen || self.emergency_pause_active
+    }
+
+    /// Cumulative seconds spent frozen or paused, including the current window
+    pub fn frozen_seconds_at(&self, current_time: i64) -> i64 {
+        if self.frozen_since > 0 {
+            self.frozen_seconds_total
+                .saturating_add(current_time.saturating_sub(self.frozen_since).max(0))
+        } else {
+            self.frozen_seconds_total
+        }
+    }
+
+    /// Open or close the frozen window after an emergency action changes the flags
+    pub fn record_frozen_transition(&mut self, was_frozen: bool, current_time: i64) -> Result<()> {
+        let is_frozen = self.is_frozen();
+        if !was_frozen && is_frozen {
+            self.frozen_since = current_time;
+        } else if was_frozen && !is_frozen {
+            let window = current_time
+                .checked_sub(self.frozen_since)
+                .ok_or(GovernanceError::MathOverflow)?;
+            self.frozen_seconds_total = self.frozen_seconds_total
+                .checked_add(window)
+                .ok_or(GovernanceError::MathOverflow)?;
+            self.frozen_since = 0;
+        }
+        Ok(())
+    }
+
+    /// Execution delay applied to a proposal of the given type
+    pub fn execution_delay_for(&self, proposal_type: &ProposalType) -> i64 {
+        match proposal_type {
+            ProposalType::ParameterChange => self.parameter_change_delay,
+            ProposalType::TreasurySpend => self.treasury_spend_delay,
+            ProposalType::ProtocolUpgrade => self.protocol_upgrade_delay,
+            ProposalType::EmergencyAction => self.emergency_action_delay,
+        }
+    }
 }
 
 #[account]
@@ -1115,6 +2082,53 @@ pub struct Governance {
     
     // External program configuration
     pub jupiter_program_id: Option<Pubkey>, // Jupiter aggregator program ID (configurable)
+
+    // Per-proposal-type execution delays (each >= min_execution_delay)
+    pub parameter_change_delay: i64,
+    pub treasury_spend_delay: i64,
+    pub protocol_upgrade_delay: i64,
+    pub emergency_action_delay: i64,
+
+    // Frozen/paused time tracking, excluded from rift staking reward accrual
+    pub frozen_seconds_total: i64, // Closed frozen windows, in seconds
+    pub frozen_since: i64,         // Start of the open frozen window (0 if not frozen)
+
+    // Vote threshold for proposals created with requires_supermajority
+    pub supermajority_bps: u16,    // Share of total votes needed in favour (5001..=10000)
+
+    // Proposal rate limiting
+    pub proposal_cooldown: i64,    // Seconds required between proposals from the same proposer
+}
+
+/// Governance layout before the per-type delays, frozen-time tracking, supermajority and
+/// proposal cooldown fields; read only by migrate_governance
+#[derive(AnchorDeserialize)]
+pub struct LegacyGovernance {
+    pub authority: Pubkey,
+    pub additional_authorities: Vec<Pubkey>,
+    pub required_signatures: u8,
+    pub rifts_mint: Pubkey,
+    pub min_voting_period: i64,
+    pub min_execution_delay: i64,
+    pub total_proposals: u64,
+    pub total_executed: u64,
+    pub max_treasury_spend: u64,
+    pub emergency_pause_active: bool,
+    pub pause_initiated_at: i64,
+    pub pause_duration: i64,
+    pub assets_frozen: bool,
+    pub freeze_initiated_at: i64,
+    pub upgrade_ready_timestamp: i64,
+    pub pending_parameter_changes: Option<LegacyProposalParameterChanges>,
+    pub parameter_change_proposal_id: u64,
+    pub pending_treasury_spend: Option<TreasurySpendInstruction>,
+    pub treasury_spend_proposal_id: u64,
+    pub pending_protocol_upgrade: Option<ProtocolUpgradeInstruction>,
+    pub protocol_upgrade_proposal_id: u64,
+    pub pending_oracle_updates: Option<Vec<Pubkey>>,
+    pub oracle_update_proposal_id: u64,
+    pub treasury_fee_bps: Option<u16>,
+    pub jupiter_program_id: Option<Pubkey>,
 }
 
 impl Proposal {
@@ -1136,7 +2150,33 @@ impl Proposal {
         8 +  // snapshot_taken_at
         8 +  // snapshot_slot
         8 +  // min_participation_required
-        8;   // emergency_expiry_time
+        8 +  // emergency_expiry_time
+        8 +  // snapshot_block_deadline
+        1;   // requires_supermajority
+
+    /// Emergency actions need a 2/3 supermajority of total votes, proposals flagged
+    /// requires_supermajority need `supermajority_bps` of total votes, others a simple majority
+    pub fn majority_met(&self, total_votes: u128, supermajority_bps: u16) -> Result<bool> {
+        match self.proposal_type {
+            ProposalType::EmergencyAction => {
+                let required_supermajority = total_votes
+                    .checked_mul(2)
+                    .and_then(|doubled| doubled.checked_div(3))
+                    .ok_or(GovernanceError::MathOverflow)?;
+                Ok(self.votes_for >= required_supermajority)
+            },
+            _ if self.requires_supermajority => {
+                let weighted_for = self.votes_for
+                    .checked_mul(10000)
+                    .ok_or(GovernanceError::MathOverflow)?;
+                let required = total_votes
+                    .checked_mul(u128::from(supermajority_bps))
+                    .ok_or(GovernanceError::MathOverflow)?;
+                Ok(weighted_for >= required)
+            },
+            _ => Ok(self.votes_for > self.votes_against),
+        }
+    }
 }
 
 #[account]
@@ -1160,6 +2200,8 @@ pub struct Proposal {
     pub snapshot_slot: u64,
     pub min_participation_required: u64,  // Minimum participation for proposal validity
     pub emergency_expiry_time: i64,       // Auto-expiry timestamp for emergency actions
+    pub snapshot_block_deadline: i64,     // Snapshots close and voting opens at this time
+    pub requires_supermajority: bool,     // Pass only with governance.supermajority_bps of total votes
 }
 
 impl VoteRecord {
@@ -1185,15 +2227,80 @@ impl VoteSnapshot {
         8 +  // proposal_id
         32 + // voter
         8 +  // snapshot_power
-        8;   // snapshot_taken_at
+        8 +  // snapshot_taken_at
+        1 +  // consumed
+        32 + // proposal
+        8 +  // unlock_at
+        1;   // bump
+
+    /// Effective power counted by cast_vote (snapshot power, no cap or weighting configured)
+    pub fn effective_voting_power(&self) -> Result<u64> {
+        Ok(self.snapshot_power)
+    }
 }
 
 #[account]
 pub struct VoteSnapshot {
     pub proposal_id: u64,
     pub voter: Pubkey,
-    pub snapshot_power: u64,  // Vote power at proposal creation
+    pub snapshot_power: u64,  // RIFTS escrowed for this snapshot
     pub snapshot_taken_at: i64,
+    pub consumed: bool,       // Counted by a vote (own or a delegate's)
+    pub proposal: Pubkey,     // Proposal account, kept for escrow signing after it is closed
+    pub unlock_at: i64,       // Escrow is releasable after this time (proposal voting_end)
+    pub bump: u8,
+}
+
+impl VoteDelegation {
+    pub const INIT_SPACE: usize = 8 + // discriminator
+        32 + // delegator
+        32 + // delegate
+        8 +  // delegated_at
+        1;   // bump
+}
+
+#[account]
+pub struct VoteDelegation {
+    pub delegator: Pubkey,
+    pub delegate: Pubkey,
+    pub delegated_at: i64,
+    pub bump: u8,
+}
+
+impl RageQuitRecord {
+    pub const INIT_SPACE: usize = 8 + // discriminator
+        32 + // voter
+        32 + // proposal
+        8 +  // proposal_id
+        8 +  // snapshot_power
+        8 +  // recorded_at
+        1;   // bump
+}
+
+/// Dissent signal from an "against" voter on an executed treasury spend
+#[account]
+pub struct RageQuitRecord {
+    pub voter: Pubkey,
+    pub proposal: Pubkey,
+    pub proposal_id: u64,
+    pub snapshot_power: u64,
+    pub recorded_at: i64,
+    pub bump: u8,
+}
+
+impl ProposerRecord {
+    pub const INIT_SPACE: usize = 8 + // discriminator
+        32 + // proposer
+        8 +  // last_proposal_at
+        1;   // bump
+}
+
+/// Last proposal time of a proposer, enforcing governance.proposal_cooldown
+#[account]
+pub struct ProposerRecord {
+    pub proposer: Pubkey,
+    pub last_proposal_at: i64,
+    pub bump: u8,
 }
 
 impl MultisigSignatureState {
@@ -1237,6 +2344,38 @@ pub enum VoteChoice {
     Against,
 }
 
+/// Proposal result as reported by proposal_outcome
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum OutcomeStatus {
+    Pending, // Voting still active
+    Passing, // Quorum and majority met after voting ended
+    Failing, // Voting ended without quorum or majority
+}
+
+/// Optional settings for initialize_governance; unset fields fall back to defaults
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Default)]
+pub struct InitGovernanceParams {
+    pub parameter_change_delay: Option<i64>, // Defaults to min_execution_delay
+    pub treasury_spend_delay: Option<i64>,
+    pub protocol_upgrade_delay: Option<i64>,
+    pub emergency_action_delay: Option<i64>,
+    pub supermajority_bps: Option<u16>, // Defaults to DEFAULT_SUPERMAJORITY_BPS
+}
+
+/// Return data for proposal_outcome
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct ProposalOutcome {
+    pub proposal_id: u64,
+    pub status: OutcomeStatus,
+    pub voting_ended: bool,
+    pub participation_met: bool,
+    pub majority_met: bool, // Simple majority, or supermajority for emergency actions and flagged proposals
+    pub total_votes: u128,
+    pub votes_for: u128,
+    pub votes_against: u128,
+    pub executable_at: i64, // voting_end plus the type-specific execution delay
+}
+
 // Events
 #[event]
 pub struct ProposalCreated {
@@ -1254,6 +2393,36 @@ pub struct VoteCast {
     pub voting_power: u64,
 }
 
+#[event]
+pub struct DelegatedVotesCounted {
+    pub proposal_id: u64,
+    pub delegate: Pubkey,
+    pub delegators: u32,
+    pub delegated_power: u64,
+}
+
+#[event]
+pub struct VotesDelegated {
+    pub delegator: Pubkey,
+    pub delegate: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DelegationRevoked {
+    pub delegator: Pubkey,
+    pub delegate: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RageQuitRecorded {
+    pub proposal_id: u64,
+    pub voter: Pubkey,
+    pub snapshot_power: u64,
+    pub recorded_at: i64,
+}
+
 #[event]
 pub struct VoteSnapshotCreated {
     pub proposal_id: u64,
@@ -1262,6 +2431,14 @@ pub struct VoteSnapshotCreated {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct VoteSnapshotReleased {
+    pub proposal_id: u64,
+    pub voter: Pubkey,
+    pub amount: u64,
+    pub released_at: i64,
+}
+
 // Enhanced audit trail events
 #[event]
 pub struct GovernanceInitialized {
@@ -1318,6 +2495,23 @@ pub struct ProposalCancelled {
     pub cancelled_by: Pubkey,
 }
 
+#[event]
+pub struct ProposalClosed {
+    pub proposal_id: u64,
+    pub closed_by: Pubkey,
+    pub rent_recipient: Pubkey,
+    pub closed_at: i64,
+}
+
+#[event]
+pub struct TreasurySpendDisbursed {
+    pub proposal_id: u64,
+    pub recipient: Pubkey,
+    pub amount: u64,
+    pub token_mint: Pubkey,
+    pub executor: Pubkey,
+}
+
 #[event]
 pub struct ParameterChangeExecuted {
     pub proposal_id: u64,
@@ -1358,6 +2552,52 @@ pub struct ProposalParameterChanges {
     pub oracle_update_interval: Option<i64>,
     pub max_rebalance_interval: Option<i64>,
     pub arbitrage_threshold_bps: Option<u16>,
+    pub protocol_admin: Option<Pubkey>, // NEW: Rotate the rifts-protocol admin authority
+    pub rifts_lp_share_bps: Option<u16>,   // Share of bought-back RIFTS sent to LP stakers
+    pub rifts_burn_share_bps: Option<u16>, // Share of bought-back RIFTS burned
+    pub backing_ratio: Option<u64>,        // Manual backing_ratio applied by rifts-protocol set_backing_ratio
+    pub backing_ratio_rift: Option<Pubkey>, // The only rift backing_ratio may be applied to
+    pub proposal_cooldown: Option<i64>,    // Seconds between proposals from the same proposer
+    pub unstake_cooldown: Option<u32>,     // lp-staking unstake_cooldown applied by set_unstake_cooldown
+    pub unstake_cooldown_pool: Option<Pubkey>, // The only staking pool unstake_cooldown may be applied to
+}
+
+/// ProposalParameterChanges as stored by pre-migration Governance accounts
+#[derive(AnchorDeserialize)]
+pub struct LegacyProposalParameterChanges {
+    pub burn_fee_bps: Option<u16>,
+    pub partner_fee_bps: Option<u16>,
+    pub wrap_fee_bps: Option<u16>,
+    pub unwrap_fee_bps: Option<u16>,
+    pub treasury_fee_bps: Option<u16>,
+    pub jupiter_program_id: Option<Pubkey>,
+    pub oracle_update_interval: Option<i64>,
+    pub max_rebalance_interval: Option<i64>,
+    pub arbitrage_threshold_bps: Option<u16>,
+}
+
+impl From<LegacyProposalParameterChanges> for ProposalParameterChanges {
+    fn from(legacy: LegacyProposalParameterChanges) -> Self {
+        Self {
+            burn_fee_bps: legacy.burn_fee_bps,
+            partner_fee_bps: legacy.partner_fee_bps,
+            wrap_fee_bps: legacy.wrap_fee_bps,
+            unwrap_fee_bps: legacy.unwrap_fee_bps,
+            treasury_fee_bps: legacy.treasury_fee_bps,
+            jupiter_program_id: legacy.jupiter_program_id,
+            oracle_update_interval: legacy.oracle_update_interval,
+            max_rebalance_interval: legacy.max_rebalance_interval,
+            arbitrage_threshold_bps: legacy.arbitrage_threshold_bps,
+            protocol_admin: None,
+            rifts_lp_share_bps: None,
+            rifts_burn_share_bps: None,
+            backing_ratio: None,
+            backing_ratio_rift: None,
+            proposal_cooldown: None,
+            unstake_cooldown: None,
+            unstake_cooldown_pool: None,
+        }
+    }
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone)]
@@ -1537,4 +2777,48 @@ pub enum GovernanceError {
     UnauthorizedSigner,
     #[msg("Invalid proposal ID or state")]
     InvalidProposal,
+    #[msg("Invalid vote delegation")]
+    InvalidDelegation,
+    #[msg("Delegator listed more than once")]
+    DuplicateDelegation,
+    #[msg("Too many delegations in a single vote")]
+    TooManyDelegations,
+    #[msg("Delegation was made after the delegator's snapshot")]
+    DelegationAfterSnapshot,
+    #[msg("Snapshot power has already been counted")]
+    SnapshotAlreadyCounted,
+    #[msg("Proposal is not a treasury spend")]
+    NotTreasurySpendProposal,
+    #[msg("Proposal has not been executed")]
+    ProposalNotExecuted,
+    #[msg("Only voters who voted against can record a rage quit")]
+    NotAgainstVote,
+    #[msg("Voting opens once the snapshot window closes")]
+    SnapshotWindowOpen,
+    #[msg("Only the proposer or governance authority can close a proposal")]
+    UnauthorizedClose,
+    #[msg("Only executed or cancelled proposals can be closed")]
+    ProposalNotClosable,
+    #[msg("Proposal cannot be closed until its retention period has passed")]
+    ProposalCloseTooEarly,
+    #[msg("Supermajority must be between 5001 and 10000 bps")]
+    InvalidSupermajority,
+    #[msg("No approved treasury spend is pending")]
+    NoPendingTreasurySpend,
+    #[msg("Treasury spend does not match the approved proposal")]
+    TreasurySpendMismatch,
+    #[msg("Treasury vault balance is below the approved spend")]
+    InsufficientTreasuryBalance,
+    #[msg("Proposer must wait for the proposal cooldown to elapse")]
+    ProposalCooldownActive,
+    #[msg("Governance account already uses the current layout")]
+    GovernanceAlreadyMigrated,
+    #[msg("Account is not a pre-migration Governance")]
+    InvalidLegacyGovernance,
+    #[msg("Proposal account already uses the current layout")]
+    ProposalAlreadyMigrated,
+    #[msg("Snapshot amount must be positive and within the voter's balance")]
+    InvalidSnapshotAmount,
+    #[msg("Snapshot escrow is locked until the proposal's voting ends")]
+    SnapshotStillLocked,
 }
\ No newline at end of file
@@ -7,6 +7,15 @@ declare_id!("9dNaLvEDeq3mo4TS2GDuJTeYQqz7GdeKYnyGmcKcWCr2");
 // **SECURITY FIX**: Define precision constant for reward calculations
 const PRECISION: u64 = 1_000_000_000_000; // 1e12 for high precision math
 
+// Longest allowed request → withdraw delay for the two-step unstake flow
+const MAX_UNSTAKE_COOLDOWN: i64 = 30 * 24 * 3600; // 30 days
+
+// Longest pause_reason accepted by pause_pool
+const MAX_PAUSE_REASON_LEN: usize = 200;
+
+// Seconds per year used to annualize rewards_per_second
+const SECONDS_PER_YEAR: u128 = 365 * 24 * 3600;
+
 #[program]
 pub mod lp_staking {
     use super::*;
@@ -16,6 +25,7 @@ pub mod lp_staking {
         rewards_per_second: u64,
         min_stake_duration: i64,
         rifts_protocol: Pubkey,
+        max_stake_per_user: u64,
     ) -> Result<()> {
         let pool = &mut ctx.accounts.staking_pool;
         
@@ -31,6 +41,9 @@ pub mod lp_staking {
             StakingError::InvalidRewardsRate
         );
         pool.rewards_per_second = rewards_per_second;
+
+        validate_stake_cap(max_stake_per_user, rewards_per_second)?;
+        pool.max_stake_per_user = max_stake_per_user;
         
         // **SECURITY FIX**: Validate minimum stake duration
         require!(
@@ -44,6 +57,24 @@ pub mod lp_staking {
         pool.rifts_protocol = rifts_protocol; // Set authorized RIFTS protocol
         pool.total_rewards_available = 0;
         pool.last_reward_deposit = 0;
+        pool.unstake_cooldown = 0; // Two-step unstake disabled by default
+        pool.governance = Pubkey::default(); // Set once by bind_governance
+        pool.secondary_reward_token_mint = None; // Single-reward pool until configured
+        pool.secondary_rewards_per_second = 0;
+        pool.secondary_accumulated_rewards_per_share = 0;
+        pool.secondary_rewards_available = 0;
+        pool.reward_shortfall = 0;
+        pool.reset_epoch = 0;
+        pool.pre_reset_accumulator = 0;
+
+        // Genesis entry so indexers have the full rate history
+        emit!(RewardsRateUpdated {
+            pool: pool.key(),
+            old_rate: 0,
+            new_rate: rewards_per_second,
+            authority: ctx.accounts.authority.key(),
+            timestamp: pool.last_update_time,
+        });
         
         Ok(())
     }
@@ -83,6 +114,10 @@ pub mod lp_staking {
         user_stake.pending_rewards = user_stake.pending_rewards
             .checked_add(pending)
             .ok_or(StakingError::MathOverflow)?;
+        let secondary_pending = calculate_pending_secondary_rewards(user_stake, pool)?;
+        user_stake.secondary_pending_rewards = user_stake.secondary_pending_rewards
+            .checked_add(secondary_pending)
+            .ok_or(StakingError::MathOverflow)?;
         
         // Transfer LP tokens to pool
         // Validate program ID before CPI call
@@ -97,7 +132,11 @@ pub mod lp_staking {
         let new_user_amount = user_stake.amount
             .checked_add(amount)
             .ok_or(StakingError::MathOverflow)?;
-        let new_reward_debt = (u128::from(new_user_amount))
+        require!(
+            pool.max_stake_per_user == 0 || new_user_amount <= pool.max_stake_per_user,
+            StakingError::StakeCapExceeded
+        );
+        let new_reward_debt = (u128::from(new_user_amount.saturating_sub(user_stake.unstake_requested_amount)))
             .checked_mul(pool.accumulated_rewards_per_share)
             .ok_or(StakingError::MathOverflow)?
             .checked_div(u128::from(PRECISION))
@@ -113,6 +152,8 @@ pub mod lp_staking {
         // Update state variables (effects)
         user_stake.amount = new_user_amount;
         user_stake.reward_debt = new_reward_debt;
+        user_stake.reset_epoch = pool.reset_epoch;
+        user_stake.secondary_reward_debt = secondary_reward_debt(user_stake.earning_amount(), pool)?;
         pool.total_staked = new_pool_total;
 
         // Transfer LP tokens from user to pool vault (interactions)
@@ -155,6 +196,25 @@ pub mod lp_staking {
             StakingError::InsufficientStake
         );
         
+        // Two-step unstake: amount must have been requested and the cooldown elapsed
+        if pool.unstake_cooldown > 0 {
+            require!(
+                user_stake.unstake_requested_amount > 0,
+                StakingError::UnstakeNotRequested
+            );
+            require!(
+                amount <= user_stake.unstake_requested_amount,
+                StakingError::ExceedsUnstakeRequest
+            );
+            let unlocked_at = user_stake.unstake_requested_at
+                .checked_add(pool.unstake_cooldown)
+                .ok_or(StakingError::MathOverflow)?;
+            require!(
+                clock.unix_timestamp >= unlocked_at,
+                StakingError::UnstakeCooldownActive
+            );
+        }
+        
         // Update pool rewards
         update_pool_rewards(pool, clock.unix_timestamp)?;
         
@@ -163,6 +223,10 @@ pub mod lp_staking {
         user_stake.pending_rewards = user_stake.pending_rewards
             .checked_add(pending)
             .ok_or(StakingError::MathOverflow)?;
+        let secondary_pending = calculate_pending_secondary_rewards(user_stake, pool)?;
+        user_stake.secondary_pending_rewards = user_stake.secondary_pending_rewards
+            .checked_add(secondary_pending)
+            .ok_or(StakingError::MathOverflow)?;
         
         // Transfer LP tokens back to user
         let pool_key = pool.key();
@@ -181,11 +245,17 @@ pub mod lp_staking {
         
         // **CRITICAL REENTRANCY FIX**: Update state BEFORE CPI call (checks-effects-interactions pattern)
 
+        // Requested stake is withdrawn first; it already left the pool's earning total
+        let from_request = amount.min(user_stake.unstake_requested_amount);
+        let new_requested_amount = user_stake.unstake_requested_amount
+            .checked_sub(from_request)
+            .ok_or(StakingError::MathOverflow)?;
+
         // **CRITICAL FIX**: Update user stake with checked arithmetic FIRST
         let new_user_amount = user_stake.amount
             .checked_sub(amount)
             .ok_or(StakingError::MathOverflow)?;
-        let new_reward_debt = (u128::from(new_user_amount))
+        let new_reward_debt = (u128::from(new_user_amount.saturating_sub(new_requested_amount)))
             .checked_mul(pool.accumulated_rewards_per_share)
             .ok_or(StakingError::MathOverflow)?
             .checked_div(u128::from(PRECISION))
@@ -195,12 +265,18 @@ pub mod lp_staking {
 
         // **CRITICAL FIX**: Update pool total with checked arithmetic FIRST
         let new_pool_total = pool.total_staked
-            .checked_sub(amount)
+            .checked_sub(amount - from_request)
             .ok_or(StakingError::MathOverflow)?;
 
         // Update state variables (effects)
         user_stake.amount = new_user_amount;
+        user_stake.unstake_requested_amount = new_requested_amount;
+        if new_requested_amount == 0 {
+            user_stake.unstake_requested_at = 0;
+        }
         user_stake.reward_debt = new_reward_debt;
+        user_stake.reset_epoch = pool.reset_epoch;
+        user_stake.secondary_reward_debt = secondary_reward_debt(user_stake.earning_amount(), pool)?;
         pool.total_staked = new_pool_total;
 
         // Transfer LP tokens from pool vault to user (interactions)
@@ -224,6 +300,134 @@ pub mod lp_staking {
         Ok(())
     }
 
+    /// Start the unstake cooldown for `amount` (only when the pool has an unstake_cooldown)
+    /// A new request replaces any previous one and restarts the cooldown
+    /// The requested amount stops earning rewards immediately
+    pub fn request_unstake(ctx: Context<RequestUnstake>, amount: u64) -> Result<()> {
+        let pool = &mut ctx.accounts.staking_pool;
+        let user_stake = &mut ctx.accounts.user_stake_account;
+        
+        require!(pool.unstake_cooldown > 0, StakingError::UnstakeCooldownDisabled);
+        require!(amount > 0, StakingError::InvalidAmount);
+        require!(
+            user_stake.amount >= amount,
+            StakingError::InsufficientStake
+        );
+        
+        let current_time = Clock::get()?.unix_timestamp;
+
+        // Settle rewards earned so far before the earning amount changes
+        update_pool_rewards(pool, current_time)?;
+        let pending = calculate_pending_rewards(user_stake, pool)?;
+        user_stake.pending_rewards = user_stake.pending_rewards
+            .checked_add(pending)
+            .ok_or(StakingError::MathOverflow)?;
+        let secondary_pending = calculate_pending_secondary_rewards(user_stake, pool)?;
+        user_stake.secondary_pending_rewards = user_stake.secondary_pending_rewards
+            .checked_add(secondary_pending)
+            .ok_or(StakingError::MathOverflow)?;
+
+        // Any previous request rejoins the earning total before the new one leaves it
+        pool.total_staked = pool.total_staked
+            .checked_add(user_stake.unstake_requested_amount)
+            .ok_or(StakingError::MathOverflow)?
+            .checked_sub(amount)
+            .ok_or(StakingError::MathOverflow)?;
+
+        user_stake.unstake_requested_at = current_time;
+        user_stake.unstake_requested_amount = amount;
+
+        let earning = user_stake.earning_amount();
+        user_stake.reward_debt = (u128::from(earning))
+            .checked_mul(pool.accumulated_rewards_per_share)
+            .ok_or(StakingError::MathOverflow)?
+            .checked_div(u128::from(PRECISION))
+            .ok_or(StakingError::MathOverflow)?
+            .try_into()
+            .map_err(|_| StakingError::MathOverflow)?;
+        user_stake.reset_epoch = pool.reset_epoch;
+        user_stake.secondary_reward_debt = secondary_reward_debt(earning, pool)?;
+        
+        emit!(UnstakeRequested {
+            user: ctx.accounts.user.key(),
+            pool: pool.key(),
+            amount,
+            available_at: current_time
+                .checked_add(pool.unstake_cooldown)
+                .ok_or(StakingError::MathOverflow)?,
+        });
+        
+        Ok(())
+    }
+
+    /// Bind the pool to the governance whose proposals set unstake_cooldown (authority, once)
+    pub fn bind_governance(ctx: Context<BindGovernance>) -> Result<()> {
+        let pool = &mut ctx.accounts.staking_pool;
+        require!(pool.governance == Pubkey::default(), StakingError::GovernanceAlreadyBound);
+
+        pool.governance = ctx.accounts.governance.key();
+
+        msg!("Staking pool bound to governance {}", pool.governance);
+
+        Ok(())
+    }
+
+    /// Apply a governance-approved request → withdraw delay for unstaking (0 = instant unstake)
+    pub fn set_unstake_cooldown(
+        ctx: Context<SetUnstakeCooldown>,
+        proposal_id: u64,
+    ) -> Result<()> {
+        let governance = &ctx.accounts.governance;
+        let proposal = &ctx.accounts.proposal;
+
+        // The proposal must be the executed parameter change pending in the pool's governance
+        require!(
+            governance.parameter_change_proposal_id == proposal_id && proposal.id == proposal_id,
+            StakingError::ProposalMismatch
+        );
+        require!(
+            proposal.proposal_type == governance::ProposalType::ParameterChange
+                && proposal.status == governance::ProposalStatus::Executed,
+            StakingError::ProposalNotExecuted
+        );
+
+        let changes = governance.pending_parameter_changes
+            .as_ref()
+            .ok_or(StakingError::NoPendingUnstakeCooldown)?;
+        let unstake_cooldown = i64::from(
+            changes.unstake_cooldown.ok_or(StakingError::NoPendingUnstakeCooldown)?
+        );
+        require!(
+            changes.unstake_cooldown_pool == Some(ctx.accounts.staking_pool.key()),
+            StakingError::ProposalNotForThisPool
+        );
+        require!(
+            (0..=MAX_UNSTAKE_COOLDOWN).contains(&unstake_cooldown),
+            StakingError::InvalidUnstakeCooldown
+        );
+        
+        let pool = &mut ctx.accounts.staking_pool;
+        pool.unstake_cooldown = unstake_cooldown;
+        
+        msg!("Unstake cooldown set to {} seconds by proposal {}", unstake_cooldown, proposal_id);
+        
+        Ok(())
+    }
+
+    /// Set the per-user stake cap (0 = unlimited); existing stakes above a lowered cap are kept
+    pub fn set_max_stake_per_user(
+        ctx: Context<SetMaxStakePerUser>,
+        max_stake_per_user: u64,
+    ) -> Result<()> {
+        let pool = &mut ctx.accounts.staking_pool;
+        validate_stake_cap(max_stake_per_user, pool.rewards_per_second)?;
+        pool.max_stake_per_user = max_stake_per_user;
+        
+        msg!("Max stake per user set to {}", max_stake_per_user);
+        
+        Ok(())
+    }
+
     /// Deposit RIFTS rewards from the fee distribution system
     /// This allows the RIFTS protocol to send actual tokens to be distributed to stakers
     pub fn deposit_rewards(ctx: Context<DepositRewards>, amount: u64) -> Result<()> {
@@ -275,6 +479,10 @@ pub mod lp_staking {
         });
         
         msg!("💰 Deposited {} RIFTS rewards to LP staking pool", amount);
+        if pool.reward_shortfall > 0 {
+            msg!("Outstanding partial-claim shortfall: {} ({} now available)",
+                 pool.reward_shortfall, pool.total_rewards_available);
+        }
         
         Ok(())
     }
@@ -338,7 +546,7 @@ pub mod lp_staking {
             .ok_or(StakingError::MathOverflow)?;
 
         // Calculate new reward debt FIRST
-        let new_reward_debt = (u128::from(user_stake.amount))
+        let new_reward_debt = (u128::from(user_stake.earning_amount()))
             .checked_mul(pool.accumulated_rewards_per_share)
             .ok_or(StakingError::MathOverflow)?
             .checked_div(u128::from(PRECISION))
@@ -348,8 +556,11 @@ pub mod lp_staking {
 
         // Update state variables (effects)
         pool.total_rewards_available = new_pool_rewards;
+        pool.reward_shortfall = pool.reward_shortfall.saturating_sub(user_stake.reward_shortfall);
         user_stake.pending_rewards = 0;
         user_stake.reward_debt = new_reward_debt;
+        user_stake.reset_epoch = pool.reset_epoch;
+        user_stake.reward_shortfall = 0;
 
         // Transfer reward tokens from vault to user (interactions)
         token::transfer(cpi_ctx, total_rewards)?;
@@ -362,6 +573,201 @@ pub mod lp_staking {
         Ok(())
     }
 
+    /// Claim as much of the pending rewards as the vault can cover
+    /// The unpaid remainder stays in pending_rewards and is recorded in the pool's
+    /// reward_shortfall so it can be claimed once deposit_rewards tops the vault up
+    pub fn claim_rewards_partial(ctx: Context<ClaimRewards>) -> Result<()> {
+        let pool = &mut ctx.accounts.staking_pool;
+        
+        require!(!pool.is_paused, StakingError::PoolPaused);
+        
+        let user_stake = &mut ctx.accounts.user_stake_account;
+        let clock = Clock::get()?;
+        
+        update_pool_rewards(pool, clock.unix_timestamp)?;
+        
+        let pending = calculate_pending_rewards(user_stake, pool)?;
+        let total_rewards = user_stake.pending_rewards
+            .checked_add(pending)
+            .ok_or(StakingError::MathOverflow)?;
+        
+        require!(total_rewards > 0, StakingError::NoRewards);
+        
+        // Pay whatever the vault holds, up to the full amount owed
+        let claimed = total_rewards.min(pool.total_rewards_available);
+        require!(claimed > 0, StakingError::InsufficientRewardsInVault);
+        let remaining = total_rewards - claimed;
+        
+        let pool_key = pool.key();
+        let seeds = &[
+            b"reward_authority",
+            pool_key.as_ref(),
+            &[ctx.bumps.reward_authority],
+        ];
+        let signer_seeds = &[&seeds[..]];
+        
+        require!(
+            ctx.accounts.token_program.key() == anchor_spl::token::ID,
+            StakingError::InvalidProgramId
+        );
+        
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.pool_reward_vault.to_account_info(),
+                to: ctx.accounts.user_reward_tokens.to_account_info(),
+                authority: ctx.accounts.reward_authority.to_account_info(),
+            },
+            signer_seeds,
+        );
+
+        let new_reward_debt = (u128::from(user_stake.earning_amount()))
+            .checked_mul(pool.accumulated_rewards_per_share)
+            .ok_or(StakingError::MathOverflow)?
+            .checked_div(u128::from(PRECISION))
+            .ok_or(StakingError::MathOverflow)?
+            .try_into()
+            .map_err(|_| StakingError::MathOverflow)?;
+
+        // Effects before the transfer; the shortfall replaces this user's previous entry
+        pool.total_rewards_available = pool.total_rewards_available
+            .checked_sub(claimed)
+            .ok_or(StakingError::MathOverflow)?;
+        pool.reward_shortfall = pool.reward_shortfall
+            .saturating_sub(user_stake.reward_shortfall)
+            .checked_add(remaining)
+            .ok_or(StakingError::MathOverflow)?;
+        user_stake.pending_rewards = remaining;
+        user_stake.reward_debt = new_reward_debt;
+        user_stake.reset_epoch = pool.reset_epoch;
+        user_stake.reward_shortfall = remaining;
+
+        token::transfer(cpi_ctx, claimed)?;
+        
+        emit!(PartialClaim {
+            user: ctx.accounts.user.key(),
+            pool: pool_key,
+            claimed,
+            remaining,
+        });
+        
+        Ok(())
+    }
+
+    /// Enable a second reward token on the pool (authority only, once)
+    pub fn initialize_secondary_rewards(
+        ctx: Context<InitializeSecondaryRewards>,
+        secondary_rewards_per_second: u64,
+    ) -> Result<()> {
+        let pool = &mut ctx.accounts.staking_pool;
+
+        require!(
+            pool.secondary_reward_token_mint.is_none(),
+            StakingError::SecondaryRewardsAlreadyConfigured
+        );
+        require!(
+            ctx.accounts.secondary_reward_token_mint.key() != pool.reward_token_mint,
+            StakingError::InvalidMint
+        );
+        require!(
+            secondary_rewards_per_second <= 1_000_000_000_000,
+            StakingError::InvalidRewardsRate
+        );
+
+        // Bring both accumulators up to date before the secondary rate starts
+        update_pool_rewards(pool, Clock::get()?.unix_timestamp)?;
+
+        pool.secondary_reward_token_mint = Some(ctx.accounts.secondary_reward_token_mint.key());
+        pool.secondary_rewards_per_second = secondary_rewards_per_second;
+
+        Ok(())
+    }
+
+    /// Fund the secondary reward vault (anyone may deposit the secondary token)
+    pub fn deposit_secondary_rewards(ctx: Context<DepositSecondaryRewards>, amount: u64) -> Result<()> {
+        require!(amount > 0, StakingError::InvalidAmount);
+
+        let cpi_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.depositor_token_account.to_account_info(),
+                to: ctx.accounts.secondary_reward_vault.to_account_info(),
+                authority: ctx.accounts.depositor.to_account_info(),
+            },
+        );
+        token::transfer(cpi_ctx, amount)?;
+
+        let pool = &mut ctx.accounts.staking_pool;
+        pool.secondary_rewards_available = pool.secondary_rewards_available
+            .checked_add(amount)
+            .ok_or(StakingError::MathOverflow)?;
+
+        emit!(SecondaryRewardsDeposited {
+            pool: pool.key(),
+            depositor: ctx.accounts.depositor.key(),
+            amount,
+            total_available: pool.secondary_rewards_available,
+        });
+
+        Ok(())
+    }
+
+    /// Claim accrued secondary reward tokens
+    pub fn claim_secondary_rewards(ctx: Context<ClaimSecondaryRewards>) -> Result<()> {
+        let pool = &mut ctx.accounts.staking_pool;
+
+        require!(!pool.is_paused, StakingError::PoolPaused);
+
+        let user_stake = &mut ctx.accounts.user_stake_account;
+        let clock = Clock::get()?;
+
+        update_pool_rewards(pool, clock.unix_timestamp)?;
+
+        let pending = calculate_pending_secondary_rewards(user_stake, pool)?;
+        let total_rewards = user_stake.secondary_pending_rewards
+            .checked_add(pending)
+            .ok_or(StakingError::MathOverflow)?;
+
+        require!(total_rewards > 0, StakingError::NoRewards);
+        require!(
+            pool.secondary_rewards_available >= total_rewards,
+            StakingError::InsufficientRewardsInVault
+        );
+
+        // Update state BEFORE CPI call
+        pool.secondary_rewards_available = pool.secondary_rewards_available
+            .checked_sub(total_rewards)
+            .ok_or(StakingError::MathOverflow)?;
+        user_stake.secondary_pending_rewards = 0;
+        user_stake.secondary_reward_debt = secondary_reward_debt(user_stake.earning_amount(), pool)?;
+
+        let pool_key = pool.key();
+        let seeds = &[
+            b"reward_authority",
+            pool_key.as_ref(),
+            &[ctx.bumps.reward_authority],
+        ];
+        let signer_seeds = &[&seeds[..]];
+
+        let cpi_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.secondary_reward_vault.to_account_info(),
+                to: ctx.accounts.user_reward_tokens.to_account_info(),
+                authority: ctx.accounts.reward_authority.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::transfer(cpi_ctx, total_rewards)?;
+
+        emit!(SecondaryClaimEvent {
+            user: ctx.accounts.user.key(),
+            amount: total_rewards,
+        });
+
+        Ok(())
+    }
+
     /// Reset rewards accumulator when it approaches bounds (governance only)
     pub fn reset_rewards_accumulator(ctx: Context<ResetAccumulator>) -> Result<()> {
         let pool = &mut ctx.accounts.staking_pool;
@@ -379,8 +785,7 @@ pub mod lp_staking {
         msg!("🔄 GOVERNANCE RESET: Rewards accumulator reset from {} to 0", 
              pool.accumulated_rewards_per_share);
         
-        // Reset to zero - all pending rewards should be claimed first
-        pool.accumulated_rewards_per_share = 0;
+        reset_accumulator(pool)?;
         
         emit!(AccumulatorReset {
             pool: pool.key(),
@@ -391,6 +796,96 @@ pub mod lp_staking {
         Ok(())
     }
 
+    /// Pause staking, unstaking and claims (pool authority only)
+    /// While paused, emergency_withdraw becomes available and no rewards accrue
+    pub fn pause_pool(ctx: Context<SetPoolPause>, pause_reason: String) -> Result<()> {
+        let pool = &mut ctx.accounts.staking_pool;
+        
+        require!(!pool.is_paused, StakingError::PoolPaused);
+        require!(pause_reason.len() <= MAX_PAUSE_REASON_LEN, StakingError::PauseReasonTooLong);
+        
+        // Settle accrual up to the pause
+        let current_time = Clock::get()?.unix_timestamp;
+        update_pool_rewards(pool, current_time)?;
+        pool.is_paused = true;
+        
+        emit!(PoolPaused {
+            pool: pool.key(),
+            authority: ctx.accounts.authority.key(),
+            pause_reason,
+            timestamp: current_time,
+        });
+        
+        Ok(())
+    }
+
+    /// Resume a paused pool (pool authority only)
+    pub fn unpause_pool(ctx: Context<SetPoolPause>) -> Result<()> {
+        let pool = &mut ctx.accounts.staking_pool;
+        
+        require!(pool.is_paused, StakingError::PoolNotPaused);
+        
+        // Paused time earns nothing: restart accrual from now
+        let current_time = Clock::get()?.unix_timestamp;
+        pool.last_update_time = current_time;
+        pool.is_paused = false;
+        
+        emit!(PoolUnpaused {
+            pool: pool.key(),
+            authority: ctx.accounts.authority.key(),
+            timestamp: current_time,
+        });
+        
+        Ok(())
+    }
+
+    /// Read-only: token-denominated APY of the primary reward, in basis points
+    /// Annual emissions (in whole reward tokens) per whole LP token staked; ignores
+    /// relative prices. Returns a borsh-encoded ApyQuote via return data.
+    pub fn current_apy(ctx: Context<CurrentApy>) -> Result<()> {
+        let pool = &ctx.accounts.staking_pool;
+        
+        // No stake: the APY is unbounded, so report 0 and flag it
+        let quote = if pool.total_staked == 0 {
+            ApyQuote {
+                apy_bps: 0,
+                has_stake: false,
+                total_staked: 0,
+                rewards_per_second: pool.rewards_per_second,
+            }
+        } else {
+            // Only absurd decimals or rates overflow; those saturate like any huge APY
+            let apy_bps = 10u128
+                .checked_pow(u32::from(ctx.accounts.lp_token_mint.decimals))
+                .and_then(|lp_scale| {
+                    u128::from(pool.rewards_per_second)
+                        .checked_mul(SECONDS_PER_YEAR)?
+                        .checked_mul(10000)?
+                        .checked_mul(lp_scale)
+                })
+                .and_then(|scaled| {
+                    let reward_scale = 10u128
+                        .checked_pow(u32::from(ctx.accounts.reward_token_mint.decimals))?;
+                    scaled
+                        .checked_div(u128::from(pool.total_staked))?
+                        .checked_div(reward_scale)
+                })
+                .map_or(u64::MAX, |bps| u64::try_from(bps).unwrap_or(u64::MAX));
+            ApyQuote {
+                apy_bps,
+                has_stake: true,
+                total_staked: pool.total_staked,
+                rewards_per_second: pool.rewards_per_second,
+            }
+        };
+        
+        msg!("Current APY: {} bps (staked: {})", quote.apy_bps, quote.total_staked);
+        
+        anchor_lang::solana_program::program::set_return_data(&quote.try_to_vec()?);
+        
+        Ok(())
+    }
+
     /// **SECURITY FIX**: Emergency withdraw function for governance
     pub fn emergency_withdraw(
         ctx: Context<EmergencyWithdraw>,
@@ -411,6 +906,12 @@ pub mod lp_staking {
         // Validate withdrawal amount
         require!(amount <= user_stake.amount, StakingError::InsufficientStake);
         
+        // Requested stake is already excluded from the pool total
+        let from_request = amount.min(user_stake.unstake_requested_amount);
+        user_stake.unstake_requested_amount = user_stake.unstake_requested_amount
+            .checked_sub(from_request)
+            .ok_or(StakingError::MathOverflow)?;
+
         // Update user stake
         user_stake.amount = user_stake.amount
             .checked_sub(amount)
@@ -418,7 +919,7 @@ pub mod lp_staking {
         
         // Update pool total
         pool.total_staked = pool.total_staked
-            .checked_sub(amount)
+            .checked_sub(amount - from_request)
             .ok_or(StakingError::MathOverflow)?;
         
         // Transfer LP tokens back to user (emergency bypass of normal unstaking logic)
@@ -488,13 +989,66 @@ pub mod lp_staking {
             StakingError::InvalidRewardsRate
         );
         
+        // A configured stake cap must stay sane against the new rate
+        validate_stake_cap(pool.max_stake_per_user, new_rewards_per_second)?;
+        
         // Update accumulated rewards before changing rate
         update_pool_rewards(pool, clock.unix_timestamp)?;
         
+        let old_rate = pool.rewards_per_second;
         pool.rewards_per_second = new_rewards_per_second;
+
+        emit!(RewardsRateUpdated {
+            pool: pool.key(),
+            old_rate,
+            new_rate: new_rewards_per_second,
+            authority: ctx.accounts.authority.key(),
+            timestamp: clock.unix_timestamp,
+        });
         
         Ok(())
     }
+
+    /// Grow a StakingPool or UserStakeAccount created before the current layout to its INIT_SPACE
+    /// Every field was appended with a zero default (no cooldown, no secondary reward, no cap,
+    /// reset epoch 0), so zero-extending is the whole migration. Permissionless: the payer only
+    /// tops up rent.
+    pub fn migrate_staking_account(ctx: Context<MigrateStakingAccount>) -> Result<()> {
+        let account = ctx.accounts.account.to_account_info();
+
+        let discriminator: [u8; 8] = account.try_borrow_data()?[..8]
+            .try_into()
+            .map_err(|_| StakingError::InvalidLegacyAccount)?;
+        let new_len = if discriminator == *StakingPool::DISCRIMINATOR {
+            StakingPool::INIT_SPACE
+        } else if discriminator == *UserStakeAccount::DISCRIMINATOR {
+            UserStakeAccount::INIT_SPACE
+        } else {
+            return err!(StakingError::InvalidLegacyAccount);
+        };
+        require!(account.data_len() < new_len, StakingError::AlreadyMigrated);
+
+        let rent_needed = Rent::get()?
+            .minimum_balance(new_len)
+            .saturating_sub(account.lamports());
+        if rent_needed > 0 {
+            anchor_lang::system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: ctx.accounts.payer.to_account_info(),
+                        to: account.clone(),
+                    },
+                ),
+                rent_needed,
+            )?;
+        }
+        account.resize(new_len)?;
+
+        msg!("Migrated staking account {} to {} bytes", account.key(), new_len);
+
+        Ok(())
+    }
 }
 
 // Re-export account types for CPI - removed duplicate export
@@ -579,37 +1133,120 @@ fn update_pool_rewards(pool: &mut Account<StakingPool>, current_time: i64) -> Re
     }
     
     pool.accumulated_rewards_per_share = new_accumulated;
+
+    // Secondary reward accumulator advances over the same bounded window
+    if pool.secondary_reward_token_mint.is_some() && pool.secondary_rewards_per_second > 0 {
+        let secondary_rewards = time_elapsed_u64
+            .checked_mul(pool.secondary_rewards_per_second)
+            .ok_or(StakingError::MathOverflow)?;
+        require!(secondary_rewards <= MAX_REWARDS_PER_UPDATE, StakingError::RewardsAccumulationExceeded);
+
+        let secondary_per_share = (u128::from(secondary_rewards))
+            .checked_mul(u128::from(PRECISION))
+            .ok_or(StakingError::MathOverflow)?
+            .checked_div(u128::from(pool.total_staked))
+            .ok_or(StakingError::MathOverflow)?;
+        let new_secondary_accumulated = pool.secondary_accumulated_rewards_per_share
+            .checked_add(secondary_per_share)
+            .ok_or(StakingError::MathOverflow)?;
+        require!(
+            new_secondary_accumulated <= MAX_ACCUMULATED_REWARDS,
+            StakingError::RewardsAccumulationExceeded
+        );
+
+        pool.secondary_accumulated_rewards_per_share = new_secondary_accumulated;
+    }
     
     pool.last_update_time = current_time;
     
     Ok(())
 }
 
+/// A set cap must be at least one second of emissions; anything smaller is almost
+/// certainly a units mistake (base units vs whole tokens)
+fn validate_stake_cap(max_stake_per_user: u64, rewards_per_second: u64) -> Result<()> {
+    require!(
+        max_stake_per_user == 0 || max_stake_per_user >= rewards_per_second,
+        StakingError::InvalidStakeCap
+    );
+    Ok(())
+}
+
+/// Reset the accumulator to zero and open a new epoch. Debts recorded before this point are
+/// stale: calculate_pending_rewards settles them against pre_reset_accumulator for users one
+/// epoch behind. Users who sit through two resets without touching their stake lose the
+/// rewards accrued before the older one, so stakers should be prompted to claim.
+fn reset_accumulator(pool: &mut StakingPool) -> Result<()> {
+    pool.pre_reset_accumulator = pool.accumulated_rewards_per_share;
+    pool.accumulated_rewards_per_share = 0;
+    pool.reset_epoch = pool.reset_epoch
+        .checked_add(1)
+        .ok_or(StakingError::MathOverflow)?;
+    Ok(())
+}
+
 fn calculate_pending_rewards(
-    user_stake: &Account<UserStakeAccount>,
-    pool: &Account<StakingPool>,
+    user_stake: &UserStakeAccount,
+    pool: &StakingPool,
 ) -> Result<u64> {
-    if user_stake.amount == 0 {
+    let earning = user_stake.earning_amount();
+    if earning == 0 {
         return Ok(0);
     }
     
+    let accumulated = rewards_at(earning, pool.accumulated_rewards_per_share)?;
+
+    if user_stake.reset_epoch == pool.reset_epoch {
+        return Ok(accumulated.saturating_sub(user_stake.reward_debt));
+    }
+
+    // reward_debt predates an accumulator reset: everything since the reset is owed in full,
+    // plus what accrued before it when the user is exactly one epoch behind
+    let before_reset = if user_stake.reset_epoch.checked_add(1) == Some(pool.reset_epoch) {
+        rewards_at(earning, pool.pre_reset_accumulator)?.saturating_sub(user_stake.reward_debt)
+    } else {
+        0
+    };
+
+    Ok(accumulated
+        .checked_add(before_reset)
+        .ok_or(StakingError::MathOverflow)?)
+}
+
+/// Rewards owed to `amount` staked from accumulator zero up to `accumulator`
+fn rewards_at(amount: u64, accumulator: u128) -> Result<u64> {
     // **CRITICAL FIX**: Use u128 math to prevent overflow in reward calculations
-    let accumulated_u128 = (u128::from(user_stake.amount))
-        .checked_mul(pool.accumulated_rewards_per_share)
+    let accumulated = (u128::from(amount))
+        .checked_mul(accumulator)
         .ok_or(StakingError::MathOverflow)?
         .checked_div(u128::from(PRECISION))
         .ok_or(StakingError::MathOverflow)?;
-    
-    let accumulated = u64::try_from(accumulated_u128)
-        .map_err(|_| StakingError::MathOverflow)?;
-    
-    if accumulated > user_stake.reward_debt {
-        Ok(accumulated
-            .checked_sub(user_stake.reward_debt)
-            .ok_or(StakingError::MathOverflow)?)
-    } else {
-        Ok(0)
+
+    u64::try_from(accumulated).map_err(|_| StakingError::MathOverflow.into())
+}
+
+fn calculate_pending_secondary_rewards(
+    user_stake: &Account<UserStakeAccount>,
+    pool: &Account<StakingPool>,
+) -> Result<u64> {
+    let earning = user_stake.earning_amount();
+    if earning == 0 || pool.secondary_reward_token_mint.is_none() {
+        return Ok(0);
     }
+
+    let accumulated = secondary_reward_debt(earning, pool)?;
+    Ok(accumulated.saturating_sub(user_stake.secondary_reward_debt))
+}
+
+/// Secondary reward debt for `amount` staked at the pool's current secondary accumulator
+fn secondary_reward_debt(amount: u64, pool: &StakingPool) -> Result<u64> {
+    let debt = (u128::from(amount))
+        .checked_mul(pool.secondary_accumulated_rewards_per_share)
+        .ok_or(StakingError::MathOverflow)?
+        .checked_div(u128::from(PRECISION))
+        .ok_or(StakingError::MathOverflow)?;
+
+    u64::try_from(debt).map_err(|_| StakingError::MathOverflow.into())
 }
 
 // Constants section moved to top of file
@@ -757,6 +1394,84 @@ pub struct UnstakeTokens<'info> {
     pub token_program: Program<'info, Token>,
 }
 
+#[derive(Accounts)]
+pub struct RequestUnstake<'info> {
+    pub user: Signer<'info>,
+    
+    #[account(mut)]
+    pub staking_pool: Account<'info, StakingPool>,
+    
+    #[account(
+        mut,
+        seeds = [b"user_stake", staking_pool.key().as_ref(), user.key().as_ref()],
+        bump,
+        constraint = user_stake_account.user == user.key()
+    )]
+    pub user_stake_account: Account<'info, UserStakeAccount>,
+}
+
+#[derive(Accounts)]
+pub struct BindGovernance<'info> {
+    #[account(
+        constraint = authority.key() == staking_pool.authority @ StakingError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub staking_pool: Account<'info, StakingPool>,
+
+    pub governance: Account<'info, governance::Governance>,
+}
+
+#[derive(Accounts)]
+pub struct SetUnstakeCooldown<'info> {
+    pub executor: Signer<'info>,
+
+    #[account(mut)]
+    pub staking_pool: Account<'info, StakingPool>,
+
+    /// The governance bound to this pool by bind_governance
+    #[account(
+        constraint = governance.key() == staking_pool.governance @ StakingError::InvalidGovernance
+    )]
+    pub governance: Account<'info, governance::Governance>,
+
+    /// Governance proposal bound to the governance account by PDA
+    #[account(
+        seeds = [b"proposal", governance.key().as_ref(), &proposal.id.to_le_bytes()],
+        bump,
+        seeds::program = governance::ID
+    )]
+    pub proposal: Account<'info, governance::Proposal>,
+}
+
+#[derive(Accounts)]
+pub struct SetMaxStakePerUser<'info> {
+    #[account(
+        constraint = authority.key() == staking_pool.authority @ StakingError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+    
+    #[account(mut)]
+    pub staking_pool: Account<'info, StakingPool>,
+}
+
+/// Read-only account struct for current_apy
+#[derive(Accounts)]
+pub struct CurrentApy<'info> {
+    pub staking_pool: Account<'info, StakingPool>,
+    
+    #[account(
+        constraint = lp_token_mint.key() == staking_pool.lp_token_mint @ StakingError::InvalidMint
+    )]
+    pub lp_token_mint: Account<'info, Mint>,
+    
+    #[account(
+        constraint = reward_token_mint.key() == staking_pool.reward_token_mint @ StakingError::InvalidMint
+    )]
+    pub reward_token_mint: Account<'info, Mint>,
+}
+
 #[derive(Accounts)]
 pub struct ClaimRewards<'info> {
     #[account(mut)]
@@ -826,6 +1541,106 @@ pub struct DepositRewards<'info> {
     pub token_program: Program<'info, Token>,
 }
 
+#[derive(Accounts)]
+pub struct InitializeSecondaryRewards<'info> {
+    #[account(
+        mut,
+        constraint = authority.key() == staking_pool.authority @ StakingError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+
+    #[account(mut)]
+    pub staking_pool: Account<'info, StakingPool>,
+
+    pub secondary_reward_token_mint: Account<'info, Mint>,
+
+    /// Secondary reward vault, owned by the pool's reward authority
+    #[account(
+        init,
+        payer = authority,
+        token::mint = secondary_reward_token_mint,
+        token::authority = reward_authority,
+        seeds = [b"secondary_reward_vault", staking_pool.key().as_ref()],
+        bump
+    )]
+    pub secondary_reward_vault: Account<'info, TokenAccount>,
+
+    /// CHECK: PDA for reward authority
+    #[account(
+        seeds = [b"reward_authority", staking_pool.key().as_ref()],
+        bump
+    )]
+    pub reward_authority: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+    pub token_program: Program<'info, Token>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct DepositSecondaryRewards<'info> {
+    pub depositor: Signer<'info>,
+
+    #[account(mut)]
+    pub staking_pool: Account<'info, StakingPool>,
+
+    #[account(
+        mut,
+        constraint = depositor_token_account.owner == depositor.key()
+    )]
+    pub depositor_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        seeds = [b"secondary_reward_vault", staking_pool.key().as_ref()],
+        bump,
+        constraint = Some(secondary_reward_vault.mint) == staking_pool.secondary_reward_token_mint @ StakingError::InvalidMint
+    )]
+    pub secondary_reward_vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimSecondaryRewards<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(mut)]
+    pub staking_pool: Account<'info, StakingPool>,
+
+    #[account(
+        mut,
+        seeds = [b"user_stake", staking_pool.key().as_ref(), user.key().as_ref()],
+        bump,
+        constraint = user_stake_account.user == user.key()
+    )]
+    pub user_stake_account: Account<'info, UserStakeAccount>,
+
+    #[account(
+        mut,
+        constraint = user_reward_tokens.owner == user.key(),
+        constraint = Some(user_reward_tokens.mint) == staking_pool.secondary_reward_token_mint @ StakingError::InvalidMint
+    )]
+    pub user_reward_tokens: Account<'info, TokenAccount>,
+
+    /// CHECK: PDA for reward authority
+    #[account(
+        seeds = [b"reward_authority", staking_pool.key().as_ref()],
+        bump
+    )]
+    pub reward_authority: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        seeds = [b"secondary_reward_vault", staking_pool.key().as_ref()],
+        bump
+    )]
+    pub secondary_reward_vault: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
 #[derive(Accounts)]
 pub struct UpdateRewardsRate<'info> {
     #[account(
@@ -855,7 +1670,17 @@ impl StakingPool {
         1 +  // is_paused
         32 + // rifts_protocol
         8 +  // total_rewards_available
-        8;   // last_reward_deposit
+        8 +  // last_reward_deposit
+        8 +  // unstake_cooldown
+        33 + // secondary_reward_token_mint (Option<Pubkey>)
+        8 +  // secondary_rewards_per_second
+        16 + // secondary_accumulated_rewards_per_share
+        8 +  // secondary_rewards_available
+        8 +  // reward_shortfall
+        8 +  // reset_epoch
+        16 + // pre_reset_accumulator
+        8 +  // max_stake_per_user
+        32;  // governance
 }
 
 #[account]
@@ -864,7 +1689,7 @@ pub struct StakingPool {
     pub lp_token_mint: Pubkey,
     pub reward_token_mint: Pubkey,
     pub reward_token_vault: Pubkey,
-    pub total_staked: u64,
+    pub total_staked: u64, // Stake earning rewards (excludes pending unstake requests)
     pub rewards_per_second: u64,
     pub min_stake_duration: i64,
     pub last_update_time: i64,
@@ -873,6 +1698,16 @@ pub struct StakingPool {
     pub rifts_protocol: Pubkey, // RIFTS protocol that can deposit rewards
     pub total_rewards_available: u64, // Total RIFTS tokens available for distribution
     pub last_reward_deposit: i64, // Timestamp of last reward deposit
+    pub unstake_cooldown: i64, // Seconds between request_unstake and unstake (0 = disabled)
+    pub secondary_reward_token_mint: Option<Pubkey>, // Optional second reward token (None = single reward)
+    pub secondary_rewards_per_second: u64,
+    pub secondary_accumulated_rewards_per_share: u128,
+    pub secondary_rewards_available: u64, // Secondary tokens deposited and not yet claimed
+    pub reward_shortfall: u64, // Rewards left unpaid by claim_rewards_partial, awaiting top-up
+    pub reset_epoch: u64, // Incremented by every reset_rewards_accumulator
+    pub pre_reset_accumulator: u128, // accumulated_rewards_per_share just before the latest reset
+    pub max_stake_per_user: u64, // Largest stake a single user may hold (0 = unlimited)
+    pub governance: Pubkey, // Governance whose proposals set unstake_cooldown (default until bind_governance)
 }
 
 impl UserStakeAccount {
@@ -882,7 +1717,18 @@ impl UserStakeAccount {
         8 +  // amount
         8 +  // stake_time
         8 +  // reward_debt
-        8;   // pending_rewards
+        8 +  // pending_rewards
+        8 +  // unstake_requested_at
+        8 +  // unstake_requested_amount
+        8 +  // secondary_reward_debt
+        8 +  // secondary_pending_rewards
+        8 +  // reward_shortfall
+        8;   // reset_epoch
+
+    /// Stake still earning rewards (requested unstakes stop earning at request time)
+    pub fn earning_amount(&self) -> u64 {
+        self.amount.saturating_sub(self.unstake_requested_amount)
+    }
 }
 
 #[account]
@@ -893,6 +1739,26 @@ pub struct UserStakeAccount {
     pub stake_time: i64,
     pub reward_debt: u64,
     pub pending_rewards: u64,
+    pub unstake_requested_at: i64,     // When the pending unstake request was made
+    pub unstake_requested_amount: u64, // Amount still withdrawable under that request
+    pub secondary_reward_debt: u64,
+    pub secondary_pending_rewards: u64,
+    pub reward_shortfall: u64, // This user's share of the pool's reward_shortfall
+    pub reset_epoch: u64, // Pool reset_epoch that reward_debt was computed against
+}
+
+
+#[derive(Accounts)]
+pub struct MigrateStakingAccount<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Pre-migration StakingPool or UserStakeAccount; ownership is checked here,
+    /// discriminator and size in the handler
+    #[account(mut, owner = crate::ID @ StakingError::InvalidLegacyAccount)]
+    pub account: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
 }
 
 #[derive(Accounts)]
@@ -907,6 +1773,17 @@ pub struct ResetAccumulator<'info> {
     pub staking_pool: Account<'info, StakingPool>,
 }
 
+#[derive(Accounts)]
+pub struct SetPoolPause<'info> {
+    #[account(
+        constraint = authority.key() == staking_pool.authority @ StakingError::Unauthorized
+    )]
+    pub authority: Signer<'info>,
+    
+    #[account(mut)]
+    pub staking_pool: Account<'info, StakingPool>,
+}
+
 #[derive(Accounts)]
 pub struct EmergencyWithdraw<'info> {
     #[account(mut)]
@@ -951,6 +1828,15 @@ pub struct EmergencyWithdraw<'info> {
 }
 
 // Events
+/// Return data for current_apy
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Debug)]
+pub struct ApyQuote {
+    pub apy_bps: u64,            // Saturates at u64::MAX
+    pub has_stake: bool,         // false when total_staked == 0 (apy_bps is then 0)
+    pub total_staked: u64,
+    pub rewards_per_second: u64,
+}
+
 #[event]
 pub struct StakeEvent {
     pub user: Pubkey,
@@ -965,12 +1851,42 @@ pub struct UnstakeEvent {
     pub remaining_staked: u64,
 }
 
+#[event]
+pub struct UnstakeRequested {
+    pub user: Pubkey,
+    pub pool: Pubkey,
+    pub amount: u64,
+    pub available_at: i64,
+}
+
 #[event]
 pub struct ClaimEvent {
     pub user: Pubkey,
     pub amount: u64,
 }
 
+#[event]
+pub struct PartialClaim {
+    pub user: Pubkey,
+    pub pool: Pubkey,
+    pub claimed: u64,
+    pub remaining: u64,
+}
+
+#[event]
+pub struct SecondaryClaimEvent {
+    pub user: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct SecondaryRewardsDeposited {
+    pub pool: Pubkey,
+    pub depositor: Pubkey,
+    pub amount: u64,
+    pub total_available: u64,
+}
+
 #[event]
 pub struct AccumulatorReset {
     pub pool: Pubkey,
@@ -986,6 +1902,21 @@ pub struct EmergencyWithdrawEvent {
     pub timestamp: i64,
 }
 
+#[event]
+pub struct PoolPaused {
+    pub pool: Pubkey,
+    pub authority: Pubkey,
+    pub pause_reason: String,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PoolUnpaused {
+    pub pool: Pubkey,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct RewardsDeposited {
     pub pool: Pubkey,
@@ -994,6 +1925,15 @@ pub struct RewardsDeposited {
     pub total_available: u64,
 }
 
+#[event]
+pub struct RewardsRateUpdated {
+    pub pool: Pubkey,
+    pub old_rate: u64,
+    pub new_rate: u64,
+    pub authority: Pubkey,
+    pub timestamp: i64,
+}
+
 // Errors
 #[error_code]
 pub enum StakingError {
@@ -1041,4 +1981,131 @@ pub enum StakingError {
     AccountAlreadyInitialized,
     #[msg("Unauthorized access to user stake account")]
     UnauthorizedAccess,
-}
\ No newline at end of file
+    #[msg("Unstake cooldown is disabled for this pool")]
+    UnstakeCooldownDisabled,
+    #[msg("Unstake must be requested first")]
+    UnstakeNotRequested,
+    #[msg("Unstake amount exceeds the requested amount")]
+    ExceedsUnstakeRequest,
+    #[msg("Unstake cooldown has not elapsed")]
+    UnstakeCooldownActive,
+    #[msg("Invalid unstake cooldown - maximum 30 days allowed")]
+    InvalidUnstakeCooldown,
+    #[msg("Secondary rewards are already configured for this pool")]
+    SecondaryRewardsAlreadyConfigured,
+    #[msg("Pause reason is too long")]
+    PauseReasonTooLong,
+    #[msg("Stake would exceed the pool's per-user cap")]
+    StakeCapExceeded,
+    #[msg("Stake cap must be 0 or at least rewards_per_second")]
+    InvalidStakeCap,
+    #[msg("Account is not a pre-migration staking pool or stake account")]
+    InvalidLegacyAccount,
+    #[msg("Account already uses the current layout")]
+    AlreadyMigrated,
+    #[msg("Staking pool is already bound to a governance")]
+    GovernanceAlreadyBound,
+    #[msg("Governance is not the one bound to this staking pool")]
+    InvalidGovernance,
+    #[msg("Proposal is not the governance's pending parameter change")]
+    ProposalMismatch,
+    #[msg("Proposal is not an executed parameter change")]
+    ProposalNotExecuted,
+    #[msg("Pending parameter change has no unstake cooldown")]
+    NoPendingUnstakeCooldown,
+    #[msg("Unstake cooldown proposal targets a different staking pool")]
+    ProposalNotForThisPool,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn pool_with_accumulator(accumulated_rewards_per_share: u128) -> StakingPool {
+        StakingPool {
+            authority: Pubkey::default(),
+            lp_token_mint: Pubkey::default(),
+            reward_token_mint: Pubkey::default(),
+            reward_token_vault: Pubkey::default(),
+            total_staked: 1_000,
+            rewards_per_second: 1,
+            min_stake_duration: 0,
+            last_update_time: 0,
+            accumulated_rewards_per_share,
+            is_paused: false,
+            rifts_protocol: Pubkey::default(),
+            total_rewards_available: 0,
+            last_reward_deposit: 0,
+            unstake_cooldown: 0,
+            secondary_reward_token_mint: None,
+            secondary_rewards_per_second: 0,
+            secondary_accumulated_rewards_per_share: 0,
+            secondary_rewards_available: 0,
+            reward_shortfall: 0,
+            reset_epoch: 0,
+            pre_reset_accumulator: 0,
+            max_stake_per_user: 0,
+            governance: Pubkey::default(),
+        }
+    }
+
+    fn staker(amount: u64, pool: &StakingPool) -> UserStakeAccount {
+        UserStakeAccount {
+            user: Pubkey::default(),
+            pool: Pubkey::default(),
+            amount,
+            stake_time: 0,
+            reward_debt: rewards_at(amount, pool.accumulated_rewards_per_share).unwrap(),
+            pending_rewards: 0,
+            unstake_requested_at: 0,
+            unstake_requested_amount: 0,
+            secondary_reward_debt: 0,
+            secondary_pending_rewards: 0,
+            reward_shortfall: 0,
+            reset_epoch: pool.reset_epoch,
+        }
+    }
+
+    /// Mirrors reset_rewards_accumulator's state transition
+    #[test]
+    fn rewards_after_reset_are_not_lost() {
+        let large = 10_000 * u128::from(PRECISION);
+        let mut pool = pool_with_accumulator(large);
+        let user = staker(100, &pool);
+
+        // 5 more reward units per share accrue, then the accumulator is reset
+        pool.accumulated_rewards_per_share = large + 5 * u128::from(PRECISION);
+        reset_accumulator(&mut pool).unwrap();
+
+        // Another 3 per share accrue after the reset
+        pool.accumulated_rewards_per_share = 3 * u128::from(PRECISION);
+
+        // Previously the stale debt (1_000_000) dwarfed the new accumulator and this was 0
+        assert_eq!(calculate_pending_rewards(&user, &pool).unwrap(), 100 * (5 + 3));
+    }
+
+    #[test]
+    fn stakers_two_resets_behind_keep_post_reset_rewards() {
+        let mut pool = pool_with_accumulator(7 * u128::from(PRECISION));
+        let user = staker(10, &pool);
+
+        reset_accumulator(&mut pool).unwrap();
+        pool.accumulated_rewards_per_share = 2 * u128::from(PRECISION);
+        reset_accumulator(&mut pool).unwrap();
+        pool.accumulated_rewards_per_share = 4 * u128::from(PRECISION);
+
+        assert_eq!(calculate_pending_rewards(&user, &pool).unwrap(), 10 * 4);
+    }
+
+    #[test]
+    fn current_epoch_uses_reward_debt() {
+        let mut pool = pool_with_accumulator(0);
+        reset_accumulator(&mut pool).unwrap();
+        pool.accumulated_rewards_per_share = 2 * u128::from(PRECISION);
+        let user = staker(50, &pool);
+
+        pool.accumulated_rewards_per_share = 6 * u128::from(PRECISION);
+
+        assert_eq!(calculate_pending_rewards(&user, &pool).unwrap(), 50 * 4);
+    }
+}
@@ -4,7 +4,7 @@ use anchor_spl::token::{self, Token, TokenAccount, Transfer, Mint, MintTo, trans
 use anchor_lang::solana_program::program_option::COption;
 // Note: Metadata functionality removed to avoid dependency issues
 use anchor_lang::solana_program::sysvar::rent::Rent;
-use std::str::FromStr;
+use anchor_lang::solana_program::hash::hashv;
 
 // External program CPI imports
 pub use fee_collector;
@@ -17,8 +17,8 @@ pub use lp_staking;
 // Meteora DAMM v2 imports
 use cp_amm::program::CpAmm;
 use cp_amm::cpi;
-use cp_amm::cpi::accounts::{InitializePoolWithDynamicConfigCtx, RemoveLiquidityCtx, AddLiquidityCtx};
-use cp_amm::{AddLiquidityParameters, RemoveLiquidityParameters};
+use cp_amm::cpi::accounts::{InitializePoolWithDynamicConfigCtx, RemoveLiquidityCtx, AddLiquidityCtx, SwapCtx, ClaimPositionFeeCtx};
+use cp_amm::{AddLiquidityParameters, RemoveLiquidityParameters, SwapParameters};
 use cp_amm::constants::{MIN_SQRT_PRICE, MAX_SQRT_PRICE};
 
 declare_id!("BPYwhoziLVUZQy2aUTfR7dJLz2WqJgaoyLmcepzNBTs8");
@@ -26,6 +26,122 @@ declare_id!("BPYwhoziLVUZQy2aUTfR7dJLz2WqJgaoyLmcepzNBTs8");
 // Meteora DAMM v2 Program ID (same for mainnet and devnet)
 pub const METEORA_DAMM_V2_PROGRAM_ID: Pubkey = pubkey!("cpamdpZCGKUy5JxQXB4dcpGPiikHawvSWAd6mEn1sGG");
 
+// Initial protocol admin written to the ProtocolConfig PDA; rotated afterwards via governance proposal
+pub const INITIAL_PROTOCOL_ADMIN: Pubkey = pubkey!("4NHB7rAvsDjV5USbuntY4UcgnQS1zQcc8K69htaAupHk");
+
+// Metaplex Token Metadata program (collection verification for gated rift creation)
+pub const TOKEN_METADATA_PROGRAM_ID: Pubkey = pubkey!("metaqbxxUerdq28cj1RbAWkYQm3ybzjb6a8bt518x1s");
+
+// Decimals of normalized oracle prices and backing_ratio (1_000_000 = 1.0)
+pub const PRICE_DECIMALS: u32 = 6;
+
+// Accepted oracle price exponents; positive ones come from a few high-value Pyth feeds
+pub const MIN_ORACLE_EXPONENT: i32 = -18;
+pub const MAX_ORACLE_EXPONENT: i32 = 8;
+
+// Default ceiling on oracle confidence as a share of price (5%); configurable up to 100%
+pub const DEFAULT_MAX_CONFIDENCE_BPS: u16 = 500;
+pub const MAX_CONFIDENCE_BPS: u16 = 10000;
+
+// Wrap/unwrap fee charged on every conversion (0.7%)
+pub const WRAP_FEE_BPS: u16 = 70;
+
+// Smallest wrap whose WRAP_FEE_BPS fee is at least 1 base unit (ceil(10000 / WRAP_FEE_BPS))
+pub const DEFAULT_MIN_WRAP_AMOUNT: u64 = (10000 + WRAP_FEE_BPS as u64 - 1) / WRAP_FEE_BPS as u64;
+
+// Treasury share of post burn/partner fees (governance adjustable, must stay below the max)
+pub const DEFAULT_TREASURY_FEE_BPS: u16 = 500;
+pub const MAX_TREASURY_FEE_BPS: u16 = 2000;
+
+// Ceiling on burn + partner + treasury fee bps combined, so fee splits never underflow
+pub const MAX_COMBINED_FEE_BPS: u32 = 9500;
+
+// Split of bought-back RIFTS between LP stakers and burn (governance adjustable, must sum to 10000)
+pub const DEFAULT_RIFTS_LP_SHARE_BPS: u16 = 9000;
+pub const DEFAULT_RIFTS_BURN_SHARE_BPS: u16 = 1000;
+
+// Bump when the LP staking reward formula changes; stakers on an older version must migrate
+pub const REWARD_FORMULA_VERSION: u8 = 1;
+
+// LP staking reward rate: 3170979 / 1e11 per hour ~= 10% APY (default), capped at ~100% APY
+pub const DEFAULT_REWARD_RATE_PER_HOUR: u64 = 3_170_979;
+pub const MAX_REWARD_RATE_PER_HOUR: u64 = 31_709_792;
+pub const REWARD_RATE_PRECISION: u128 = 100_000_000_000;
+
+// Longest allowed linear vesting for claimed staking rewards (1 year)
+pub const MAX_REWARD_VEST_PERIOD: i64 = 365 * 24 * 60 * 60;
+
+// Largest Jupiter route payload accepted by jupiter_swap_for_buyback
+pub const MAX_SWAP_DATA_LEN: usize = 10000;
+
+// Rift pubkeys stored per RiftRegistryPage account
+pub const RIFTS_PER_REGISTRY_PAGE: usize = 100;
+
+// Maximum rifts updated by one update_oracle_multi call (bounded by compute)
+pub const MAX_ORACLE_BATCH_SIZE: usize = 10;
+
+// claim_staking_rewards_batch: accounts per rift entry and maximum rifts per call
+pub const CLAIM_BATCH_ACCOUNTS_PER_RIFT: usize = 5;
+pub const MAX_CLAIM_BATCH_SIZE: usize = 5;
+
+// Oracle staleness window: default 5 minutes, governance-adjustable per rift within bounds
+pub const DEFAULT_MAX_ORACLE_STALENESS: i64 = 300;
+pub const MIN_MAX_ORACLE_STALENESS: i64 = 60;
+pub const MAX_MAX_ORACLE_STALENESS: i64 = 3600;
+
+// Arbitrage detection threshold: 0.1%..=50%, shared by rift creation and governance updates
+pub const MIN_ARB_BPS: u16 = 10;
+pub const MAX_ARB_BPS: u16 = 5000;
+pub const DEFAULT_ARB_BPS: u16 = 200;
+
+// validate_rift_config violation bits (returned as a u32 bitmask, 0 = consistent)
+pub const CONFIG_VIOLATION_FEE_BOUNDS: u32 = 1 << 0;
+pub const CONFIG_VIOLATION_ORACLE_PARAMS: u32 = 1 << 1;
+pub const CONFIG_VIOLATION_BACKING_RATIO: u32 = 1 << 2;
+pub const CONFIG_VIOLATION_POOL_STATE: u32 = 1 << 3;
+
+// Meteora config allowlist: seeded with the original 0.25% dynamic-fee config, governance adds fee tiers
+pub const DEFAULT_METEORA_CONFIG: Pubkey = pubkey!("82p7sVzQWZfCrmStPhsG8BYKwheQkUiXSs2wiqdhwNxr");
+pub const MAX_METEORA_CONFIGS: usize = 10;
+
+// Longest allowed wrap → unwrap cooldown (anti-MEV), configurable per rift by the creator
+pub const MAX_WRAP_COOLDOWN_SECONDS: i64 = 300;
+
+// Distinct oracle feed types (Pyth, Switchboard) available for a rebalance quorum
+pub const MAX_ORACLE_SOURCES: u8 = 2;
+
+// Keeper keys the creator may authorize to push Pyth/Switchboard prices
+pub const MAX_ORACLE_PUSHERS: usize = 4;
+
+// Longest allowed minimum LP stake duration (30 days)
+pub const MAX_MIN_STAKE_DURATION: i64 = 30 * 86400;
+
+// Default backing_ratio floor (0.95) below which a rift is flagged as undercollateralized
+pub const DEFAULT_UNDERCOLLATERALIZATION_FLOOR: u64 = 950_000;
+
+// Allowed drift between caller-supplied and on-chain liquidity in wrap_and_add_liquidity (1%)
+pub const LIQUIDITY_TOLERANCE_BPS: u128 = 100;
+
+// Protocol arbitrage guards: trade at most 1% of the pool's input reserve, at most 1% slippage
+pub const MAX_ARBITRAGE_TRADE_BPS: u64 = 100;
+pub const MAX_ARBITRAGE_SLIPPAGE_BPS: u16 = 100;
+
+// Widest deviation of a new pool's price from the oracle average (5%)
+pub const MAX_INITIAL_PRICE_DEVIATION_BPS: u16 = 500;
+
+// AppliedProposalRecord seed suffixes, one replay sequence per governance effect on a rift
+pub const PROPOSAL_EFFECT_PARAMETERS: &[u8] = b"parameters";
+pub const PROPOSAL_EFFECT_BACKING_RATIO: &[u8] = b"backing_ratio";
+
+// Widest oracle slippage jupiter_swap_for_buyback accepts (3%)
+pub const MAX_BUYBACK_SLIPPAGE_BPS: u16 = 300;
+
+// Notice the admin must give (72 hours) between admin_propose_close and admin_close_rift
+pub const ADMIN_CLOSE_GRACE: i64 = 72 * 60 * 60;
+
+// Largest move (10%) set_backing_ratio may make from the current backing_ratio in one call
+pub const MAX_MANUAL_BACKING_RATIO_CHANGE_BPS: u64 = 1000;
+
 #[program]
 pub mod rifts_protocol {
     use super::*;
@@ -49,8 +165,17 @@ pub mod rifts_protocol {
         // Validate fees and seed length
         require!(burn_fee_bps <= 4500, ErrorCode::InvalidBurnFee);
         require!(partner_fee_bps <= 500, ErrorCode::InvalidPartnerFee);
+        validate_combined_fees(burn_fee_bps, partner_fee_bps, DEFAULT_TREASURY_FEE_BPS)?;
         require!(seed_len <= 32, ErrorCode::InvalidVanitySeed);
 
+        // Launchpad gate: when configured, only holders of the collection may create rifts
+        check_collection_gate(
+            &ctx.accounts.factory_config,
+            &ctx.accounts.creator.key(),
+            ctx.accounts.gate_nft_account.as_deref(),
+            ctx.accounts.gate_nft_metadata.as_deref(),
+        )?;
+
         // PDA derivation is automatically verified by Anchor through the seeds constraint
 
         // **MEMORY OPTIMIZATION**: Skip vanity address validation to prevent heap allocation
@@ -88,6 +213,7 @@ pub mod rifts_protocol {
             price: 1_000_000, // Default to 1.0 price (with 6 decimals)
             confidence: 100_000, // Moderate confidence for initial state
             timestamp: current_time,
+            source: None,        // Seeded placeholder, not from a feed
         };
 
         // **SECURITY FIX**: Validate oracle parameters to prevent manipulation
@@ -107,11 +233,8 @@ pub mod rifts_protocol {
             ErrorCode::InvalidOracleParameters
         );
 
-        rift.arbitrage_threshold_bps = 200; // 2% (min 0.1%, max 50%)
-        require!(
-            rift.arbitrage_threshold_bps >= 10 && rift.arbitrage_threshold_bps <= 5000,
-            ErrorCode::InvalidOracleParameters
-        );
+        rift.arbitrage_threshold_bps = DEFAULT_ARB_BPS; // 2%
+        validate_arbitrage_threshold(rift.arbitrage_threshold_bps)?;
 
         rift.last_oracle_update = current_time;
 
@@ -122,6 +245,8 @@ pub mod rifts_protocol {
         // **SECURITY FIX**: Initialize Jupiter program ID as None (uses hardcoded fallback)
         rift.jupiter_program_id = None;
         rift.rebalance_count = 0;
+        rift.init_extended_fields(current_time);
+        rift.fee_vault = ctx.accounts.fee_vault.key();
 
         // Initialize RIFTS token distribution tracking
         rift.total_fees_collected = 0;
@@ -146,6 +271,15 @@ pub mod rifts_protocol {
 
         // Mint account is automatically initialized by Anchor with the init constraint
 
+        // Append to the on-chain rift registry for discovery
+        let rift_key = rift.key();
+        ctx.accounts.registry.register(
+            &mut ctx.accounts.registry_page,
+            rift_key,
+            ctx.bumps.registry,
+            ctx.bumps.registry_page,
+        )?;
+
         // Emit creation event
         emit!(RiftCreated {
             rift: rift.key(),
@@ -155,6 +289,15 @@ pub mod rifts_protocol {
             partner_fee_bps,
         });
 
+        // Let clients read every derived address from a single simulation
+        let addresses = CreatedRiftAddresses {
+            rift: rift.key(),
+            rift_mint: rift.rift_mint,
+            vault: rift.vault,
+            rift_mint_authority: ctx.accounts.rift_mint_authority.key(),
+        };
+        anchor_lang::solana_program::program::set_return_data(&addresses.try_to_vec()?);
+
         Ok(())
     }
 
@@ -172,6 +315,15 @@ pub mod rifts_protocol {
         // Validate fees
         require!(burn_fee_bps <= 4500, ErrorCode::InvalidBurnFee);
         require!(partner_fee_bps <= 500, ErrorCode::InvalidPartnerFee);
+        validate_combined_fees(burn_fee_bps, partner_fee_bps, DEFAULT_TREASURY_FEE_BPS)?;
+
+        // Launchpad gate: when configured, only holders of the collection may create rifts
+        check_collection_gate(
+            &ctx.accounts.factory_config,
+            &ctx.accounts.creator.key(),
+            ctx.accounts.gate_nft_account.as_deref(),
+            ctx.accounts.gate_nft_metadata.as_deref(),
+        )?;
         
         // Validate and set rift name (fixed-size array - no heap allocation!)
         require!(name_len <= 32, ErrorCode::NameTooLong);
@@ -199,15 +351,17 @@ pub mod rifts_protocol {
         rift.total_rift_minted = 0;
         rift.total_burned = 0;
         rift.backing_ratio = 10000; // 1.0000x in basis points
-        rift.last_rebalance = Clock::get()?.unix_timestamp;
-        rift.created_at = Clock::get()?.unix_timestamp;
+        let current_time = Clock::get()?.unix_timestamp;
+        rift.last_rebalance = current_time;
+        rift.created_at = current_time;
 
         // Initialize hybrid oracle system
         rift.oracle_prices = [PriceData::default(); 10];
         rift.price_index = 0;
         rift.oracle_update_interval = 30 * 60; // 30 minutes
         rift.max_rebalance_interval = 24 * 60 * 60; // 24 hours
-        rift.arbitrage_threshold_bps = 200; // 2% threshold
+        rift.arbitrage_threshold_bps = DEFAULT_ARB_BPS; // 2% threshold
+        validate_arbitrage_threshold(rift.arbitrage_threshold_bps)?;
         rift.last_oracle_update = Clock::get()?.unix_timestamp;
         
         // Initialize advanced metrics
@@ -253,6 +407,18 @@ pub mod rifts_protocol {
         
         // Initialize governance integration
         rift.last_governance_update = Clock::get()?.unix_timestamp;
+
+        rift.init_extended_fields(current_time);
+        rift.fee_vault = ctx.accounts.fee_vault.key();
+
+        // Append to the on-chain rift registry for discovery
+        let rift_key = rift.key();
+        ctx.accounts.registry.register(
+            &mut ctx.accounts.registry_page,
+            rift_key,
+            ctx.bumps.registry,
+            ctx.bumps.registry_page,
+        )?;
         
         emit!(RiftCreated {
             rift: rift.key(),
@@ -262,6 +428,15 @@ pub mod rifts_protocol {
             partner_fee_bps,
         });
 
+        // Let clients read every derived address from a single simulation
+        let addresses = CreatedRiftAddresses {
+            rift: rift.key(),
+            rift_mint: rift.rift_mint,
+            vault: rift.vault,
+            rift_mint_authority: ctx.accounts.rift_mint_authority.key(),
+        };
+        anchor_lang::solana_program::program::set_return_data(&addresses.try_to_vec()?);
+
         Ok(())
     }
 
@@ -271,11 +446,13 @@ pub mod rifts_protocol {
         amount: u64,
         bin_step: u16,
         base_factor: u16,
+        expected_init_price: u64,       // Initial pool price, same 6-decimal units as the oracle
+        max_price_deviation_bps: u16,   // Allowed deviation of expected_init_price from oracle average
     ) -> Result<()> {
         let rift = &mut ctx.accounts.rift;
 
-        // Basic validation
-        require!(!rift.is_paused, ErrorCode::RiftPaused);
+        // Basic validation (maintenance pauses may have expired on their own)
+        require!(!rift.is_paused_at(Clock::get()?.unix_timestamp), ErrorCode::RiftPaused);
         require!(amount > 0, ErrorCode::InvalidAmount);
         require!(amount <= 1_000_000_000_000_000, ErrorCode::AmountTooLarge);
         require!(amount >= 10000, ErrorCode::AmountTooSmall);
@@ -287,9 +464,8 @@ pub mod rifts_protocol {
             ErrorCode::InvalidBinStep
         );
 
-        // Simple fee calculation (0.7%)
-        let wrap_fee = amount.checked_mul(70).ok_or(ErrorCode::MathOverflow)?
-            .checked_div(10000).ok_or(ErrorCode::MathOverflow)?;
+        // Calculate fees (0.7% wrap fee)
+        let wrap_fee = rift.calculate_wrap_fee(amount)?;
         let amount_after_fee = amount.checked_sub(wrap_fee).ok_or(ErrorCode::MathOverflow)?;
 
         // CRITICAL FIX: Transfer underlying tokens to user's payer_token_a account
@@ -311,9 +487,23 @@ pub mod rifts_protocol {
             &authority_bump_slice,
         ]];
 
+        // Exactly one side must be the RIFT mint and the other the underlying mint,
+        // otherwise mint selection below would be ambiguous
+        let token_a_mint_key = ctx.accounts.token_a_mint.key();
+        let token_b_mint_key = ctx.accounts.token_b_mint.key();
+        require!(
+            rift.rift_mint != rift.underlying_mint,
+            ErrorCode::InvalidPoolMints
+        );
+        require!(
+            (token_a_mint_key == rift.rift_mint && token_b_mint_key == rift.underlying_mint) ||
+            (token_b_mint_key == rift.rift_mint && token_a_mint_key == rift.underlying_mint),
+            ErrorCode::InvalidPoolMints
+        );
+
         // Check which mint is the RIFT mint by comparing to rift.rift_mint
         // The rift.rift_mint field contains the rift token mint pubkey
-        let is_token_b_rift = ctx.accounts.token_b_mint.key() == rift.rift_mint;
+        let is_token_b_rift = token_b_mint_key == rift.rift_mint;
 
         // Mint RIFT tokens to the correct payer account
         // Meteora's initialize_pool will transfer these to the pool
@@ -347,35 +537,32 @@ pub mod rifts_protocol {
             &base_factor.to_le_bytes(),
         ];
 
-        // Calculate initial price (adjust as needed for your tokenomics)
-        let init_price = 1_000_000; // 0.001 quote per base token (with 9 decimal adjustment)
+        // Initial price must sit within max_price_deviation_bps of the rift's oracle average
+        let init_price = expected_init_price;
+        rift.validate_initial_price(init_price, max_price_deviation_bps)?;
 
         // Create official Meteora DAMM v2 pool using proper config-based approach
         // Based on Meteora docs, pools are created using a config key that defines parameters
-        // We'll use a public config that matches our desired fee structure (0.4% base fee with dynamic fee)
-
-        let meteora_config_key = Pubkey::from_str("82p7sVzQWZfCrmStPhsG8BYKwheQkUiXSs2wiqdhwNxr")
-            .map_err(|_| ErrorCode::InvalidPublicKey)?; // Config index 1: 0.25% base fee, collect fee mode 1, dynamic fee enabled
+        // The config (fee tier) is the rift's chosen one and must be on the governance-approved allowlist
+        let meteora_config_key = rift.meteora_config_key.unwrap_or(DEFAULT_METEORA_CONFIG);
+        require!(
+            ctx.accounts.config.key() == meteora_config_key,
+            ErrorCode::InvalidMeteoraConfig
+        );
+        require!(
+            ctx.accounts.config.owner == &METEORA_DAMM_V2_PROGRAM_ID,
+            ErrorCode::InvalidMeteoraConfig
+        );
+        require!(
+            ctx.accounts.meteora_config_allowlist.is_allowed(&meteora_config_key),
+            ErrorCode::MeteoraConfigNotAllowed
+        );
 
         // Calculate pool PDA - Meteora uses: ["pool", config, larger_mint, smaller_mint]
-        // Sort the mints to get firstMint (larger) and secondMint (smaller)
-        let mint1_bytes = ctx.accounts.token_a_mint.key().to_bytes();
-        let mint2_bytes = ctx.accounts.token_b_mint.key().to_bytes();
-
-        let (first_mint, second_mint) = if mint1_bytes > mint2_bytes {
-            (mint1_bytes, mint2_bytes)
-        } else {
-            (mint2_bytes, mint1_bytes)
-        };
-
-        let (expected_pool_pubkey, _) = Pubkey::find_program_address(
-            &[
-                b"pool",
-                meteora_config_key.as_ref(),
-                first_mint.as_ref(),
-                second_mint.as_ref(),
-            ],
-            &METEORA_DAMM_V2_PROGRAM_ID,
+        let expected_pool_pubkey = meteora_pool_pda(
+            &meteora_config_key,
+            &ctx.accounts.token_a_mint.key(),
+            &ctx.accounts.token_b_mint.key(),
         );
 
         // Verify the provided pool account matches the expected PDA
@@ -428,26 +615,91 @@ pub mod rifts_protocol {
 
 
 
+    /// Rewrite a Rift account created before the current layout so it deserializes again
+    /// (PriceData gained `source`, and every field after last_governance_update was appended)
+    /// The account grows to 8 + size_of::<Rift>() with the payer topping up rent; appended fields
+    /// take the defaults a new rift gets. Permissionless: the result does not depend on the caller.
+    pub fn migrate_rift(ctx: Context<MigrateRift>) -> Result<()> {
+        let rift_info = ctx.accounts.rift.to_account_info();
+        let new_len = 8 + std::mem::size_of::<Rift>();
+
+        // Current-layout accounts are always allocated at least this large
+        require!(rift_info.data_len() < new_len, ErrorCode::RiftAlreadyMigrated);
+
+        let migrated = migrate_legacy_rift(
+            &rift_info.try_borrow_data()?,
+            Clock::get()?.unix_timestamp,
+        )?;
+
+        let rent_needed = Rent::get()?
+            .minimum_balance(new_len)
+            .saturating_sub(rift_info.lamports());
+        if rent_needed > 0 {
+            anchor_lang::system_program::transfer(
+                CpiContext::new(
+                    ctx.accounts.system_program.to_account_info(),
+                    anchor_lang::system_program::Transfer {
+                        from: ctx.accounts.payer.to_account_info(),
+                        to: rift_info.clone(),
+                    },
+                ),
+                rent_needed,
+            )?;
+        }
+        rift_info.resize(new_len)?;
+
+        let mut data = rift_info.try_borrow_mut_data()?;
+        data.fill(0);
+        migrated.try_serialize(&mut &mut data[..])?;
+
+        msg!("Rift {} migrated to the current layout", rift_info.key());
+
+        Ok(())
+    }
+
     /// Initialize vault for rift
     pub fn initialize_vault(ctx: Context<InitializeVault>) -> Result<()> {
         // Vault is automatically initialized through the constraint
         Ok(())
     }
 
+    /// Initialize the fee vault that holds wrap fees apart from the backing vault
+    /// New rifts get their fee vault at creation; rifts brought over by migrate_rift call this once.
+    pub fn initialize_fee_vault(ctx: Context<InitializeFeeVault>) -> Result<()> {
+        let rift = &mut ctx.accounts.rift;
+        rift.fee_vault = ctx.accounts.fee_vault.key();
+
+        msg!("Fee vault initialized: {}", rift.fee_vault);
+
+        Ok(())
+    }
+
     /// STEP 1: Wrap SOL to RIFT tokens (stores SOL in vault, mints RIFT to user)
     /// This must be called BEFORE creating the Meteora pool
     pub fn wrap_tokens(
         ctx: Context<WrapTokens>,
         amount: u64,
+        proof: Vec<[u8; 32]>,
     ) -> Result<()> {
         let rift = &mut ctx.accounts.rift;
 
-        // Basic validation
-        require!(!rift.is_paused, ErrorCode::RiftPaused);
+        // Basic validation (maintenance pauses may have expired on their own)
+        require!(!rift.is_paused_at(Clock::get()?.unix_timestamp), ErrorCode::RiftPaused);
         require!(amount > 0, ErrorCode::InvalidAmount);
+        require!(amount >= rift.min_wrap_amount, ErrorCode::AmountBelowMinimum);
         require!(amount <= 1_000_000_000_000_000, ErrorCode::AmountTooLarge);
+        rift.check_deposit_cap(amount)?;
+        rift.check_wrap_size(amount)?;
 
-        // Transfer underlying tokens from user to vault
+        // Private phase: only whitelisted users may wrap while a root is set
+        verify_whitelist(rift.whitelist_merkle_root, &ctx.accounts.user.key(), &proof)?;
+
+        // Calculate fees (0.7% wrap fee, discounted for qualifying LP stakers)
+        let wrap_fee = rift.calculate_wrap_fee(amount)?;
+        let wrap_fee = rift.apply_staker_discount(wrap_fee, ctx.accounts.staker_account.as_deref())?;
+        let amount_after_fee = amount.checked_sub(wrap_fee).ok_or(ErrorCode::MathOverflow)?;
+
+        // Transfer the backing portion from user to vault
         let transfer_ctx = CpiContext::new(
             ctx.accounts.token_program.to_account_info(),
             Transfer {
@@ -456,12 +708,22 @@ pub mod rifts_protocol {
                 authority: ctx.accounts.user.to_account_info(),
             },
         );
-        transfer(transfer_ctx, amount)?;
+        transfer(transfer_ctx, amount_after_fee)?;
 
-        // Calculate fees (0.7% wrap fee)
-        let wrap_fee = amount.checked_mul(70).ok_or(ErrorCode::MathOverflow)?
-            .checked_div(10000).ok_or(ErrorCode::MathOverflow)?;
-        let amount_after_fee = amount.checked_sub(wrap_fee).ok_or(ErrorCode::MathOverflow)?;
+        // Transfer the wrap fee into the fee vault, never mixed with backing
+        if wrap_fee > 0 {
+            let fee_transfer_ctx = CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.user_underlying.to_account_info(),
+                    to: ctx.accounts.fee_vault.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            );
+            transfer(fee_transfer_ctx, wrap_fee)?;
+            rift.accrued_underlying_fees = rift.accrued_underlying_fees
+                .checked_add(wrap_fee).ok_or(ErrorCode::MathOverflow)?;
+        }
 
         // Mint RIFT tokens to user
         let rift_key = rift.key();
@@ -484,14 +746,220 @@ pub mod rifts_protocol {
         );
         mint_to(mint_ctx, amount_after_fee)?;
 
-        // Update rift state
+        // Update rift state (the fee sits in fee_vault, so only amount_after_fee is backing)
         rift.total_underlying_wrapped = rift.total_underlying_wrapped
-            .checked_add(amount).ok_or(ErrorCode::MathOverflow)?;
+            .checked_add(amount_after_fee).ok_or(ErrorCode::MathOverflow)?;
         rift.total_rift_minted = rift.total_rift_minted
             .checked_add(amount_after_fee).ok_or(ErrorCode::MathOverflow)?;
+        rift.bootstrap_complete = true;
+
+        // Mint supply must never exceed total_rift_minted
+        rift.assert_supply_invariant(&mut ctx.accounts.rift_mint)?;
+
+        // Start the unwrap cooldown for this user
+        ctx.accounts.wrap_record.record_wrap(
+            ctx.accounts.user.key(),
+            rift.key(),
+            Clock::get()?.unix_timestamp,
+            ctx.bumps.wrap_record,
+        );
 
         msg!("✅ Wrapped {} SOL → {} RIFT", amount, amount_after_fee);
 
+        emit!(TokensWrapped {
+            rift: rift.key(),
+            user: ctx.accounts.user.key(),
+            amount_in: amount,
+            fee_paid: wrap_fee,
+            rift_tokens_minted: amount_after_fee,
+        });
+
+        Ok(())
+    }
+
+    /// Creator-only fee-free 1:1 mint to seed liquidity before launch
+    /// Disabled for good once the first public wrap lands (bootstrap_complete)
+    pub fn bootstrap_mint(
+        ctx: Context<BootstrapMint>,
+        amount: u64,
+    ) -> Result<()> {
+        let rift = &mut ctx.accounts.rift;
+
+        require!(!rift.is_paused_at(Clock::get()?.unix_timestamp), ErrorCode::RiftPaused);
+        require!(amount > 0, ErrorCode::InvalidAmount);
+        // Pre-launch only: everything minted so far must be bootstrap supply
+        require!(
+            !rift.bootstrap_complete && rift.total_rift_minted == rift.bootstrap_minted,
+            ErrorCode::BootstrapComplete
+        );
+        let new_bootstrap_minted = rift.bootstrap_minted
+            .checked_add(amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+        require!(
+            new_bootstrap_minted <= rift.bootstrap_cap,
+            ErrorCode::BootstrapCapExceeded
+        );
+        rift.check_deposit_cap(amount)?;
+
+        // Transfer underlying tokens from creator to vault
+        let transfer_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.creator_underlying.to_account_info(),
+                to: ctx.accounts.vault.to_account_info(),
+                authority: ctx.accounts.creator.to_account_info(),
+            },
+        );
+        transfer(transfer_ctx, amount)?;
+
+        // Mint RIFT 1:1 with no wrap fee
+        let rift_key = rift.key();
+        let bump_seed = [ctx.bumps.rift_mint_authority];
+        let signer_seeds: &[&[u8]] = &[
+            b"rift_mint_auth",
+            rift_key.as_ref(),
+            &bump_seed,
+        ];
+        let signer = &[&signer_seeds[..]];
+
+        let mint_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.rift_mint.to_account_info(),
+                to: ctx.accounts.creator_rift_tokens.to_account_info(),
+                authority: ctx.accounts.rift_mint_authority.to_account_info(),
+            },
+            signer,
+        );
+        mint_to(mint_ctx, amount)?;
+
+        rift.total_underlying_wrapped = rift.total_underlying_wrapped
+            .checked_add(amount).ok_or(ErrorCode::MathOverflow)?;
+        rift.total_rift_minted = rift.total_rift_minted
+            .checked_add(amount).ok_or(ErrorCode::MathOverflow)?;
+        rift.bootstrap_minted = new_bootstrap_minted;
+
+        // Mint supply must never exceed total_rift_minted
+        rift.assert_supply_invariant(&mut ctx.accounts.rift_mint)?;
+
+        emit!(BootstrapMinted {
+            rift: rift_key,
+            creator: ctx.accounts.creator.key(),
+            amount,
+            bootstrap_minted: rift.bootstrap_minted,
+            bootstrap_cap: rift.bootstrap_cap,
+        });
+
+        Ok(())
+    }
+
+    /// Wrap the signer's underlying tokens and mint the RIFT to a beneficiary
+    /// For aggregators and smart wallets wrapping on behalf of their users
+    pub fn wrap_tokens_for(
+        ctx: Context<WrapTokensFor>,
+        beneficiary: Pubkey,
+        amount: u64,
+        proof: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        let rift = &mut ctx.accounts.rift;
+
+        // Basic validation (maintenance pauses may have expired on their own)
+        require!(!rift.is_paused_at(Clock::get()?.unix_timestamp), ErrorCode::RiftPaused);
+        require!(amount > 0, ErrorCode::InvalidAmount);
+        require!(amount >= rift.min_wrap_amount, ErrorCode::AmountBelowMinimum);
+        require!(amount <= 1_000_000_000_000_000, ErrorCode::AmountTooLarge);
+        rift.check_deposit_cap(amount)?;
+        rift.check_wrap_size(amount)?;
+
+        // Private phase: the beneficiary ends up holding the RIFT, so they must be whitelisted
+        verify_whitelist(rift.whitelist_merkle_root, &beneficiary, &proof)?;
+
+        // Calculate fees (0.7% wrap fee)
+        let wrap_fee = rift.calculate_wrap_fee(amount)?;
+        let amount_after_fee = amount.checked_sub(wrap_fee).ok_or(ErrorCode::MathOverflow)?;
+
+        // Transfer the backing portion from the payer to vault
+        let transfer_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.payer_underlying.to_account_info(),
+                to: ctx.accounts.vault.to_account_info(),
+                authority: ctx.accounts.payer.to_account_info(),
+            },
+        );
+        transfer(transfer_ctx, amount_after_fee)?;
+
+        // Transfer the wrap fee into the fee vault, never mixed with backing
+        if wrap_fee > 0 {
+            let fee_transfer_ctx = CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.payer_underlying.to_account_info(),
+                    to: ctx.accounts.fee_vault.to_account_info(),
+                    authority: ctx.accounts.payer.to_account_info(),
+                },
+            );
+            transfer(fee_transfer_ctx, wrap_fee)?;
+            rift.accrued_underlying_fees = rift.accrued_underlying_fees
+                .checked_add(wrap_fee).ok_or(ErrorCode::MathOverflow)?;
+        }
+
+        // Mint RIFT tokens to the beneficiary
+        let rift_key = rift.key();
+        let bump_seed = [ctx.bumps.rift_mint_authority];
+        let signer_seeds: &[&[u8]] = &[
+            b"rift_mint_auth",
+            rift_key.as_ref(),
+            &bump_seed,
+        ];
+        let signer = &[&signer_seeds[..]];
+
+        let mint_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            MintTo {
+                mint: ctx.accounts.rift_mint.to_account_info(),
+                to: ctx.accounts.beneficiary_rift_tokens.to_account_info(),
+                authority: ctx.accounts.rift_mint_authority.to_account_info(),
+            },
+            signer,
+        );
+        mint_to(mint_ctx, amount_after_fee)?;
+
+        // Update rift state (the fee sits in fee_vault, so only amount_after_fee is backing)
+        rift.total_underlying_wrapped = rift.total_underlying_wrapped
+            .checked_add(amount_after_fee).ok_or(ErrorCode::MathOverflow)?;
+        rift.total_rift_minted = rift.total_rift_minted
+            .checked_add(amount_after_fee).ok_or(ErrorCode::MathOverflow)?;
+        rift.bootstrap_complete = true;
+
+        // Mint supply must never exceed total_rift_minted
+        rift.assert_supply_invariant(&mut ctx.accounts.rift_mint)?;
+
+        // Only a self-wrap restarts the unwrap cooldown; third parties must not be able
+        // to keep a beneficiary locked by wrapping dust for them
+        let last_wrap_time = if ctx.accounts.payer.key() == beneficiary {
+            Clock::get()?.unix_timestamp
+        } else {
+            ctx.accounts.wrap_record.last_wrap_time
+        };
+        ctx.accounts.wrap_record.record_wrap(
+            beneficiary,
+            rift.key(),
+            last_wrap_time,
+            ctx.bumps.wrap_record,
+        );
+
+        msg!("✅ Wrapped {} → {} RIFT for {}", amount, amount_after_fee, beneficiary);
+
+        emit!(TokensWrappedFor {
+            rift: rift.key(),
+            payer: ctx.accounts.payer.key(),
+            beneficiary,
+            amount_in: amount,
+            fee_paid: wrap_fee,
+            rift_tokens_minted: amount_after_fee,
+        });
+
         Ok(())
     }
 
@@ -499,29 +967,92 @@ pub mod rifts_protocol {
     /// User must have RIFT and SOL tokens from wrapping first
     /// Pool creation is done via JavaScript SDK, this just tracks it
     /// **SECURITY FIX #48**: Store all pool state for validation
+    /// DAMM v2 liquidity is held in position NFTs with no fungible LP mint, so a Meteora-bound
+    /// rift has no lp_token_mint and accepts no LP stake
     pub fn set_pool_address(
         ctx: Context<SetPoolAddress>,
         pool_address: Pubkey,
         pool_authority: Pubkey,
         token_a_vault: Pubkey,
         token_b_vault: Pubkey,
+        pool_config: Pubkey,
+        max_price_deviation_bps: u16, // Allowed deviation of the pool price from oracle average
     ) -> Result<()> {
         let rift = &mut ctx.accounts.rift;
 
         require!(rift.creator == ctx.accounts.user.key(), ErrorCode::Unauthorized);
         require!(rift.liquidity_pool.is_none(), ErrorCode::PoolAlreadyInitialized);
+        require!(
+            ctx.accounts.meteora_config_allowlist.is_allowed(&pool_config),
+            ErrorCode::MeteoraConfigNotAllowed
+        );
+
+        // The pool must be the rift pair's Meteora pool created under pool_config
+        require!(ctx.accounts.pool.key() == pool_address, ErrorCode::InvalidPoolAccount);
+        let pool_state = read_meteora_pool_state(&ctx.accounts.pool)?;
+        require!(
+            (pool_state.token_a_mint == rift.rift_mint && pool_state.token_b_mint == rift.underlying_mint)
+                || (pool_state.token_a_mint == rift.underlying_mint && pool_state.token_b_mint == rift.rift_mint),
+            ErrorCode::InvalidPoolMints
+        );
+        require!(
+            pool_address == meteora_pool_pda(&pool_config, &pool_state.token_a_mint, &pool_state.token_b_mint),
+            ErrorCode::InvalidMeteoraConfig
+        );
+        require!(
+            token_a_vault == pool_state.token_a_vault && token_b_vault == pool_state.token_b_vault,
+            ErrorCode::InvalidPoolVault
+        );
+
+        // The externally created pool must be priced near the rift's oracle average
+        let pool_price = parse_meteora_pool_price(&ctx.accounts.pool, &rift.rift_mint)?;
+        rift.validate_initial_price(pool_price, max_price_deviation_bps)?;
 
         // **SECURITY FIX #48**: Store all Meteora pool state for later validation
         rift.liquidity_pool = Some(pool_address);
         rift.pool_authority = Some(pool_authority);
         rift.pool_token_a_vault = Some(token_a_vault);
         rift.pool_token_b_vault = Some(token_b_vault);
+        rift.pool_config = Some(pool_config);
+        rift.lp_token_mint = None;
 
         msg!("✅ Set Meteora pool state:");
         msg!("  Pool: {}", pool_address);
         msg!("  Authority: {}", pool_authority);
         msg!("  Token A Vault: {}", token_a_vault);
         msg!("  Token B Vault: {}", token_b_vault);
+        msg!("  Config: {}", pool_config);
+
+        Ok(())
+    }
+
+    /// Clear the Meteora pool binding so a new pool can be set (creator only, pool must be empty)
+    pub fn unset_pool_address(
+        ctx: Context<UnsetPoolAddress>,
+    ) -> Result<()> {
+        let rift = &mut ctx.accounts.rift;
+
+        let pool = rift.liquidity_pool.ok_or(ErrorCode::PoolNotInitialized)?;
+        require!(
+            rift.total_liquidity_underlying == 0 && rift.total_liquidity_rift == 0,
+            ErrorCode::PoolHasLiquidity
+        );
+
+        rift.liquidity_pool = None;
+        rift.pool_authority = None;
+        rift.pool_token_a_vault = None;
+        rift.pool_token_b_vault = None;
+        rift.pool_config = None;
+        rift.lp_token_mint = None;
+
+        emit!(PoolUnbound {
+            rift: rift.key(),
+            pool,
+            creator: ctx.accounts.creator.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        msg!("Unbound Meteora pool {}", pool);
 
         Ok(())
     }
@@ -530,13 +1061,16 @@ pub mod rifts_protocol {
     pub fn wrap_and_add_liquidity(
         ctx: Context<WrapAndAddLiquidity>,
         amount: u64,
-        liquidity_to_add: u128,  // Pre-calculated off-chain using sqrt(amount_a * amount_b)
+        liquidity_to_add: u128,  // Must be within LIQUIDITY_TOLERANCE_BPS of sqrt(amount_a * amount_b)
     ) -> Result<()> {
         let rift = &mut ctx.accounts.rift;
 
-        // Basic validation
-        require!(!rift.is_paused, ErrorCode::RiftPaused);
+        // Basic validation (maintenance pauses may have expired on their own)
+        require!(!rift.is_paused_at(Clock::get()?.unix_timestamp), ErrorCode::RiftPaused);
         require!(amount > 0, ErrorCode::InvalidAmount);
+        require!(amount >= rift.min_wrap_amount, ErrorCode::AmountBelowMinimum);
+        rift.check_deposit_cap(amount)?;
+        rift.check_wrap_size(amount)?;
 
         // Verify pool exists
         require!(rift.liquidity_pool.is_some(), ErrorCode::PoolNotInitialized);
@@ -570,14 +1104,28 @@ pub mod rifts_protocol {
         rift.reentrancy_guard = true;
 
         // Calculate fees
-        let wrap_fee = amount.checked_mul(70).ok_or(ErrorCode::MathOverflow)?
-            .checked_div(10000).ok_or(ErrorCode::MathOverflow)?;
+        let wrap_fee = rift.calculate_wrap_fee(amount)?;
         let amount_after_fee = amount.checked_sub(wrap_fee).ok_or(ErrorCode::MathOverflow)?;
 
-        // Mint RIFT tokens to user
-        let rift_key = rift.key();
-        let bump_seed = [ctx.bumps.rift_mint_authority];
-        let signer_seeds: &[&[u8]] = &[
+        // Collect the wrap fee into the fee vault, same as wrap_tokens
+        if wrap_fee > 0 {
+            let fee_transfer_ctx = CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.user_underlying.to_account_info(),
+                    to: ctx.accounts.fee_vault.to_account_info(),
+                    authority: ctx.accounts.user.to_account_info(),
+                },
+            );
+            transfer(fee_transfer_ctx, wrap_fee)?;
+            rift.accrued_underlying_fees = rift.accrued_underlying_fees
+                .checked_add(wrap_fee).ok_or(ErrorCode::MathOverflow)?;
+        }
+
+        // Mint RIFT tokens to user
+        let rift_key = rift.key();
+        let bump_seed = [ctx.bumps.rift_mint_authority];
+        let signer_seeds: &[&[u8]] = &[
             b"rift_mint_auth",
             rift_key.as_ref(),
             &bump_seed,
@@ -595,7 +1143,20 @@ pub mod rifts_protocol {
         );
         mint_to(mint_ctx, amount_after_fee)?;
 
-        // Use pre-calculated liquidity delta (sqrt of product)
+        // Derive liquidity on-chain from the amounts actually being deposited
+        ctx.accounts.user_rift_tokens.reload()?;
+        let deposit_a = ctx.accounts.user_underlying.amount.min(amount_after_fee) as u128;
+        let deposit_b = ctx.accounts.user_rift_tokens.amount.min(amount_after_fee) as u128;
+        let expected_liquidity = integer_sqrt(
+            deposit_a.checked_mul(deposit_b).ok_or(ErrorCode::MathOverflow)?
+        );
+        let tolerance = expected_liquidity
+            .checked_mul(LIQUIDITY_TOLERANCE_BPS).ok_or(ErrorCode::MathOverflow)?
+            / 10000;
+        require!(
+            liquidity_to_add.abs_diff(expected_liquidity) <= tolerance,
+            ErrorCode::LiquidityOutOfTolerance
+        );
         let liquidity_delta = liquidity_to_add;
         let token_a_threshold = amount_after_fee.checked_mul(101).ok_or(ErrorCode::MathOverflow)?
             .checked_div(100).ok_or(ErrorCode::MathOverflow)?;
@@ -636,20 +1197,40 @@ pub mod rifts_protocol {
 
         cpi::add_liquidity(meteora_ctx, add_liquidity_params)?;
 
-        // Update state
+        // Update state (the fee sits in fee_vault, so only amount_after_fee is backing)
         rift.total_underlying_wrapped = rift.total_underlying_wrapped
-            .checked_add(amount).ok_or(ErrorCode::MathOverflow)?;
+            .checked_add(amount_after_fee).ok_or(ErrorCode::MathOverflow)?;
         rift.total_rift_minted = rift.total_rift_minted
             .checked_add(amount_after_fee).ok_or(ErrorCode::MathOverflow)?;
+        rift.bootstrap_complete = true;
         rift.total_liquidity_underlying = rift.total_liquidity_underlying
             .checked_add(amount_after_fee).ok_or(ErrorCode::MathOverflow)?;
         rift.total_liquidity_rift = rift.total_liquidity_rift
             .checked_add(amount_after_fee).ok_or(ErrorCode::MathOverflow)?;
 
+        // Mint supply must never exceed total_rift_minted
+        rift.assert_supply_invariant(&mut ctx.accounts.rift_mint)?;
+
+        // Start the unwrap cooldown for this user
+        ctx.accounts.wrap_record.record_wrap(
+            ctx.accounts.user.key(),
+            rift.key(),
+            Clock::get()?.unix_timestamp,
+            ctx.bumps.wrap_record,
+        );
+
         rift.reentrancy_guard = false;
 
         msg!("✅ Wrapped {} SOL and added liquidity to Meteora", amount);
 
+        emit!(TokensWrapped {
+            rift: rift.key(),
+            user: ctx.accounts.user.key(),
+            amount_in: amount,
+            fee_paid: wrap_fee,
+            rift_tokens_minted: amount_after_fee,
+        });
+
         Ok(())
     }
 
@@ -665,8 +1246,12 @@ pub mod rifts_protocol {
         let rift = &mut ctx.accounts.rift;
 
         // Validation
-        require!(!rift.is_paused, ErrorCode::RiftPaused);
+        require!(!rift.is_paused_at(Clock::get()?.unix_timestamp), ErrorCode::RiftPaused);
         require!(rift_amount > 0, ErrorCode::InvalidAmount);
+        rift.check_wrap_cooldown(
+            WrapRecord::last_wrap_time_of(&ctx.accounts.wrap_record)?,
+            Clock::get()?.unix_timestamp,
+        )?;
         require!(rift.liquidity_pool.is_some(), ErrorCode::PoolNotInitialized);
         require!(
             ctx.accounts.pool.key() == rift.liquidity_pool.unwrap(),
@@ -698,12 +1283,12 @@ pub mod rifts_protocol {
         rift.reentrancy_guard = true;
 
         // Calculate unwrap fee
-        let unwrap_fee = rift_amount.checked_mul(70).ok_or(ErrorCode::MathOverflow)?
-            .checked_div(10000).ok_or(ErrorCode::MathOverflow)?;
+        let unwrap_fee = rift.calculate_wrap_fee(rift_amount)?;
         let amount_after_fee = rift_amount.checked_sub(unwrap_fee).ok_or(ErrorCode::MathOverflow)?;
 
         // Use the liquidity amount calculated off-chain
         let liquidity_delta = liquidity_to_remove;
+        rift.check_liquidity_removal(liquidity_delta)?;
 
         msg!("Removing {} liquidity for {} RIFT (pre-calculated off-chain)",
              liquidity_delta, amount_after_fee);
@@ -741,16 +1326,16 @@ pub mod rifts_protocol {
 
         cpi::remove_liquidity(meteora_ctx, remove_liquidity_params)?;
 
-        // Burn RIFT tokens
-        let burn_ctx = CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
-            anchor_spl::token::Burn {
-                mint: ctx.accounts.rift_mint.to_account_info(),
-                from: ctx.accounts.user_rift_tokens.to_account_info(),
-                authority: ctx.accounts.user.to_account_info(),
-            },
-        );
-        anchor_spl::token::burn(burn_ctx, rift_amount)?;
+        // Burn RIFT tokens (or send them to the incinerator, per rift.burn_mode)
+        dispose_redeemed_rift(
+            rift,
+            &ctx.accounts.token_program,
+            &ctx.accounts.rift_mint,
+            &ctx.accounts.user_rift_tokens,
+            &ctx.accounts.user,
+            ctx.accounts.incinerator_rift_tokens.as_ref(),
+            rift_amount,
+        )?;
 
         // Update state
         rift.total_underlying_wrapped = rift.total_underlying_wrapped.saturating_sub(amount_after_fee);
@@ -758,6 +1343,9 @@ pub mod rifts_protocol {
         rift.total_liquidity_underlying = rift.total_liquidity_underlying.saturating_sub(amount_after_fee);
         rift.total_liquidity_rift = rift.total_liquidity_rift.saturating_sub(amount_after_fee);
 
+        // Mint supply must never exceed total_rift_minted
+        rift.assert_supply_invariant(&mut ctx.accounts.rift_mint)?;
+
         rift.reentrancy_guard = false;
 
         msg!("✅ Removed liquidity and unwrapped {} RIFT → {} SOL", rift_amount, amount_after_fee);
@@ -769,10 +1357,10 @@ pub mod rifts_protocol {
     pub fn admin_fix_vault_conflict(ctx: Context<AdminFixVaultConflict>) -> Result<()> {
         let rift = &ctx.accounts.rift;
 
-        // Only program authority can call this
+        // Only the protocol admin can call this
         require!(
-            ctx.accounts.program_authority.key() == rift.creator,
-            ErrorCode::Unauthorized
+            ctx.accounts.program_authority.key() == ctx.accounts.protocol_config.protocol_admin,
+            ErrorCode::UnauthorizedAdmin
         );
 
         // Get the current vault and expected authority
@@ -802,16 +1390,24 @@ pub mod rifts_protocol {
     }
 
     /// Initialize Meteora pool for rift (separate from wrapping)
+    /// The creator receives the initial LP tokens from the rift's ["lp_mint", rift] PDA mint,
+    /// which becomes the only token stake_lp_tokens accepts
+    /// The creator seeds both sides, and the seeded price must sit near the oracle average
     pub fn initialize_pool(
         ctx: Context<InitializePool>,
         initial_rift_amount: u64,
+        initial_underlying_amount: u64,
         trading_fee_bps: u16,
         bin_step: u16,
+        max_price_deviation_bps: u16, // Allowed deviation of the seeded price from oracle average
     ) -> Result<()> {
         let rift = &mut ctx.accounts.rift;
 
         // Validation
+        require!(rift.creator == ctx.accounts.user.key(), ErrorCode::Unauthorized);
+        require!(rift.liquidity_pool.is_none(), ErrorCode::PoolAlreadyInitialized);
         require!(initial_rift_amount > 0, ErrorCode::InvalidAmount);
+        require!(initial_underlying_amount > 0, ErrorCode::InvalidAmount);
         require!(trading_fee_bps <= 100, ErrorCode::InvalidTradingFee);
         require!(
             bin_step == 1 || bin_step == 5 || bin_step == 10 || bin_step == 25 ||
@@ -819,10 +1415,19 @@ pub mod rifts_protocol {
             ErrorCode::InvalidBinStep
         );
 
+        // The seeded ratio (underlying per RIFT, 6 decimals) must sit near the oracle average
+        let init_price = u128::from(initial_underlying_amount)
+            .checked_mul(1_000_000)
+            .ok_or(ErrorCode::MathOverflow)?
+            / u128::from(initial_rift_amount);
+        let init_price = u64::try_from(init_price).map_err(|_| ErrorCode::MathOverflow)?;
+        rift.validate_initial_price(init_price, max_price_deviation_bps)?;
+
         // Store pool parameters in rift state
         rift.pool_trading_fee_bps = trading_fee_bps;
         rift.bin_step = bin_step;
         rift.liquidity_pool = Some(ctx.accounts.pool_underlying.key());
+        rift.lp_token_mint = Some(ctx.accounts.lp_mint.key());
 
         // Mint initial rift tokens to pool
         let rift_key = rift.key();
@@ -844,15 +1449,54 @@ pub mod rifts_protocol {
         );
         token::mint_to(mint_ctx, initial_rift_amount)?;
 
+        // Creator supplies the underlying side
+        let deposit_ctx = CpiContext::new(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.user_underlying.to_account_info(),
+                to: ctx.accounts.pool_underlying.to_account_info(),
+                authority: ctx.accounts.user.to_account_info(),
+            },
+        );
+        transfer(deposit_ctx, initial_underlying_amount)?;
+
+        // LP tokens for the initial liquidity, 1:1 with the rift tokens supplied
+        let pool_auth_bump = [ctx.bumps.pool_authority];
+        let pool_auth_seeds: &[&[u8]] = &[
+            b"pool_auth",
+            rift_key.as_ref(),
+            &pool_auth_bump,
+        ];
+        token::mint_to(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                MintTo {
+                    mint: ctx.accounts.lp_mint.to_account_info(),
+                    to: ctx.accounts.user_lp_tokens.to_account_info(),
+                    authority: ctx.accounts.pool_authority.to_account_info(),
+                },
+                &[pool_auth_seeds],
+            ),
+            initial_rift_amount,
+        )?;
+
         // Update pool state
         rift.total_liquidity_rift = initial_rift_amount;
+        rift.total_liquidity_underlying = initial_underlying_amount;
         rift.lp_token_supply = initial_rift_amount; // Simple 1:1 for now
+        rift.total_rift_minted = rift.total_rift_minted
+            .checked_add(initial_rift_amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        // Mint supply must never exceed total_rift_minted
+        rift.assert_supply_invariant(&mut ctx.accounts.rift_mint)?;
 
         emit!(PoolInitialized {
             rift: rift.key(),
             pool_underlying: ctx.accounts.pool_underlying.key(),
             pool_rift: ctx.accounts.pool_rift.key(),
             initial_rift_amount,
+            initial_underlying_amount,
             trading_fee_bps,
             bin_step,
         });
@@ -874,8 +1518,14 @@ pub mod rifts_protocol {
         require!(!rift.reentrancy_guard, ErrorCode::ReentrancyDetected);
         rift.reentrancy_guard = true;
 
-        // Check if rift is paused
-        require!(!rift.is_paused, ErrorCode::RiftPaused);
+        // Check if rift is paused (honoring pause auto-expiry)
+        require!(!rift.is_paused_at(Clock::get()?.unix_timestamp), ErrorCode::RiftPaused);
+
+        // Anti-MEV: no unwrap within wrap_cooldown_seconds of this user's last wrap
+        rift.check_wrap_cooldown(
+            WrapRecord::last_wrap_time_of(&ctx.accounts.wrap_record)?,
+            Clock::get()?.unix_timestamp,
+        )?;
 
         // Validate amount
         require!(rift_token_amount > 0, ErrorCode::InvalidAmount);
@@ -888,13 +1538,8 @@ pub mod rifts_protocol {
         );
 
         // Calculate unwrap fee (0.7%)
-        let unwrap_fee = u64::try_from(
-            (rift_token_amount as u128)
-                .checked_mul(70)
-                .ok_or(ErrorCode::MathOverflow)?
-                .checked_div(10000)
-                .ok_or(ErrorCode::MathOverflow)?
-        ).map_err(|_| ErrorCode::MathOverflow)?;
+        let unwrap_fee = rift.calculate_wrap_fee(rift_token_amount)?;
+        let unwrap_fee = rift.apply_staker_discount(unwrap_fee, ctx.accounts.staker_account.as_deref())?;
         let amount_after_fee = rift_token_amount
             .checked_sub(unwrap_fee)
             .ok_or(ErrorCode::MathOverflow)?;
@@ -915,20 +1560,10 @@ pub mod rifts_protocol {
             .checked_mul(amount_128)
             .ok_or(ErrorCode::MathOverflow)?;
 
-        // Integer square root using Newton's method
-        let liquidity_delta = if product == 0 {
-            0
-        } else {
-            let mut x = product;
-            let mut y = (x + 1) / 2;
-            while y < x {
-                x = y;
-                y = (x + product / x) / 2;
-            }
-            x
-        };
+        let liquidity_delta = integer_sqrt(product);
 
         msg!("📊 Calculated liquidity_delta: {} (from amount: {})", liquidity_delta, amount_after_fee);
+        rift.check_liquidity_removal(liquidity_delta)?;
 
         // Set minimal slippage thresholds
         let token_a_threshold = 1u64; // Min 1 lamport of WSOL
@@ -972,17 +1607,15 @@ pub mod rifts_protocol {
         msg!("✅ Removed liquidity from Meteora pool");
 
         // Burn the RIFT tokens (both original and what came back from pool)
-        let burn_ctx = CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
-            anchor_spl::token::Burn {
-                mint: ctx.accounts.rift_mint.to_account_info(),
-                from: ctx.accounts.user_rift_tokens.to_account_info(),
-                authority: ctx.accounts.user.to_account_info(),
-            },
-        );
-        anchor_spl::token::burn(burn_ctx, rift_token_amount)?;
-
-        msg!("✅ Burned {} RIFT tokens", rift_token_amount);
+        dispose_redeemed_rift(
+            rift,
+            &ctx.accounts.token_program,
+            &ctx.accounts.rift_mint,
+            &ctx.accounts.user_rift_tokens,
+            &ctx.accounts.user,
+            ctx.accounts.incinerator_rift_tokens.as_ref(),
+            rift_token_amount,
+        )?;
 
         // Update rift state
         rift.total_underlying_wrapped = rift.total_underlying_wrapped.saturating_sub(amount_after_fee);
@@ -1000,10 +1633,8 @@ pub mod rifts_protocol {
             .checked_add(rift_token_amount)
             .ok_or(ErrorCode::MathOverflow)?;
 
-        // Update volume with checked arithmetic
-        rift.total_volume_24h = rift.total_volume_24h
-            .checked_add(amount_after_fee)
-            .ok_or(ErrorCode::MathOverflow)?;
+        // Update sliding 24h volume (decays old volume before adding)
+        rift.record_volume(amount_after_fee, Clock::get()?.unix_timestamp)?;
 
         // Update oracle timestamp to mark activity
         rift.last_oracle_update = Clock::get()?.unix_timestamp;
@@ -1016,9 +1647,13 @@ pub mod rifts_protocol {
         let clock = Clock::get()?;
         let should_rebalance = rift.should_trigger_rebalance(clock.unix_timestamp)?;
         if should_rebalance {
-            rift.trigger_automatic_rebalance(clock.unix_timestamp)?;
+            let rift_key = rift.key();
+            rift.trigger_automatic_rebalance(rift_key, clock.unix_timestamp)?;
         }
 
+        // Mint supply must never exceed total_rift_minted
+        rift.assert_supply_invariant(&mut ctx.accounts.rift_mint)?;
+
         // **CRITICAL FIX**: Release reentrancy guard
         rift.reentrancy_guard = false;
 
@@ -1043,9 +1678,9 @@ pub mod rifts_protocol {
     ) -> Result<()> {
         let rift = &mut ctx.accounts.rift;
 
-        // **SECURITY FIX #50**: Validate oracle authority (creator or governance)
+        // **SECURITY FIX #50**: Validate oracle authority (creator or a listed oracle pusher)
         require!(
-            ctx.accounts.oracle_authority.key() == rift.creator,
+            rift.is_oracle_authority(&ctx.accounts.oracle_authority.key()),
             ErrorCode::Unauthorized
         );
 
@@ -1058,101 +1693,23 @@ pub mod rifts_protocol {
             ErrorCode::OracleAccountMismatch
         );
 
-        // **SECURITY FIX #50**: Validate Pyth account ownership
-        let pyth_program_id = Pubkey::from_str_const("FsJ3A3u2vn5cTVofAjvy6y5kwABJAqYWpe4975bi2epH");
-        require!(
-            ctx.accounts.pyth_price_account.owner == &pyth_program_id,
-            ErrorCode::InvalidOracleOwner
-        );
-
-        // Parse Pyth price data
-        let pyth_price_data = &ctx.accounts.pyth_price_account.data.borrow();
-        require!(pyth_price_data.len() >= 240, ErrorCode::InvalidOracleData);
-
-        // Pyth price account layout:
-        // 0-4: magic (0xa1b2c3d4)
-        // 4-8: version
-        // 8-12: type
-        // 12-16: size
-        // 16-48: product account
-        // 48-80: next price account
-        // 80-88: aggregate price (i64)
-        // 88-96: confidence (u64)
-        // 96-100: status (u32)
-        // 100-104: corp_act (u32)
-        // 104-112: publish_time (i64)
-        // 112-120: prev_publish_time (i64)
-        // 120-128: prev_price (i64)
-        // 128-136: prev_conf (u64)
-        // ... more fields
-
-        let price_i64 = i64::from_le_bytes(
-            pyth_price_data[80..88].try_into().map_err(|_| ErrorCode::InvalidOracleData)?
-        );
-        let confidence_u64 = u64::from_le_bytes(
-            pyth_price_data[88..96].try_into().map_err(|_| ErrorCode::InvalidOracleData)?
-        );
-        let publish_time_i64 = i64::from_le_bytes(
-            pyth_price_data[104..112].try_into().map_err(|_| ErrorCode::InvalidOracleData)?
-        );
-        let exponent_i32 = i32::from_le_bytes(
-            pyth_price_data[20..24].try_into().map_err(|_| ErrorCode::InvalidOracleData)?
-        );
-
-        // Convert price to u64 (handle negative prices as error)
-        require!(price_i64 > 0, ErrorCode::InvalidOraclePrice);
-        let price = price_i64 as u64;
-        let confidence = confidence_u64;
-
-        // **SECURITY FIX #50**: Validate staleness (max 5 minutes)
         let current_time = Clock::get()?.unix_timestamp;
-        const MAX_AGE_SECONDS: i64 = 300; // 5 minutes
-        require!(
-            current_time - publish_time_i64 <= MAX_AGE_SECONDS,
-            ErrorCode::OraclePriceStale
-        );
-
-        msg!("Pyth price age: {} seconds", current_time - publish_time_i64);
-
-        // **SECURITY FIX #50**: Validate confidence (confidence should be <= 5% of price)
-        let max_confidence = price.checked_mul(5).ok_or(ErrorCode::MathOverflow)?
-            .checked_div(100).ok_or(ErrorCode::MathOverflow)?;
-        require!(
-            confidence <= max_confidence,
-            ErrorCode::OracleConfidenceTooLow
-        );
-
-        msg!("Pyth confidence: {} (max allowed: {})", confidence, max_confidence);
-
-        // **SECURITY FIX #50**: Validate exponent/decimals (-8 to -6 typical for USD pairs)
-        require!(
-            exponent_i32 >= -18 && exponent_i32 <= 0,
-            ErrorCode::InvalidOracleExponent
-        );
-
-        msg!("Pyth exponent: {}", exponent_i32);
-
-        // Normalize price to 6 decimals (standard for rift system)
-        let normalized_price = if exponent_i32 < -6 {
-            // Scale down (e.g., -8 to -6: divide by 100)
-            let scale_factor = 10u64.pow((exponent_i32.abs() - 6) as u32);
-            price.checked_div(scale_factor).ok_or(ErrorCode::MathOverflow)?
-        } else if exponent_i32 > -6 {
-            // Scale up (e.g., -4 to -6: multiply by 100)
-            let scale_factor = 10u64.pow((6 - exponent_i32.abs()) as u32);
-            price.checked_mul(scale_factor).ok_or(ErrorCode::MathOverflow)?
-        } else {
-            price
-        };
+        let (normalized_price, confidence, _publish_time, _confidence_bps) = parse_pyth_price(
+            &ctx.accounts.pyth_price_account.to_account_info(),
+            current_time,
+            rift.max_oracle_staleness,
+            rift.max_confidence_bps,
+        )?;
 
         // Update rift oracle with validated price
-        rift.add_price_data(normalized_price, confidence, current_time)?;
+        rift.add_price_data(normalized_price, confidence, current_time, OracleType::Pyth)?;
 
         emit!(OraclePriceUpdated {
             rift: rift.key(),
             oracle_type: OracleType::Pyth,
             price: normalized_price,
             confidence,
+            max_confidence_bps: rift.max_confidence_bps,
             timestamp: current_time,
         });
 
@@ -1166,9 +1723,9 @@ pub mod rifts_protocol {
     ) -> Result<()> {
         let rift = &mut ctx.accounts.rift;
 
-        // **SECURITY FIX #50**: Validate oracle authority (creator or governance)
+        // **SECURITY FIX #50**: Validate oracle authority (creator or a listed oracle pusher)
         require!(
-            ctx.accounts.oracle_authority.key() == rift.creator,
+            rift.is_oracle_authority(&ctx.accounts.oracle_authority.key()),
             ErrorCode::Unauthorized
         );
 
@@ -1181,111 +1738,119 @@ pub mod rifts_protocol {
             ErrorCode::OracleAccountMismatch
         );
 
-        // **SECURITY FIX #50**: Validate Switchboard account ownership
-        let switchboard_program_id = Pubkey::from_str_const("SW1TCH7qEPTdLsDHRgPuMQjbQxKdH2aBStViMFnt64f");
-        require!(
-            ctx.accounts.switchboard_feed.owner == &switchboard_program_id,
-            ErrorCode::InvalidOracleOwner
-        );
-
-        // Parse Switchboard aggregator data
-        let switchboard_data = &ctx.accounts.switchboard_feed.data.borrow();
-        require!(switchboard_data.len() >= 512, ErrorCode::InvalidOracleData);
+        let current_time = Clock::get()?.unix_timestamp;
+        let (normalized_price, confidence, _publish_time, _confidence_bps) = parse_switchboard_price(
+            &ctx.accounts.switchboard_feed.to_account_info(),
+            current_time,
+            rift.max_oracle_staleness,
+            rift.max_confidence_bps,
+        )?;
 
-        // Switchboard V2 AggregatorAccountData layout (simplified):
-        // The actual layout is complex, but the key fields we need are:
-        // - latest_confirmed_round.result (SwitchboardDecimal at specific offset)
-        // - latest_confirmed_round.round_open_timestamp (i64)
-        // - min_oracle_results (u32)
-        // - oracle_request_batch_size (u32)
+        // Update rift oracle with validated price
+        rift.add_price_data(normalized_price, confidence, current_time, OracleType::Switchboard)?;
 
-        // For security, we parse the essential fields for validation
-        // Offset 200: latest_confirmed_round.result.mantissa (i128)
-        // Offset 216: latest_confirmed_round.result.scale (u32)
-        // Offset 232: latest_confirmed_round.round_open_timestamp (i64)
+        emit!(OraclePriceUpdated {
+            rift: rift.key(),
+            oracle_type: OracleType::Switchboard,
+            price: normalized_price,
+            confidence,
+            max_confidence_bps: rift.max_confidence_bps,
+            timestamp: current_time,
+        });
 
-        let mantissa_bytes: [u8; 16] = switchboard_data[200..216].try_into()
-            .map_err(|_| ErrorCode::InvalidOracleData)?;
-        let mantissa = i128::from_le_bytes(mantissa_bytes);
+        Ok(())
+    }
 
-        let scale = u32::from_le_bytes(
-            switchboard_data[216..220].try_into().map_err(|_| ErrorCode::InvalidOracleData)?
-        );
+    /// Replace the keepers allowed to push oracle prices (creator only)
+    /// Pubkey::default() marks an empty slot; the creator is always authorized
+    pub fn set_oracle_pushers(
+        ctx: Context<SetOraclePushers>,
+        oracle_pushers: [Pubkey; MAX_ORACLE_PUSHERS],
+    ) -> Result<()> {
+        let rift = &mut ctx.accounts.rift;
 
-        let round_open_timestamp = i64::from_le_bytes(
-            switchboard_data[232..240].try_into().map_err(|_| ErrorCode::InvalidOracleData)?
-        );
+        rift.oracle_pushers = oracle_pushers;
+        rift.last_governance_update = Clock::get()?.unix_timestamp;
 
-        // Convert mantissa to u64 price
-        require!(mantissa > 0, ErrorCode::InvalidOraclePrice);
+        let active = oracle_pushers.iter().filter(|p| **p != Pubkey::default()).count();
+        msg!("Oracle pushers set: {} active", active);
 
-        // Calculate price with scale: price = mantissa / 10^scale
-        let price = if scale > 0 {
-            let divisor = 10u128.pow(scale);
-            let price_u128 = (mantissa as u128).checked_div(divisor)
-                .ok_or(ErrorCode::MathOverflow)?;
-            u64::try_from(price_u128).map_err(|_| ErrorCode::MathOverflow)?
-        } else {
-            u64::try_from(mantissa).map_err(|_| ErrorCode::MathOverflow)?
-        };
+        Ok(())
+    }
 
-        // **SECURITY FIX #50**: Validate staleness (max 5 minutes)
-        let current_time = Clock::get()?.unix_timestamp;
-        const MAX_AGE_SECONDS: i64 = 300; // 5 minutes
+    /// Apply one Pyth/Switchboard price to every rift in remaining_accounts bound to that feed
+    /// The feed is parsed and validated once; each rift must be writable, owned by this program,
+    /// created by the oracle authority and have the feed stored as its oracle account
+    pub fn update_oracle_multi<'info>(
+        ctx: Context<'_, '_, 'info, 'info, UpdateOracleMulti<'info>>,
+        oracle_type: OracleType,
+    ) -> Result<()> {
+        require!(!ctx.remaining_accounts.is_empty(), ErrorCode::InvalidAmount);
         require!(
-            current_time - round_open_timestamp <= MAX_AGE_SECONDS,
-            ErrorCode::OraclePriceStale
+            ctx.remaining_accounts.len() <= MAX_ORACLE_BATCH_SIZE,
+            ErrorCode::TooManyRifts
         );
 
-        msg!("Switchboard price age: {} seconds", current_time - round_open_timestamp);
+        let oracle_key = ctx.accounts.oracle_account.key();
+        let oracle_info = ctx.accounts.oracle_account.to_account_info();
+        let current_time = Clock::get()?.unix_timestamp;
 
-        // **SECURITY FIX #50**: Validate scale/decimals (0-18 typical)
-        require!(
-            scale <= 18,
-            ErrorCode::InvalidOracleExponent
-        );
+        // Parse the shared feed once with the widest allowed window and confidence ceiling;
+        // each rift's own max_oracle_staleness and max_confidence_bps are enforced below
+        let (normalized_price, confidence, publish_time, confidence_bps) = match oracle_type {
+            OracleType::Pyth => parse_pyth_price(
+                &oracle_info, current_time, MAX_MAX_ORACLE_STALENESS, MAX_CONFIDENCE_BPS,
+            )?,
+            OracleType::Switchboard => parse_switchboard_price(
+                &oracle_info, current_time, MAX_MAX_ORACLE_STALENESS, MAX_CONFIDENCE_BPS,
+            )?,
+        };
 
-        msg!("Switchboard scale: {}", scale);
+        for rift_info in ctx.remaining_accounts.iter() {
+            require!(rift_info.is_writable, ErrorCode::InvalidRiftAccount);
+            let mut rift: Account<'info, Rift> = Account::try_from(rift_info)?;
 
-        // For Switchboard, we use a default confidence of 1% of price
-        // In production, you could parse the std_deviation field for actual confidence
-        let confidence = price.checked_mul(1).ok_or(ErrorCode::MathOverflow)?
-            .checked_div(100).ok_or(ErrorCode::MathOverflow)?;
+            require!(
+                rift.is_oracle_authority(&ctx.accounts.oracle_authority.key()),
+                ErrorCode::Unauthorized
+            );
 
-        // **SECURITY FIX #50**: Validate confidence (confidence should be <= 5% of price)
-        let max_confidence = price.checked_mul(5).ok_or(ErrorCode::MathOverflow)?
-            .checked_div(100).ok_or(ErrorCode::MathOverflow)?;
-        require!(
-            confidence <= max_confidence,
-            ErrorCode::OracleConfidenceTooLow
-        );
+            let expected_oracle = match oracle_type {
+                OracleType::Pyth => rift.pyth_price_account,
+                OracleType::Switchboard => rift.switchboard_feed_account,
+            }.ok_or(ErrorCode::OracleAccountNotSet)?;
+            require!(expected_oracle == oracle_key, ErrorCode::OracleAccountMismatch);
+            require!(
+                current_time - publish_time <= rift.max_oracle_staleness,
+                ErrorCode::OraclePriceStale
+            );
+            require!(
+                confidence_bps <= u64::from(rift.max_confidence_bps),
+                ErrorCode::OracleConfidenceTooLow
+            );
 
-        // Normalize price to 6 decimals if needed
-        let normalized_price = if scale > 6 {
-            let scale_factor = 10u64.pow((scale - 6) as u32);
-            price.checked_div(scale_factor).ok_or(ErrorCode::MathOverflow)?
-        } else if scale < 6 {
-            let scale_factor = 10u64.pow((6 - scale) as u32);
-            price.checked_mul(scale_factor).ok_or(ErrorCode::MathOverflow)?
-        } else {
-            price
-        };
+            rift.add_price_data(normalized_price, confidence, current_time, oracle_type)?;
 
-        // Update rift oracle with validated price
-        rift.add_price_data(normalized_price, confidence, current_time)?;
+            emit!(OraclePriceUpdated {
+                rift: rift.key(),
+                oracle_type: oracle_type.clone(),
+                price: normalized_price,
+                confidence,
+                max_confidence_bps: rift.max_confidence_bps,
+                timestamp: current_time,
+            });
 
-        emit!(OraclePriceUpdated {
-            rift: rift.key(),
-            oracle_type: OracleType::Switchboard,
-            price: normalized_price,
-            confidence,
-            timestamp: current_time,
-        });
+            // Persist changes for manually deserialized accounts
+            rift.exit(&crate::ID)?;
+        }
+
+        msg!("Oracle price {} applied to {} rifts", normalized_price, ctx.remaining_accounts.len());
 
         Ok(())
     }
 
     /// Manual rebalance (can be called by anyone if conditions are met)
+    /// Pays rift.keeper_reward to the caller when the backing ratio actually moves
     pub fn trigger_rebalance(
         ctx: Context<TriggerRebalance>,
     ) -> Result<()> {
@@ -1297,13 +1862,100 @@ pub mod rifts_protocol {
 
         let clock = Clock::get()?;
 
+        // Refresh arbitrage_opportunity_bps from the live pool price when the pool is passed in
+        if let Some(pool) = &ctx.accounts.pool {
+            require!(
+                rift.liquidity_pool == Some(pool.key()),
+                ErrorCode::InvalidPoolAccount
+            );
+            let pool_spot_price = parse_meteora_pool_price(pool, &rift.rift_mint)?;
+            let arbitrage_bps = rift.compute_arbitrage_bps(pool_spot_price)?;
+            msg!("Pool spot price {} → arbitrage opportunity {} bps", pool_spot_price, arbitrage_bps);
+        }
+
         // Check if manual rebalance is allowed
         require!(
             rift.can_manual_rebalance(clock.unix_timestamp)?,
             ErrorCode::RebalanceTooSoon
         );
 
-        rift.trigger_automatic_rebalance(clock.unix_timestamp)?;
+        let previous_backing_ratio = rift.backing_ratio;
+        let rift_key = rift.key();
+        rift.trigger_automatic_rebalance(rift_key, clock.unix_timestamp)?;
+
+        // Pay the keeper only for a real rebalance, at most once per oracle interval
+        let mut keeper_reward_paid = 0u64;
+        let rebalanced = rift.backing_ratio != previous_backing_ratio;
+        let reward_due = clock.unix_timestamp
+            >= rift.last_keeper_reward_time
+                .checked_add(rift.oracle_update_interval)
+                .ok_or(ErrorCode::MathOverflow)?;
+        if rebalanced
+            && reward_due
+            && rift.keeper_reward > 0
+            && rift.keeper_reward <= rift.rifts_reward_budget
+        {
+            if let (Some(protocol_config), Some(governance), Some(reward_budget_vault), Some(keeper_rifts_tokens), Some(rifts_mint_authority), Some(token_program)) = (
+                &ctx.accounts.protocol_config,
+                &ctx.accounts.governance,
+                &ctx.accounts.reward_budget_vault,
+                &ctx.accounts.keeper_rifts_tokens,
+                &ctx.accounts.rifts_mint_authority,
+                &ctx.accounts.token_program,
+            ) {
+                // Only the canonical governance names the reward mint
+                require!(
+                    governance.key() == protocol_config.governance,
+                    ErrorCode::InvalidGovernance
+                );
+
+                // Same reward vault checks as ClaimStakingRewards; never the rift's own wrapped mint
+                require!(
+                    reward_budget_vault.mint == governance.rifts_mint
+                        && reward_budget_vault.mint != rift.rift_mint,
+                    ErrorCode::InvalidMint
+                );
+                require!(keeper_rifts_tokens.mint == reward_budget_vault.mint, ErrorCode::InvalidMint);
+
+                let bump = ctx.bumps.rifts_mint_authority.ok_or(ErrorCode::InvalidMintAuthority)?;
+                let rifts_mint_seeds = &[
+                    b"rift_mint_auth",
+                    rift_key.as_ref(),
+                    &[bump]
+                ];
+                let signer_seeds = &[&rifts_mint_seeds[..]];
+
+                token::transfer(
+                    CpiContext::new_with_signer(
+                        token_program.to_account_info(),
+                        Transfer {
+                            from: reward_budget_vault.to_account_info(),
+                            to: keeper_rifts_tokens.to_account_info(),
+                            authority: rifts_mint_authority.to_account_info(),
+                        },
+                        signer_seeds,
+                    ),
+                    rift.keeper_reward,
+                )?;
+
+                keeper_reward_paid = rift.keeper_reward;
+                rift.rifts_reward_budget = rift.rifts_reward_budget
+                    .checked_sub(keeper_reward_paid)
+                    .ok_or(ErrorCode::InsufficientRewardBudget)?;
+                rift.rifts_tokens_distributed = rift.rifts_tokens_distributed
+                    .checked_add(keeper_reward_paid)
+                    .ok_or(ErrorCode::MathOverflow)?;
+                rift.last_keeper_reward_time = clock.unix_timestamp;
+            }
+        }
+
+        emit!(RebalanceTriggered {
+            rift: rift_key,
+            keeper: ctx.accounts.user.key(),
+            backing_ratio: rift.backing_ratio,
+            keeper_reward_paid,
+            timestamp: clock.unix_timestamp,
+        });
 
         // **SECURITY FIX**: Release reentrancy guard
         rift.reentrancy_guard = false;
@@ -1325,43 +1977,20 @@ pub mod rifts_protocol {
         // Basic validation
         require!(fee_amount > 0, ErrorCode::InvalidAmount);
         require!(fee_amount <= 1_000_000_000_000, ErrorCode::AmountTooLarge);
+        require!(
+            fee_amount <= rift.accrued_underlying_fees,
+            ErrorCode::InsufficientAccruedFees
+        );
 
-        // Calculate fee splits with minimal stack usage
-        let burn_bps = u64::from(rift.burn_fee_bps);
-        let partner_bps = u64::from(rift.partner_fee_bps);
-
-        let burn_amount = fee_amount
-            .checked_mul(burn_bps)
-            .ok_or(ErrorCode::MathOverflow)?
-            .checked_div(10000)
-            .ok_or(ErrorCode::MathOverflow)?;
-
-        let partner_amount = fee_amount
-            .checked_mul(partner_bps)
-            .ok_or(ErrorCode::MathOverflow)?
-            .checked_div(10000)
-            .ok_or(ErrorCode::MathOverflow)?;
-
-        let burn_plus_partner = burn_amount
-            .checked_add(partner_amount)
-            .ok_or(ErrorCode::MathOverflow)?;
-
-        let remaining = fee_amount
-            .checked_sub(burn_plus_partner)
-            .ok_or(ErrorCode::MathOverflow)?;
-
-        // 5% to treasury, 95% to fee collector
-        let treasury_amount = remaining
-            .checked_mul(5)
-            .ok_or(ErrorCode::MathOverflow)?
-            .checked_div(100)
-            .ok_or(ErrorCode::MathOverflow)?;
-
-        let fee_collector_amount = remaining
-            .checked_sub(treasury_amount)
-            .ok_or(ErrorCode::MathOverflow)?;
+        // Calculate fee splits (shared with preview_fee_split)
+        let FeeSplit {
+            burn_amount,
+            partner_amount,
+            treasury_amount,
+            fee_collector_amount,
+        } = rift.calculate_fee_split(fee_amount)?;
 
-        // Prepare vault authority seeds for all transfers
+        // Prepare vault authority seeds for all fee vault transfers
         let rift_key = rift.key();
         let bump = [ctx.bumps.vault_authority];
         let vault_seeds: &[&[u8]] = &[b"vault_auth", rift_key.as_ref(), &bump];
@@ -1372,7 +2001,7 @@ pub mod rifts_protocol {
             let transfer_ctx = CpiContext::new_with_signer(
                 ctx.accounts.token_program.to_account_info(),
                 Transfer {
-                    from: ctx.accounts.vault.to_account_info(),
+                    from: ctx.accounts.fee_vault.to_account_info(),
                     to: ctx.accounts.treasury.to_account_info(),
                     authority: ctx.accounts.vault_authority.to_account_info(),
                 },
@@ -1387,7 +2016,7 @@ pub mod rifts_protocol {
             let transfer_ctx = CpiContext::new_with_signer(
                 ctx.accounts.token_program.to_account_info(),
                 Transfer {
-                    from: ctx.accounts.vault.to_account_info(),
+                    from: ctx.accounts.fee_vault.to_account_info(),
                     to: fee_collector_vault.to_account_info(),
                     authority: ctx.accounts.vault_authority.to_account_info(),
                 },
@@ -1407,7 +2036,7 @@ pub mod rifts_protocol {
                     ErrorCode::InvalidPartnerVault
                 );
                 require!(
-                    partner_vault.mint == ctx.accounts.vault.mint,
+                    partner_vault.mint == ctx.accounts.fee_vault.mint,
                     ErrorCode::InvalidPartnerVault
                 );
             } else {
@@ -1416,7 +2045,7 @@ pub mod rifts_protocol {
             let transfer_ctx = CpiContext::new_with_signer(
                 ctx.accounts.token_program.to_account_info(),
                 Transfer {
-                    from: ctx.accounts.vault.to_account_info(),
+                    from: ctx.accounts.fee_vault.to_account_info(),
                     to: partner_vault.to_account_info(),
                     authority: ctx.accounts.vault_authority.to_account_info(),
                 },
@@ -1426,6 +2055,8 @@ pub mod rifts_protocol {
         }
 
         // Update tracking
+        rift.accrued_underlying_fees = rift.accrued_underlying_fees
+            .checked_sub(fee_amount).ok_or(ErrorCode::MathOverflow)?;
         rift.total_fees_collected = rift.total_fees_collected.checked_add(fee_amount).unwrap_or(rift.total_fees_collected);
 
         emit!(FeesCalculated {
@@ -1442,6 +2073,25 @@ pub mod rifts_protocol {
         Ok(())
     }
 
+    /// Read-only: preview how process_fee_distribution would split `fee_amount`
+    /// Result is returned via set_return_data as a borsh-encoded FeeSplit
+    pub fn preview_fee_split(
+        ctx: Context<PreviewFeeSplit>,
+        fee_amount: u64,
+    ) -> Result<()> {
+        require!(fee_amount > 0, ErrorCode::InvalidAmount);
+        require!(fee_amount <= 1_000_000_000_000, ErrorCode::AmountTooLarge);
+
+        let split = ctx.accounts.rift.calculate_fee_split(fee_amount)?;
+
+        msg!("Fee split preview: burn={}, partner={}, treasury={}, fee_collector={}",
+             split.burn_amount, split.partner_amount, split.treasury_amount, split.fee_collector_amount);
+
+        anchor_lang::solana_program::program::set_return_data(&split.try_to_vec()?);
+
+        Ok(())
+    }
+
     /// Stake LP tokens for RIFTS rewards
     pub fn stake_lp_tokens(
         ctx: Context<StakeLPTokens>,
@@ -1468,35 +2118,20 @@ pub mod rifts_protocol {
             staker.total_staked = 0;
             staker.total_rewards_claimed = 0;
             staker.last_reward_update = Clock::get()?.unix_timestamp;
-            staker.stake_start_time = Clock::get()?.unix_timestamp;
+            staker.reward_formula_version = rift.reward_formula_version;
+            staker.frozen_seconds_checkpoint = ctx.accounts.governance.frozen_seconds_at(staker.last_reward_update);
         }
+
+        // Stakers must be migrated before accruing under a new reward formula
+        require!(
+            staker.reward_formula_version == rift.reward_formula_version,
+            ErrorCode::StakerMigrationRequired
+        );
         
         // Update pending rewards before changing stake
         let current_time = Clock::get()?.unix_timestamp;
-        let time_elapsed_i64 = current_time
-            .checked_sub(staker.last_reward_update)
-            .ok_or(ErrorCode::MathOverflow)?;
-        let time_elapsed = u64::try_from(time_elapsed_i64)
-            .map_err(|_| ErrorCode::MathOverflow)?;
-        
-        if staker.staked_amount > 0 && time_elapsed > 0 {
-            // Calculate pending rewards: 10% APY = ~0.00003170979% per hour
-            // Rewards = staked_amount * time_hours * hourly_rate / 1000000
-            let time_hours = time_elapsed
-                .checked_div(3600)
-                .ok_or(ErrorCode::MathOverflow)?;
-            let pending_rewards = staker.staked_amount
-                .checked_mul(time_hours)
-                .ok_or(ErrorCode::MathOverflow)?
-                .checked_mul(3170979)
-                .ok_or(ErrorCode::MathOverflow)?
-                .checked_div(100000000000)
-                .ok_or(ErrorCode::MathOverflow)?;
-            
-            staker.pending_rewards = staker.pending_rewards
-                .checked_add(pending_rewards)
-                .ok_or(ErrorCode::MathOverflow)?;
-        }
+        let frozen_seconds = ctx.accounts.governance.frozen_seconds_at(current_time);
+        staker.accrue_rewards(current_time, rift.reward_rate_per_hour, frozen_seconds)?;
         
         // Transfer LP tokens from user to staking vault
         let transfer_ctx = CpiContext::new(
@@ -1509,11 +2144,11 @@ pub mod rifts_protocol {
         );
         token::transfer(transfer_ctx, amount)?;
         
-        // Update staking records
+        // Update staking records; each new stake restarts the min_stake_duration lock
+        staker.stake_start_time = current_time;
         staker.staked_amount = staker.staked_amount
             .checked_add(amount)
             .ok_or(ErrorCode::MathOverflow)?;
-        staker.last_reward_update = current_time;
         staker.total_staked = staker.total_staked
             .checked_add(amount)
             .ok_or(ErrorCode::MathOverflow)?;
@@ -1528,7 +2163,8 @@ pub mod rifts_protocol {
         let clock = Clock::get()?;
         let should_rebalance = rift.should_trigger_rebalance(clock.unix_timestamp)?;
         if should_rebalance {
-            rift.trigger_automatic_rebalance(clock.unix_timestamp)?;
+            let rift_key = rift.key();
+            rift.trigger_automatic_rebalance(rift_key, clock.unix_timestamp)?;
         }
 
         emit!(LPTokensStaked {
@@ -1544,43 +2180,172 @@ pub mod rifts_protocol {
         Ok(())
     }
 
-    /// Claim RIFTS token rewards from LP staking - FULL IMPLEMENTATION
-    pub fn claim_staking_rewards(
-        ctx: Context<ClaimStakingRewards>,
+    /// Claim RIFTS staking rewards from several rifts in one transaction
+    /// remaining_accounts holds one group per rift:
+    /// (rift, staker_account, reward_budget_vault, rifts_mint_authority, user_rifts_tokens)
+    /// Rifts where the user has no staker account, nothing claimable, vesting enabled or an
+    /// unmigrated staker are skipped; the total paid is returned via set_return_data as a u64
+    pub fn claim_staking_rewards_batch<'info>(
+        ctx: Context<'_, '_, 'info, 'info, ClaimStakingRewardsBatch<'info>>,
     ) -> Result<()> {
-        let rift = &mut ctx.accounts.rift;
-        let staker = &mut ctx.accounts.staker_account;
-        
-        // Calculate total claimable rewards
+        let remaining = ctx.remaining_accounts;
+        require!(
+            !remaining.is_empty() && remaining.len() % CLAIM_BATCH_ACCOUNTS_PER_RIFT == 0,
+            ErrorCode::InvalidAmount
+        );
+        require!(
+            remaining.len() / CLAIM_BATCH_ACCOUNTS_PER_RIFT <= MAX_CLAIM_BATCH_SIZE,
+            ErrorCode::TooManyRifts
+        );
+
+        let user_key = ctx.accounts.user.key();
         let current_time = Clock::get()?.unix_timestamp;
-        let time_elapsed_i64 = current_time
-            .checked_sub(staker.last_reward_update)
-            .ok_or(ErrorCode::MathOverflow)?;
-        let time_elapsed = u64::try_from(time_elapsed_i64)
-            .map_err(|_| ErrorCode::MathOverflow)?;
-        
-        let mut total_rewards = staker.pending_rewards;
-        
-        if staker.staked_amount > 0 && time_elapsed > 0 {
-            let time_hours = time_elapsed
-                .checked_div(3600)
-                .ok_or(ErrorCode::MathOverflow)?;
-            let new_rewards = staker.staked_amount
-                .checked_mul(time_hours)
-                .ok_or(ErrorCode::MathOverflow)?
-                .checked_mul(3170979)
-                .ok_or(ErrorCode::MathOverflow)?
-                .checked_div(100000000000)
+        let frozen_seconds = ctx.accounts.governance.frozen_seconds_at(current_time);
+        let mut total_paid = 0u64;
+
+        for group in remaining.chunks(CLAIM_BATCH_ACCOUNTS_PER_RIFT) {
+            let (rift_info, staker_info, vault_info, authority_info, destination_info) =
+                (&group[0], &group[1], &group[2], &group[3], &group[4]);
+
+            require!(rift_info.is_writable, ErrorCode::InvalidRiftAccount);
+            let mut rift: Account<'info, Rift> = Account::try_from(rift_info)?;
+            let rift_key = rift.key();
+
+            let (expected_staker, _) = Pubkey::find_program_address(
+                &[b"staker", rift_key.as_ref(), user_key.as_ref()],
+                &crate::ID,
+            );
+            require!(staker_info.key() == expected_staker, ErrorCode::InvalidStakerAccount);
+
+            // No stake in this rift
+            if staker_info.owner != &crate::ID || staker_info.data_is_empty() {
+                continue;
+            }
+            require!(staker_info.is_writable, ErrorCode::InvalidStakerAccount);
+            let mut staker: Account<'info, StakerAccount> = Account::try_from(staker_info)?;
+            require!(staker.user == user_key, ErrorCode::Unauthorized);
+
+            if staker.reward_formula_version != rift.reward_formula_version
+                || rift.reward_vest_period > 0
+            {
+                continue;
+            }
+
+            staker.accrue_rewards(current_time, rift.reward_rate_per_hour, frozen_seconds)?;
+            let rewards = staker.pending_rewards;
+            if rewards == 0
+                || rewards < rift.min_claimable_rewards
+                || rewards > rift.rifts_reward_budget
+            {
+                staker.exit(&crate::ID)?;
+                continue;
+            }
+
+            let (expected_authority, authority_bump) = Pubkey::find_program_address(
+                &[b"rift_mint_auth", rift_key.as_ref()],
+                &crate::ID,
+            );
+            require!(authority_info.key() == expected_authority, ErrorCode::InvalidMintAuthority);
+
+            // Same reward vault and RIFTS mint checks as ClaimStakingRewards
+            let (expected_vault, _) = Pubkey::find_program_address(
+                &[b"reward_budget_vault", rift_key.as_ref()],
+                &crate::ID,
+            );
+            require!(vault_info.key() == expected_vault, ErrorCode::InvalidVault);
+            let reward_budget_vault: Account<'info, TokenAccount> = Account::try_from(vault_info)?;
+            require!(
+                reward_budget_vault.mint == ctx.accounts.governance.rifts_mint
+                    && reward_budget_vault.mint != rift.rift_mint,
+                ErrorCode::InvalidMint
+            );
+
+            let rifts_mint_seeds = &[
+                b"rift_mint_auth",
+                rift_key.as_ref(),
+                &[authority_bump]
+            ];
+            let signer_seeds = &[&rifts_mint_seeds[..]];
+
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: vault_info.clone(),
+                        to: destination_info.clone(),
+                        authority: authority_info.clone(),
+                    },
+                    signer_seeds,
+                ),
+                rewards,
+            )?;
+
+            staker.pending_rewards = 0;
+            staker.total_rewards_claimed = staker.total_rewards_claimed
+                .checked_add(rewards)
+                .ok_or(ErrorCode::MathOverflow)?;
+
+            rift.rifts_tokens_distributed = rift.rifts_tokens_distributed
+                .checked_add(rewards)
                 .ok_or(ErrorCode::MathOverflow)?;
-            
-            total_rewards = total_rewards
-                .checked_add(new_rewards)
+            rift.rifts_reward_budget = rift.rifts_reward_budget
+                .checked_sub(rewards)
+                .ok_or(ErrorCode::InsufficientRewardBudget)?;
+            rift.last_reward_distribution = current_time;
+
+            emit!(StakingRewardsClaimed {
+                rift: rift_key,
+                user: user_key,
+                rewards_claimed: rewards,
+                total_claimed: staker.total_rewards_claimed,
+            });
+
+            total_paid = total_paid
+                .checked_add(rewards)
                 .ok_or(ErrorCode::MathOverflow)?;
+
+            // Persist changes for manually deserialized accounts
+            staker.exit(&crate::ID)?;
+            rift.exit(&crate::ID)?;
         }
+
+        msg!("Batch claimed {} RIFTS across {} rifts", total_paid, remaining.len() / CLAIM_BATCH_ACCOUNTS_PER_RIFT);
+        anchor_lang::solana_program::program::set_return_data(&total_paid.try_to_vec()?);
+
+        Ok(())
+    }
+
+    /// Claim RIFTS token rewards from LP staking - FULL IMPLEMENTATION
+    pub fn claim_staking_rewards(
+        ctx: Context<ClaimStakingRewards>,
+    ) -> Result<()> {
+        let rift = &mut ctx.accounts.rift;
+        let staker = &mut ctx.accounts.staker_account;
+
+        require!(
+            staker.reward_formula_version == rift.reward_formula_version,
+            ErrorCode::StakerMigrationRequired
+        );
+        
+        // Calculate total claimable rewards
+        let current_time = Clock::get()?.unix_timestamp;
+        let frozen_seconds = ctx.accounts.governance.frozen_seconds_at(current_time);
+        staker.accrue_rewards(current_time, rift.reward_rate_per_hour, frozen_seconds)?;
+        let total_rewards = staker.pending_rewards;
         
         require!(total_rewards > 0, ErrorCode::NoRewardsToClaim);
+        // Leave dust accruing instead of paying out uneconomical amounts
+        require!(
+            total_rewards >= rift.min_claimable_rewards,
+            ErrorCode::RewardsBelowMinimum
+        );
+        // Rewards are capped by the funded budget held in reward_budget_vault
+        require!(
+            total_rewards <= rift.rifts_reward_budget,
+            ErrorCode::InsufficientRewardBudget
+        );
         
-        // Mint RIFTS tokens as rewards
+        // Pay RIFTS rewards from the funded vault, into the vesting escrow when vesting is enabled
         let rift_key = rift.key();
         let rifts_mint_seeds = &[
             b"rift_mint_auth",
@@ -1588,21 +2353,36 @@ pub mod rifts_protocol {
             &[ctx.bumps.rifts_mint_authority]
         ];
         let signer_seeds = &[&rifts_mint_seeds[..]];
+
+        let reward_destination = if rift.reward_vest_period > 0 {
+            let reward_vesting = ctx.accounts.reward_vesting
+                .as_mut()
+                .ok_or(ErrorCode::VestingAccountRequired)?;
+            let vesting_escrow = ctx.accounts.vesting_escrow
+                .as_ref()
+                .ok_or(ErrorCode::VestingAccountRequired)?;
+
+            reward_vesting.add_rewards(total_rewards, current_time, rift.reward_vest_period)?;
+            msg!("Vesting {} RIFTS until {}", total_rewards, reward_vesting.vest_end);
+
+            vesting_escrow.to_account_info()
+        } else {
+            ctx.accounts.user_rifts_tokens.to_account_info()
+        };
         
-        let mint_ctx = CpiContext::new_with_signer(
+        let transfer_ctx = CpiContext::new_with_signer(
             ctx.accounts.token_program.to_account_info(),
-            MintTo {
-                mint: ctx.accounts.rifts_mint.to_account_info(),
-                to: ctx.accounts.user_rifts_tokens.to_account_info(),
+            Transfer {
+                from: ctx.accounts.reward_budget_vault.to_account_info(),
+                to: reward_destination,
                 authority: ctx.accounts.rifts_mint_authority.to_account_info(),
             },
             signer_seeds,
         );
-        token::mint_to(mint_ctx, total_rewards)?;
+        token::transfer(transfer_ctx, total_rewards)?;
         
         // Update staker records
         staker.pending_rewards = 0;
-        staker.last_reward_update = current_time;
         staker.total_rewards_claimed = staker.total_rewards_claimed
             .checked_add(total_rewards)
             .ok_or(ErrorCode::MathOverflow)?;
@@ -1611,6 +2391,9 @@ pub mod rifts_protocol {
         rift.rifts_tokens_distributed = rift.rifts_tokens_distributed
             .checked_add(total_rewards)
             .ok_or(ErrorCode::MathOverflow)?;
+        rift.rifts_reward_budget = rift.rifts_reward_budget
+            .checked_sub(total_rewards)
+            .ok_or(ErrorCode::InsufficientRewardBudget)?;
         rift.last_reward_distribution = current_time;
         
         emit!(StakingRewardsClaimed {
@@ -1623,6 +2406,207 @@ pub mod rifts_protocol {
         Ok(())
     }
     
+    /// Restake claimable RIFTS rewards as principal instead of paying them out
+    /// Only valid when the staking vault holds the reward token itself
+    pub fn compound_rewards(
+        ctx: Context<CompoundRewards>,
+    ) -> Result<()> {
+        let rift = &mut ctx.accounts.rift;
+        let staker = &mut ctx.accounts.staker_account;
+
+        require!(
+            ctx.accounts.staking_vault.mint == ctx.accounts.rifts_mint.key(),
+            ErrorCode::RewardTokenNotStakeable
+        );
+        // Vesting rewards must go through the escrow, not straight into principal
+        require!(rift.reward_vest_period == 0, ErrorCode::CannotCompoundVestedRewards);
+        require!(
+            staker.reward_formula_version == rift.reward_formula_version,
+            ErrorCode::StakerMigrationRequired
+        );
+
+        // Same claimable amount and limits as claim_staking_rewards
+        let current_time = Clock::get()?.unix_timestamp;
+        let frozen_seconds = ctx.accounts.governance.frozen_seconds_at(current_time);
+        staker.accrue_rewards(current_time, rift.reward_rate_per_hour, frozen_seconds)?;
+        let total_rewards = staker.pending_rewards;
+
+        require!(total_rewards > 0, ErrorCode::NoRewardsToClaim);
+        require!(
+            total_rewards >= rift.min_claimable_rewards,
+            ErrorCode::RewardsBelowMinimum
+        );
+        require!(
+            total_rewards <= rift.rifts_reward_budget,
+            ErrorCode::InsufficientRewardBudget
+        );
+
+        let rift_key = rift.key();
+        let rifts_mint_seeds = &[
+            b"rift_mint_auth",
+            rift_key.as_ref(),
+            &[ctx.bumps.rifts_mint_authority]
+        ];
+        let signer_seeds = &[&rifts_mint_seeds[..]];
+
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.reward_budget_vault.to_account_info(),
+                to: ctx.accounts.staking_vault.to_account_info(),
+                authority: ctx.accounts.rifts_mint_authority.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::transfer(transfer_ctx, total_rewards)?;
+
+        // Rewards become principal; the new stake restarts the min_stake_duration lock
+        staker.pending_rewards = 0;
+        staker.stake_start_time = current_time;
+        staker.staked_amount = staker.staked_amount
+            .checked_add(total_rewards)
+            .ok_or(ErrorCode::MathOverflow)?;
+        staker.total_staked = staker.total_staked
+            .checked_add(total_rewards)
+            .ok_or(ErrorCode::MathOverflow)?;
+        staker.total_rewards_claimed = staker.total_rewards_claimed
+            .checked_add(total_rewards)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        rift.total_lp_staked = rift.total_lp_staked
+            .checked_add(total_rewards)
+            .ok_or(ErrorCode::MathOverflow)?;
+        rift.rifts_tokens_distributed = rift.rifts_tokens_distributed
+            .checked_add(total_rewards)
+            .ok_or(ErrorCode::MathOverflow)?;
+        rift.rifts_reward_budget = rift.rifts_reward_budget
+            .checked_sub(total_rewards)
+            .ok_or(ErrorCode::InsufficientRewardBudget)?;
+        rift.last_reward_distribution = current_time;
+
+        emit!(RewardsCompounded {
+            rift: rift_key,
+            user: ctx.accounts.user.key(),
+            amount: total_rewards,
+            total_staked: staker.staked_amount,
+        });
+
+        Ok(())
+    }
+
+    /// Deposit the rift's accumulated pending_rewards into an lp-staking pool (permissionless crank)
+    /// RIFTS are paid from the funded reward budget vault, never minted: lp-staking pools require
+    /// their reward mint to be minted only by the pool's own reward_authority PDA. The pool must
+    /// authorize this rift's RIFTS authority PDA as its rifts_protocol depositor.
+    pub fn flush_pending_rewards_to_staking_pool(
+        ctx: Context<FlushPendingRewards>,
+    ) -> Result<()> {
+        let rift = &mut ctx.accounts.rift;
+        // Whatever the budget cannot cover stays pending for a later flush
+        let amount = rift.pending_rewards.min(rift.rifts_reward_budget);
+        require!(amount > 0, ErrorCode::NoRewardsToClaim);
+
+        let rift_key = rift.key();
+        let rifts_mint_seeds = &[
+            b"rift_mint_auth",
+            rift_key.as_ref(),
+            &[ctx.bumps.rifts_mint_authority]
+        ];
+        let signer_seeds = &[&rifts_mint_seeds[..]];
+
+        let deposit_ctx = CpiContext::new_with_signer(
+            ctx.accounts.lp_staking_program.to_account_info(),
+            lp_staking::cpi::accounts::DepositRewards {
+                depositor_authority: ctx.accounts.rifts_mint_authority.to_account_info(),
+                staking_pool: ctx.accounts.staking_pool.to_account_info(),
+                depositor_token_account: ctx.accounts.reward_budget_vault.to_account_info(),
+                pool_reward_vault: ctx.accounts.pool_reward_vault.to_account_info(),
+                token_program: ctx.accounts.token_program.to_account_info(),
+            },
+            signer_seeds,
+        );
+        lp_staking::cpi::deposit_rewards(deposit_ctx, amount)?;
+
+        rift.pending_rewards = rift.pending_rewards
+            .checked_sub(amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+        rift.rifts_reward_budget = rift.rifts_reward_budget
+            .checked_sub(amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+        rift.last_reward_distribution = Clock::get()?.unix_timestamp;
+
+        msg!("Flushed {} RIFTS pending rewards to staking pool {}", amount, ctx.accounts.staking_pool.key());
+
+        emit!(PendingRewardsFlushed {
+            rift: rift_key,
+            staking_pool: ctx.accounts.staking_pool.key(),
+            amount,
+        });
+
+        Ok(())
+    }
+
+    /// Create the per-user vesting state and escrow used when a rift vests rewards
+    pub fn initialize_reward_vesting(
+        ctx: Context<InitializeRewardVesting>,
+    ) -> Result<()> {
+        let reward_vesting = &mut ctx.accounts.reward_vesting;
+
+        reward_vesting.user = ctx.accounts.user.key();
+        reward_vesting.rift = ctx.accounts.rift.key();
+        reward_vesting.escrow = ctx.accounts.vesting_escrow.key();
+        reward_vesting.carried_amount = 0;
+        reward_vesting.locked_amount = 0;
+        reward_vesting.released_from_locked = 0;
+        reward_vesting.vest_start = Clock::get()?.unix_timestamp;
+        reward_vesting.vest_end = reward_vesting.vest_start;
+        reward_vesting.total_claimed = 0;
+        reward_vesting.bump = ctx.bumps.reward_vesting;
+
+        Ok(())
+    }
+
+    /// Release vested staking rewards from the escrow to the user
+    pub fn claim_vested_rewards(
+        ctx: Context<ClaimVestedRewards>,
+    ) -> Result<()> {
+        let reward_vesting = &mut ctx.accounts.reward_vesting;
+        let current_time = Clock::get()?.unix_timestamp;
+
+        let claimable = reward_vesting.claim(current_time)?;
+        require!(claimable > 0, ErrorCode::NoRewardsToClaim);
+
+        let rift_key = ctx.accounts.rift.key();
+        let user_key = ctx.accounts.user.key();
+        let vesting_seeds = &[
+            b"reward_vesting",
+            rift_key.as_ref(),
+            user_key.as_ref(),
+            &[reward_vesting.bump]
+        ];
+        let signer_seeds = &[&vesting_seeds[..]];
+
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.vesting_escrow.to_account_info(),
+                to: ctx.accounts.user_rifts_tokens.to_account_info(),
+                authority: reward_vesting.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::transfer(transfer_ctx, claimable)?;
+
+        emit!(VestedRewardsClaimed {
+            rift: rift_key,
+            user: user_key,
+            amount: claimable,
+            total_claimed: reward_vesting.total_claimed,
+        });
+
+        Ok(())
+    }
+    
     /// Unstake LP tokens and claim pending rewards
     pub fn unstake_lp_tokens(
         ctx: Context<UnstakeLPTokens>,
@@ -1633,31 +2617,19 @@ pub mod rifts_protocol {
         
         require!(amount > 0, ErrorCode::InvalidAmount);
         require!(amount <= staker.staked_amount, ErrorCode::InsufficientStakedTokens);
+        require!(
+            staker.reward_formula_version == rift.reward_formula_version,
+            ErrorCode::StakerMigrationRequired
+        );
         
         // Auto-claim rewards before unstaking
         let current_time = Clock::get()?.unix_timestamp;
-        let time_elapsed_i64 = current_time
-            .checked_sub(staker.last_reward_update)
+        let unlock_time = staker.stake_start_time
+            .checked_add(rift.min_stake_duration)
             .ok_or(ErrorCode::MathOverflow)?;
-        let time_elapsed = u64::try_from(time_elapsed_i64)
-            .map_err(|_| ErrorCode::MathOverflow)?;
-        
-        if time_elapsed > 0 {
-            let time_hours = time_elapsed
-                .checked_div(3600)
-                .ok_or(ErrorCode::MathOverflow)?;
-            let new_rewards = staker.staked_amount
-                .checked_mul(time_hours)
-                .ok_or(ErrorCode::MathOverflow)?
-                .checked_mul(3170979)
-                .ok_or(ErrorCode::MathOverflow)?
-                .checked_div(100000000000)
-                .ok_or(ErrorCode::MathOverflow)?;
-            
-            staker.pending_rewards = staker.pending_rewards
-                .checked_add(new_rewards)
-                .ok_or(ErrorCode::MathOverflow)?;
-        }
+        require!(current_time >= unlock_time, ErrorCode::StakeDurationNotMet);
+        let frozen_seconds = ctx.accounts.governance.frozen_seconds_at(current_time);
+        staker.accrue_rewards(current_time, rift.reward_rate_per_hour, frozen_seconds)?;
         
         // Transfer LP tokens back to user
         let rift_key = rift.key();
@@ -1683,7 +2655,6 @@ pub mod rifts_protocol {
         staker.staked_amount = staker.staked_amount
             .checked_sub(amount)
             .ok_or(ErrorCode::MathOverflow)?;
-        staker.last_reward_update = current_time;
         
         // Update rift totals
         rift.total_lp_staked = rift.total_lp_staked
@@ -1695,7 +2666,8 @@ pub mod rifts_protocol {
         let clock = Clock::get()?;
         let should_rebalance = rift.should_trigger_rebalance(clock.unix_timestamp)?;
         if should_rebalance {
-            rift.trigger_automatic_rebalance(clock.unix_timestamp)?;
+            let rift_key = rift.key();
+            rift.trigger_automatic_rebalance(rift_key, clock.unix_timestamp)?;
         }
 
         emit!(LPTokensUnstaked {
@@ -1709,6 +2681,44 @@ pub mod rifts_protocol {
         Ok(())
     }
 
+    /// Migrate a staker to the rift's current reward formula version
+    /// Settles pending rewards under the old formula so nothing is lost or double-counted
+    pub fn migrate_staker_rewards(
+        ctx: Context<MigrateStakerRewards>,
+    ) -> Result<()> {
+        let rift = &mut ctx.accounts.rift;
+        let staker = &mut ctx.accounts.staker_account;
+
+        // Adopt the program's current formula version on first migration after an upgrade
+        if rift.reward_formula_version < REWARD_FORMULA_VERSION {
+            rift.reward_formula_version = REWARD_FORMULA_VERSION;
+        }
+
+        require!(
+            staker.reward_formula_version < rift.reward_formula_version,
+            ErrorCode::StakerAlreadyMigrated
+        );
+
+        // Settle everything accrued under the old formula
+        let current_time = Clock::get()?.unix_timestamp;
+        let frozen_seconds = ctx.accounts.governance.frozen_seconds_at(current_time);
+        let settled_rewards = staker.accrue_rewards(current_time, rift.reward_rate_per_hour, frozen_seconds)?;
+
+        let old_version = staker.reward_formula_version;
+        staker.reward_formula_version = rift.reward_formula_version;
+
+        emit!(StakerRewardsMigrated {
+            rift: rift.key(),
+            user: ctx.accounts.user.key(),
+            old_version,
+            new_version: staker.reward_formula_version,
+            settled_rewards,
+            pending_rewards: staker.pending_rewards,
+        });
+
+        Ok(())
+    }
+
     /// Execute Jupiter swap for fee buybacks (integrated with fee collector)
     /// **SECURITY FIX #51**: Added balance verification to enforce minimum_amount_out
     pub fn jupiter_swap_for_buyback(
@@ -1716,6 +2726,7 @@ pub mod rifts_protocol {
         amount_in: u64,
         minimum_amount_out: u64,
         swap_data: Vec<u8>,
+        max_slippage_bps: u16, // Also require output within this slippage of the oracle price
     ) -> Result<()> {
         let rift = &mut ctx.accounts.rift;
 
@@ -1734,7 +2745,22 @@ pub mod rifts_protocol {
         require!(amount_in > 0, ErrorCode::InvalidAmount);
         require!(amount_in <= 1_000_000_000_000, ErrorCode::AmountTooLarge);
         require!(minimum_amount_out > 0, ErrorCode::InvalidAmount);
-        require!(swap_data.len() <= 10000, ErrorCode::InvalidInputData);
+        require!(
+            !swap_data.is_empty() && swap_data.len() <= MAX_SWAP_DATA_LEN,
+            ErrorCode::InvalidInputData
+        );
+        require!(
+            max_slippage_bps <= MAX_BUYBACK_SLIPPAGE_BPS,
+            ErrorCode::InvalidSlippageBps
+        );
+
+        // Independent floor from the oracle price, so a bad client minimum can't loosen slippage
+        let oracle_minimum_out = rift.oracle_min_amount_out(
+            ctx.accounts.source_token_account.mint,
+            ctx.accounts.destination_token_account.mint,
+            amount_in,
+            max_slippage_bps,
+        )?;
 
         // **SECURITY FIX #51**: Snapshot source balance before swap
         let source_balance_before = ctx.accounts.source_token_account.amount;
@@ -1797,6 +2823,18 @@ pub mod rifts_protocol {
             .checked_sub(dest_balance_before)
             .ok_or(ErrorCode::MathOverflow)?;
 
+        // Log the failure cause before the slippage checks revert the swap
+        let expected_out = minimum_amount_out.max(oracle_minimum_out);
+        if dest_delta < expected_out {
+            emit!(JupiterSwapFailed {
+                rift: rift.key(),
+                amount_in,
+                expected_out,
+                actual_out: dest_delta,
+                timestamp: Clock::get()?.unix_timestamp,
+            });
+        }
+
         // **CRITICAL ENFORCEMENT**: Require actual output >= minimum required
         require!(
             dest_delta >= minimum_amount_out,
@@ -1804,15 +2842,20 @@ pub mod rifts_protocol {
         );
         msg!("Destination delta verified: {} (minimum required: {})", dest_delta, minimum_amount_out);
 
-        // Update rift metrics
-        rift.total_volume_24h = rift.total_volume_24h
-            .checked_add(amount_in)
-            .ok_or(ErrorCode::MathOverflow)?;
+        require!(
+            dest_delta >= oracle_minimum_out,
+            ErrorCode::SlippageExceeded
+        );
+        msg!("Oracle floor verified: {} (oracle minimum: {})", dest_delta, oracle_minimum_out);
+
+        // Update rift metrics (sliding 24h volume)
+        rift.record_volume(amount_in, Clock::get()?.unix_timestamp)?;
 
         emit!(JupiterSwapExecuted {
             rift: rift.key(),
             amount_in,
             minimum_amount_out,
+            oracle_minimum_out,
             timestamp: Clock::get()?.unix_timestamp,
         });
 
@@ -1849,6 +2892,9 @@ pub mod rifts_protocol {
             ErrorCode::ProposalMismatch
         );
 
+        // Parameter proposals from this governance apply at most once and in increasing id order
+        ctx.accounts.applied_proposal.record(proposal_id, ctx.bumps.applied_proposal)?;
+
         // Validate proposal execution via CPI to governance program
         require!(
             proposal.proposal_type == governance::ProposalType::ParameterChange,
@@ -1868,15 +2914,31 @@ pub mod rifts_protocol {
         // Apply parameter changes from governance (source of truth)
         let param_changes = governance.pending_parameter_changes.as_ref().unwrap();
 
-        // Execute parameter changes from governance-approved values
+        // Fee decreases apply now; increases wait min_execution_delay in pending_fee_changes
+        let current_time = Clock::get()?.unix_timestamp;
+        let mut pending = rift.pending_fee_changes.unwrap_or_default();
+        let mut scheduled = false;
+
         if let Some(burn_fee) = param_changes.burn_fee_bps {
             require!(burn_fee <= 4500, ErrorCode::InvalidBurnFee);
-            rift.burn_fee_bps = burn_fee;
+            if burn_fee > rift.burn_fee_bps {
+                pending.burn_fee_bps = Some(burn_fee);
+                scheduled = true;
+            } else {
+                rift.burn_fee_bps = burn_fee;
+                pending.burn_fee_bps = None;
+            }
         }
 
         if let Some(partner_fee) = param_changes.partner_fee_bps {
             require!(partner_fee <= 500, ErrorCode::InvalidPartnerFee);
-            rift.partner_fee_bps = partner_fee;
+            if partner_fee > rift.partner_fee_bps {
+                pending.partner_fee_bps = Some(partner_fee);
+                scheduled = true;
+            } else {
+                rift.partner_fee_bps = partner_fee;
+                pending.partner_fee_bps = None;
+            }
         }
 
         if let Some(oracle_interval) = param_changes.oracle_update_interval {
@@ -1885,10 +2947,54 @@ pub mod rifts_protocol {
         }
 
         if let Some(threshold) = param_changes.arbitrage_threshold_bps {
-            require!(threshold >= 50 && threshold <= 1000, ErrorCode::InvalidRebalanceThreshold);
+            validate_arbitrage_threshold(threshold)?;
             rift.arbitrage_threshold_bps = threshold;
         }
 
+        if let Some(treasury_fee) = param_changes.treasury_fee_bps {
+            require!(treasury_fee < MAX_TREASURY_FEE_BPS, ErrorCode::InvalidTreasuryFee);
+            if treasury_fee > rift.treasury_fee_bps {
+                pending.treasury_fee_bps = Some(treasury_fee);
+                scheduled = true;
+            } else {
+                rift.treasury_fee_bps = treasury_fee;
+                pending.treasury_fee_bps = None;
+            }
+        }
+
+        if scheduled {
+            pending.effective_at = current_time
+                .checked_add(governance.min_execution_delay)
+                .ok_or(ErrorCode::MathOverflow)?;
+
+            emit!(RiftFeeChangesScheduled {
+                rift: rift.key(),
+                burn_fee_bps: pending.burn_fee_bps,
+                partner_fee_bps: pending.partner_fee_bps,
+                treasury_fee_bps: pending.treasury_fee_bps,
+                effective_at: pending.effective_at,
+            });
+        }
+
+        // Fees must stay within the combined cap once scheduled increases take effect too
+        validate_combined_fees(
+            pending.burn_fee_bps.unwrap_or(rift.burn_fee_bps),
+            pending.partner_fee_bps.unwrap_or(rift.partner_fee_bps),
+            pending.treasury_fee_bps.unwrap_or(rift.treasury_fee_bps),
+        )?;
+        rift.pending_fee_changes = if pending.is_empty() { None } else { Some(pending) };
+
+        if param_changes.rifts_lp_share_bps.is_some() || param_changes.rifts_burn_share_bps.is_some() {
+            let lp_share = param_changes.rifts_lp_share_bps.unwrap_or(rift.rifts_lp_share_bps);
+            let burn_share = param_changes.rifts_burn_share_bps.unwrap_or(rift.rifts_burn_share_bps);
+            require!(
+                u32::from(lp_share) + u32::from(burn_share) == 10000,
+                ErrorCode::InvalidRiftsShareSplit
+            );
+            rift.rifts_lp_share_bps = lp_share;
+            rift.rifts_burn_share_bps = burn_share;
+        }
+
         // Update governance timestamp
         rift.last_governance_update = Clock::get()?.unix_timestamp;
 
@@ -1902,6 +3008,44 @@ pub mod rifts_protocol {
         Ok(())
     }
 
+    /// Apply fee increases buffered by execute_governance_proposal once their timelock passes
+    /// Permissionless: anyone can call after pending_fee_changes.effective_at
+    pub fn apply_pending_rift_changes(
+        ctx: Context<ApplyPendingRiftChanges>,
+    ) -> Result<()> {
+        let rift = &mut ctx.accounts.rift;
+        let current_time = Clock::get()?.unix_timestamp;
+
+        let pending = rift.pending_fee_changes.ok_or(ErrorCode::NoPendingRiftChanges)?;
+        require!(
+            current_time >= pending.effective_at,
+            ErrorCode::RiftChangesNotEffective
+        );
+
+        if let Some(burn_fee) = pending.burn_fee_bps {
+            rift.burn_fee_bps = burn_fee;
+        }
+        if let Some(partner_fee) = pending.partner_fee_bps {
+            rift.partner_fee_bps = partner_fee;
+        }
+        if let Some(treasury_fee) = pending.treasury_fee_bps {
+            rift.treasury_fee_bps = treasury_fee;
+        }
+
+        rift.pending_fee_changes = None;
+        rift.last_governance_update = current_time;
+
+        emit!(RiftFeeChangesApplied {
+            rift: rift.key(),
+            burn_fee_bps: rift.burn_fee_bps,
+            partner_fee_bps: rift.partner_fee_bps,
+            treasury_fee_bps: rift.treasury_fee_bps,
+            timestamp: current_time,
+        });
+
+        Ok(())
+    }
+
     /// Emergency pause function (governance controlled)
     /// **SECURITY FIX #44**: Add CPI verification to governance for emergency controls
     ///
@@ -1911,39 +3055,63 @@ pub mod rifts_protocol {
     /// This balances security with the need for rapid emergency response.
     pub fn emergency_pause(
         ctx: Context<EmergencyPause>,
+        pause_reason: PauseReason,
+        auto_unpause_at: i64, // 0 = stays paused until emergency_unpause
     ) -> Result<()> {
         let rift = &mut ctx.accounts.rift;
-        let governance = &ctx.accounts.governance;
-
-        // **SECURITY FIX #44**: Verify governance authority via signature bitmap
-        // Check if signer is either primary or additional authority
         let signer = ctx.accounts.governance_authority.key();
-        let is_primary = signer == governance.authority;
-        let is_additional = governance.additional_authorities.contains(&signer);
 
-        require!(
-            is_primary || is_additional,
-            ErrorCode::UnauthorizedGovernance
-        );
+        // The rift's own pause authority needs no governance account
+        if rift.pause_authority != Some(signer) {
+            let governance = ctx.accounts.governance
+                .as_ref()
+                .ok_or(ErrorCode::UnauthorizedGovernance)?;
+
+            // **SECURITY FIX #44**: Verify governance authority via signature bitmap
+            // Check if signer is either primary or additional authority
+            let is_primary = signer == governance.authority;
+            let is_additional = governance.additional_authorities.contains(&signer);
 
-        // **SECURITY FIX #44**: If multisig is configured, verify emergency pause is authorized
-        // Emergency actions should be backed by a valid proposal
-        // **NOTE**: Single-sig mode (required_signatures == 1) allows immediate pause for true emergencies
-        if governance.required_signatures > 1 {
-            // Verify emergency pause is active in governance
             require!(
-                governance.emergency_pause_active,
-                ErrorCode::EmergencyActionNotAuthorized
+                is_primary || is_additional,
+                ErrorCode::UnauthorizedGovernance
             );
+
+            // **SECURITY FIX #44**: If multisig is configured, verify emergency pause is authorized
+            // Emergency actions should be backed by a valid proposal
+            // **NOTE**: Single-sig mode (required_signatures == 1) allows immediate pause for true emergencies
+            if governance.required_signatures > 1 {
+                // Verify emergency pause is active in governance
+                require!(
+                    governance.emergency_pause_active,
+                    ErrorCode::EmergencyActionNotAuthorized
+                );
+            }
+        }
+
+        let current_time = Clock::get()?.unix_timestamp;
+        require!(
+            auto_unpause_at == 0 || auto_unpause_at > current_time,
+            ErrorCode::InvalidPauseExpiry
+        );
+
+        // The pause authority may not take over (and so later lift) a pause it did not place
+        if rift.pause_authority == Some(signer) && rift.is_paused_at(current_time) {
+            require!(rift.paused_by == Some(signer), ErrorCode::PausedByAnotherAuthority);
         }
 
         rift.is_paused = true;
-        rift.pause_timestamp = Clock::get()?.unix_timestamp;
+        rift.pause_timestamp = current_time;
+        rift.pause_reason = Some(pause_reason);
+        rift.pause_auto_unpause_at = auto_unpause_at;
+        rift.paused_by = Some(signer);
 
         emit!(RiftPaused {
             rift: rift.key(),
             authority: ctx.accounts.governance_authority.key(),
             timestamp: rift.pause_timestamp,
+            reason: pause_reason,
+            auto_unpause_at,
         });
 
         Ok(())
@@ -1960,33 +3128,44 @@ pub mod rifts_protocol {
         ctx: Context<EmergencyUnpause>,
     ) -> Result<()> {
         let rift = &mut ctx.accounts.rift;
-        let governance = &ctx.accounts.governance;
-
-        // **SECURITY FIX #44**: Verify governance authority via signature bitmap
-        // Check if signer is either primary or additional authority
         let signer = ctx.accounts.governance_authority.key();
-        let is_primary = signer == governance.authority;
-        let is_additional = governance.additional_authorities.contains(&signer);
 
-        require!(
-            is_primary || is_additional,
-            ErrorCode::UnauthorizedGovernance
-        );
+        // The rift's own pause authority may lift only pauses it placed itself;
+        // governance and automatic pauses still need governance authorization
+        let own_pause = rift.pause_authority == Some(signer) && rift.paused_by == Some(signer);
+        if !own_pause {
+            let governance = ctx.accounts.governance
+                .as_ref()
+                .ok_or(ErrorCode::UnauthorizedGovernance)?;
+
+            // **SECURITY FIX #44**: Verify governance authority via signature bitmap
+            // Check if signer is either primary or additional authority
+            let is_primary = signer == governance.authority;
+            let is_additional = governance.additional_authorities.contains(&signer);
 
-        // **SECURITY FIX #44**: If multisig is configured, verify unpause is authorized
-        // Unpause should either be done when pause is not active OR when authorized
-        // **NOTE**: Single-sig mode (required_signatures == 1) allows immediate unpause for operational recovery
-        if governance.required_signatures > 1 {
-            // Allow unpause if emergency pause is not active in governance anymore
-            // This means the governance multisig has already approved the unpause
             require!(
-                !governance.emergency_pause_active,
-                ErrorCode::EmergencyActionNotAuthorized
+                is_primary || is_additional,
+                ErrorCode::UnauthorizedGovernance
             );
+
+            // **SECURITY FIX #44**: If multisig is configured, verify unpause is authorized
+            // Unpause should either be done when pause is not active OR when authorized
+            // **NOTE**: Single-sig mode (required_signatures == 1) allows immediate unpause for operational recovery
+            if governance.required_signatures > 1 {
+                // Allow unpause if emergency pause is not active in governance anymore
+                // This means the governance multisig has already approved the unpause
+                require!(
+                    !governance.emergency_pause_active,
+                    ErrorCode::EmergencyActionNotAuthorized
+                );
+            }
         }
 
         rift.is_paused = false;
         rift.pause_timestamp = 0;
+        rift.pause_reason = None;
+        rift.pause_auto_unpause_at = 0;
+        rift.paused_by = None;
 
         emit!(RiftUnpaused {
             rift: rift.key(),
@@ -2025,19 +3204,83 @@ pub mod rifts_protocol {
         Ok(())
     }
 
+    /// Admin function: Announce a close of any rift (program authority only)
+    /// admin_close_rift can act once ADMIN_CLOSE_GRACE has passed; the creator may cancel meanwhile
+    pub fn admin_propose_close(
+        ctx: Context<AdminProposeClose>,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.program_authority.key() == ctx.accounts.protocol_config.protocol_admin,
+            ErrorCode::UnauthorizedAdmin
+        );
+
+        let rift = &mut ctx.accounts.rift;
+        require!(rift.close_proposed_at == 0, ErrorCode::CloseAlreadyProposed);
+
+        let now = Clock::get()?.unix_timestamp;
+        rift.close_proposed_at = now;
+
+        let executable_at = now
+            .checked_add(ADMIN_CLOSE_GRACE)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        msg!("Admin proposed closing rift {} (executable at {})", rift.key(), executable_at);
+
+        emit!(RiftCloseProposed {
+            rift: rift.key(),
+            creator: rift.creator,
+            admin: ctx.accounts.program_authority.key(),
+            proposed_at: now,
+            executable_at,
+        });
+
+        Ok(())
+    }
+
+    /// Cancel a pending admin close (creator only)
+    pub fn cancel_admin_close(
+        ctx: Context<CancelAdminClose>,
+    ) -> Result<()> {
+        let rift = &mut ctx.accounts.rift;
+        require!(rift.close_proposed_at != 0, ErrorCode::CloseNotProposed);
+
+        let proposed_at = rift.close_proposed_at;
+        rift.close_proposed_at = 0;
+
+        msg!("Creator cancelled pending admin close of rift {}", rift.key());
+
+        emit!(RiftCloseCancelled {
+            rift: rift.key(),
+            creator: ctx.accounts.creator.key(),
+            proposed_at,
+        });
+
+        Ok(())
+    }
+
     /// Admin function: Close any rift regardless of creator (program authority only)
+    /// Requires a close proposed via admin_propose_close at least ADMIN_CLOSE_GRACE ago
     pub fn admin_close_rift(
         ctx: Context<AdminCloseRift>,
     ) -> Result<()> {
         let rift = &ctx.accounts.rift;
 
         // Only program authority can use this function
-        let admin_pubkey = Pubkey::from_str_const("4NHB7rAvsDjV5USbuntY4UcgnQS1zQcc8K69htaAupHk");
         require!(
-            ctx.accounts.program_authority.key() == admin_pubkey,
+            ctx.accounts.program_authority.key() == ctx.accounts.protocol_config.protocol_admin,
             ErrorCode::UnauthorizedAdmin
         );
 
+        // The creator must have had the full grace period to object or migrate
+        require!(rift.close_proposed_at != 0, ErrorCode::CloseNotProposed);
+        let executable_at = rift.close_proposed_at
+            .checked_add(ADMIN_CLOSE_GRACE)
+            .ok_or(ErrorCode::MathOverflow)?;
+        require!(
+            Clock::get()?.unix_timestamp >= executable_at,
+            ErrorCode::CloseGracePending
+        );
+
         // Log the admin close action
         msg!("Admin closing rift: {} (original creator: {})", rift.key(), rift.creator);
 
@@ -2050,6 +3293,47 @@ pub mod rifts_protocol {
         Ok(())
     }
 
+    /// Recover non-underlying SPL tokens mistakenly sent to a rift's vault authority (admin only)
+    /// The underlying mint can never be moved here, so backing stays intact
+    pub fn admin_rescue_tokens(
+        ctx: Context<AdminRescueTokens>,
+        amount: u64,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.program_authority.key() == ctx.accounts.protocol_config.protocol_admin,
+            ErrorCode::UnauthorizedAdmin
+        );
+        require!(amount > 0, ErrorCode::InvalidAmount);
+
+        let rift_key = ctx.accounts.rift.key();
+        let vault_seeds = &[b"vault_auth", rift_key.as_ref(), &[ctx.bumps.vault_authority]];
+        let signer_seeds = &[&vault_seeds[..]];
+
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.stray_token_account.to_account_info(),
+                to: ctx.accounts.recipient_token_account.to_account_info(),
+                authority: ctx.accounts.vault_authority.to_account_info(),
+            },
+            signer_seeds,
+        );
+        token::transfer(transfer_ctx, amount)?;
+
+        msg!("Rescued {} of mint {} from rift {}",
+             amount, ctx.accounts.stray_token_account.mint, rift_key);
+
+        emit!(TokensRescued {
+            rift: rift_key,
+            mint: ctx.accounts.stray_token_account.mint,
+            amount,
+            recipient: ctx.accounts.recipient_token_account.key(),
+            admin: ctx.accounts.program_authority.key(),
+        });
+
+        Ok(())
+    }
+
     /// Clean up stuck accounts from failed rift creation attempts
     /// **SECURITY FIX**: Only allow creator to clean up their own stuck accounts
     pub fn cleanup_stuck_accounts(
@@ -2169,924 +3453,4518 @@ pub mod rifts_protocol {
         Ok(())
     }
 
-}
+    /// Apply a governance-approved oracle registry update to a rift
+    /// First pending pubkey becomes the Pyth account, second (if any) the Switchboard feed
+    pub fn execute_oracle_update_proposal(
+        ctx: Context<ExecuteGovernanceProposal>,
+        proposal_id: u64,
+    ) -> Result<()> {
+        let rift = &mut ctx.accounts.rift;
+        let governance = &ctx.accounts.governance;
+        let proposal = &ctx.accounts.proposal;
 
-// SIMPLIFIED ACCOUNT STRUCTS TO REDUCE STACK USAGE
+        // Same binding checks as execute_governance_proposal, against the oracle update slot
+        require!(
+            governance.oracle_update_proposal_id == proposal_id,
+            ErrorCode::ProposalNotBoundToGovernance
+        );
+        require!(
+            proposal.id == proposal_id,
+            ErrorCode::ProposalMismatch
+        );
+        require!(
+            proposal.proposal_type == governance::ProposalType::EmergencyAction,
+            ErrorCode::InvalidProposalType
+        );
+        require!(
+            proposal.status == governance::ProposalStatus::Executed,
+            ErrorCode::ProposalNotApproved
+        );
 
-#[derive(Accounts)]
-#[instruction(vanity_seed: [u8; 32], seed_len: u8, mint_bump: u8, burn_fee_bps: u16, partner_fee_bps: u16, partner_wallet: Option<Pubkey>, rift_name: [u8; 32], name_len: u8)]
-pub struct CreateRiftWithVanityPDA<'info> {
-    #[account(mut)]
-    pub creator: Signer<'info>,
+        let oracle_updates = governance.pending_oracle_updates
+            .as_ref()
+            .filter(|updates| !updates.is_empty())
+            .ok_or(ErrorCode::NoPendingOracleUpdates)?;
 
-    #[account(
-        init,
-        payer = creator,
-        space = 8 + std::mem::size_of::<Rift>(),
-        seeds = [b"rift", underlying_mint.key().as_ref(), creator.key().as_ref(), &vanity_seed[..seed_len as usize]],
-        bump,
-    )]
-    pub rift: Account<'info, Rift>,
+        let pyth_account = oracle_updates[0];
+        let switchboard_account = oracle_updates.get(1).copied();
 
-    pub underlying_mint: Account<'info, Mint>,
+        for oracle in std::iter::once(pyth_account).chain(switchboard_account) {
+            require!(
+                oracle != Pubkey::default()
+                    && oracle != anchor_lang::solana_program::system_program::ID,
+                ErrorCode::InvalidOracleAccount
+            );
+        }
 
-    /// The PDA-derived mint account for vanity address
-    #[account(
-        init,
-        payer = creator,
-        mint::decimals = underlying_mint.decimals,
-        mint::authority = rift_mint_authority,
-        seeds = [b"rift_mint", creator.key().as_ref(), underlying_mint.key().as_ref(), &vanity_seed[..seed_len as usize]],
-        bump,
-    )]
-    pub rift_mint: Account<'info, Mint>,
+        rift.pyth_price_account = Some(pyth_account);
+        if switchboard_account.is_some() {
+            rift.switchboard_feed_account = switchboard_account;
+        }
+        rift.last_governance_update = Clock::get()?.unix_timestamp;
 
-    /// CHECK: PDA vault for tokens
-    #[account(
-        seeds = [b"vault", rift.key().as_ref()],
-        bump
-    )]
-    pub vault: UncheckedAccount<'info>,
+        msg!("Oracle accounts updated by governance proposal {} - Pyth: {}, Switchboard: {:?}",
+             proposal_id, pyth_account, switchboard_account);
 
-    /// CHECK: PDA for rift mint authority
-    #[account(
-        seeds = [b"rift_mint_auth", rift.key().as_ref()],
-        bump
-    )]
-    pub rift_mint_authority: UncheckedAccount<'info>,
+        Ok(())
+    }
 
-    pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
-}
+    /// Read-only: report every fee destination and its share of the wrap fee
+    /// Result is returned via set_return_data as a borsh-encoded FeeRouting
+    pub fn get_fee_routing(
+        ctx: Context<GetFeeRouting>,
+    ) -> Result<()> {
+        let rift = &ctx.accounts.rift;
 
-#[derive(Accounts)]
-#[instruction(burn_fee_bps: u16, partner_fee_bps: u16, partner_wallet: Option<Pubkey>, rift_name: [u8; 32], name_len: u8)]
-pub struct CreateRift<'info> {
-    #[account(mut)]
-    pub creator: Signer<'info>,
-    
-    #[account(
-        init,
-        payer = creator,
-        space = 8 + std::mem::size_of::<Rift>() + 36, // Extra 36 bytes for String (4 bytes length + 32 bytes max string)
-        seeds = [b"rift", underlying_mint.key().as_ref(), creator.key().as_ref()],
-        constraint = underlying_mint.key() != Pubkey::default() && creator.key() != Pubkey::default() @ ErrorCode::InvalidSeedComponent,
-        bump,
-        constraint = creator.lamports() >= Rent::get()?.minimum_balance(8 + std::mem::size_of::<Rift>() + 36) @ ErrorCode::InsufficientRentExemption
-    )]
-    pub rift: Account<'info, Rift>,
-    
-    pub underlying_mint: Account<'info, Mint>,
-    
-    #[account(
-        init_if_needed,
-        payer = creator,
-        mint::decimals = underlying_mint.decimals,
-        mint::authority = rift_mint_authority,
-        seeds = [b"rift_mint", underlying_mint.key().as_ref(), creator.key().as_ref()],
-        constraint = underlying_mint.key() != Pubkey::default() && creator.key() != Pubkey::default() @ ErrorCode::InvalidSeedComponent,
-        bump,
-        constraint = creator.lamports() >= Rent::get()?.minimum_balance(Mint::LEN) @ ErrorCode::InsufficientRentExemption
-    )]
-    pub rift_mint: Account<'info, Mint>,
-    
-    /// CHECK: PDA for mint authority
-    #[account(
-        seeds = [b"rift_mint_auth", rift.key().as_ref()],
-        bump
-    )]
-    pub rift_mint_authority: UncheckedAccount<'info>,
-    
-    pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
-}
+        let routing = rift.get_fee_routing(rift.key())?;
 
-#[derive(Accounts)]
-pub struct InitializeVault<'info> {
-    #[account(mut)]
-    pub user: Signer<'info>,
+        msg!("Fee routing: burn={}, partner={}, treasury={}, fee_collector={} (lp_stakers={}, rifts_burn={})",
+             routing.burn_bps, routing.partner_bps, routing.treasury_bps,
+             routing.fee_collector_bps, routing.lp_staker_bps, routing.rifts_burn_bps);
 
-    #[account(mut)]
-    pub rift: Account<'info, Rift>,
+        anchor_lang::solana_program::program::set_return_data(&routing.try_to_vec()?);
 
-    /// Vault token account
-    #[account(
-        init,
-        payer = user,
-        token::mint = underlying_mint,
-        token::authority = rift_mint_authority,
-        seeds = [b"vault", rift.key().as_ref()],
-        bump
-    )]
-    pub vault: Account<'info, TokenAccount>,
+        Ok(())
+    }
 
-    pub underlying_mint: Account<'info, Mint>,
+    /// Estimate how long staking `amount` takes for rewards to cover the wrap + unwrap fees
+    /// Returns a BreakevenEstimate via return data (read-only)
+    pub fn get_breakeven_estimate(
+        ctx: Context<GetBreakevenEstimate>,
+        amount: u64,
+    ) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidAmount);
 
-    /// CHECK: PDA
-    #[account(
-        seeds = [b"rift_mint_auth", rift.key().as_ref()],
-        bump
-    )]
-    pub rift_mint_authority: UncheckedAccount<'info>,
+        let rift = &ctx.accounts.rift;
+        let estimate = rift.get_breakeven_estimate(amount)?;
 
-    pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
-}
+        msg!("Breakeven: fees={}, rewards/hour={}, seconds={:?}",
+             estimate.total_fees, estimate.rewards_per_hour, estimate.seconds_to_breakeven);
 
-#[derive(Accounts)]
-pub struct WrapTokens<'info> {
-    #[account(mut)]
-    pub user: Signer<'info>,
+        anchor_lang::solana_program::program::set_return_data(&estimate.try_to_vec()?);
 
-    #[account(mut)]
-    pub rift: Account<'info, Rift>,
+        Ok(())
+    }
 
-    /// **SECURITY FIX #49**: Validate user's underlying token account
-    #[account(
-        mut,
-        constraint = user_underlying.mint == rift.underlying_mint @ ErrorCode::InvalidMint,
-        constraint = user_underlying.owner == user.key() @ ErrorCode::UnauthorizedTokenAccount
-    )]
-    pub user_underlying: Account<'info, TokenAccount>,
+    /// Configure how wraps whose fee rounds to zero are handled (creator only)
+    /// ChargeMinimum charges 1 base unit, Reject fails with FeeTooSmall
+    pub fn set_zero_fee_policy(
+        ctx: Context<SetZeroFeePolicy>,
+        policy: ZeroFeePolicy,
+    ) -> Result<()> {
+        let rift = &mut ctx.accounts.rift;
+
+        rift.zero_fee_policy = policy;
+        rift.last_governance_update = Clock::get()?.unix_timestamp;
+
+        msg!("Zero-fee wrap policy set: {:?}", policy);
+
+        Ok(())
+    }
+
+    /// Set the token account that receives the treasury cut of process_fee_distribution (creator only)
+    /// Typically the governance treasury vault, owned by its ["treasury", governance] PDA
+    pub fn set_treasury(ctx: Context<SetTreasury>, treasury: Option<Pubkey>) -> Result<()> {
+        let rift = &mut ctx.accounts.rift;
+
+        if let Some(treasury) = treasury {
+            require!(treasury != Pubkey::default(), ErrorCode::InvalidTreasury);
+        }
+
+        rift.treasury = treasury;
+        rift.last_governance_update = Clock::get()?.unix_timestamp;
+
+        msg!("Treasury set to {:?}", treasury);
+
+        Ok(())
+    }
+
+    /// Set or clear a key that may pause/unpause this rift without governance (creator only)
+    pub fn set_pause_authority(
+        ctx: Context<SetPauseAuthority>,
+        pause_authority: Option<Pubkey>,
+    ) -> Result<()> {
+        let rift = &mut ctx.accounts.rift;
+
+        rift.pause_authority = pause_authority;
+        rift.last_governance_update = Clock::get()?.unix_timestamp;
+
+        msg!("Pause authority set to {:?}", pause_authority);
+
+        Ok(())
+    }
+
+    /// Rename a rift (creator only); bytes past name_len are zeroed
+    pub fn set_rift_name(
+        ctx: Context<SetRiftName>,
+        new_name: [u8; 32],
+        name_len: u8,
+    ) -> Result<()> {
+        require!(name_len <= 32, ErrorCode::NameTooLong);
+
+        let rift = &mut ctx.accounts.rift;
+
+        let old_name = rift.name;
+        let mut name = [0u8; 32];
+        name[..name_len as usize].copy_from_slice(&new_name[..name_len as usize]);
+        rift.name = name;
+        rift.last_governance_update = Clock::get()?.unix_timestamp;
+
+        msg!("Rift {} renamed", rift.key());
+
+        emit!(RiftRenamed {
+            rift: rift.key(),
+            old_name,
+            new_name: name,
+            renamed_by: ctx.accounts.creator.key(),
+        });
+
+        Ok(())
+    }
+
+    /// Choose whether unwrapped RIFT is burned or sent to an incinerator (creator only)
+    /// `incinerator` is the owner of the receiving account: the Solana incinerator (None) or rift_incinerator_pda
+    pub fn set_burn_mode(
+        ctx: Context<SetBurnMode>,
+        burn_mode: BurnMode,
+        incinerator: Option<Pubkey>,
+    ) -> Result<()> {
+        let rift = &mut ctx.accounts.rift;
+
+        // Only owners nobody can sign for: Solana's incinerator or this rift's incinerator PDA
+        if let Some(owner) = incinerator {
+            require!(
+                owner == anchor_lang::solana_program::incinerator::ID
+                    || owner == rift_incinerator_pda(&rift.key()),
+                ErrorCode::InvalidIncinerator
+            );
+        }
+
+        rift.burn_mode = burn_mode;
+        rift.incinerator = incinerator;
+        rift.last_governance_update = Clock::get()?.unix_timestamp;
+
+        msg!("Burn mode set: {:?} (incinerator {})", burn_mode, rift.incinerator_owner());
+
+        Ok(())
+    }
+
+    /// Choose whether protocol position fees are claimed into the fee vault or compounded (creator only)
+    pub fn set_protocol_fee_mode(
+        ctx: Context<SetProtocolFeeMode>,
+        protocol_fee_mode: ProtocolFeeMode,
+    ) -> Result<()> {
+        let rift = &mut ctx.accounts.rift;
+
+        rift.protocol_fee_mode = protocol_fee_mode;
+        rift.last_governance_update = Clock::get()?.unix_timestamp;
+
+        msg!("Protocol fee mode set: {:?}", protocol_fee_mode);
+
+        Ok(())
+    }
+
+    /// Set the maximum underlying a rift may hold (creator only, 0 = unlimited)
+    pub fn set_deposit_cap(
+        ctx: Context<SetDepositCap>,
+        max_total_underlying: u64,
+    ) -> Result<()> {
+        let rift = &mut ctx.accounts.rift;
+
+        let old_cap = rift.max_total_underlying;
+        rift.max_total_underlying = max_total_underlying;
+        rift.last_governance_update = Clock::get()?.unix_timestamp;
+
+        emit!(DepositCapChanged {
+            rift: rift.key(),
+            old_cap,
+            new_cap: max_total_underlying,
+            changed_by: ctx.accounts.creator.key(),
+        });
+
+        Ok(())
+    }
+
+    /// Cap a single wrap to a share of total_underlying_wrapped (creator only, 0 bps = disabled)
+    /// The cap only applies once total_underlying_wrapped reaches `tvl_threshold`
+    pub fn set_max_wrap_pct_of_tvl(
+        ctx: Context<SetMaxWrapPctOfTvl>,
+        max_wrap_pct_of_tvl_bps: u16,
+        tvl_threshold: u64,
+    ) -> Result<()> {
+        require!(max_wrap_pct_of_tvl_bps <= 10000, ErrorCode::InvalidWrapTvlCap);
+
+        let rift = &mut ctx.accounts.rift;
+
+        rift.max_wrap_pct_of_tvl_bps = max_wrap_pct_of_tvl_bps;
+        rift.wrap_tvl_cap_threshold = tvl_threshold;
+        rift.last_governance_update = Clock::get()?.unix_timestamp;
+
+        msg!("Max wrap set to {} bps of TVL above {} underlying", max_wrap_pct_of_tvl_bps, tvl_threshold);
+
+        Ok(())
+    }
+
+    /// Restrict wrapping to users in a Merkle allowlist (creator only)
+    pub fn set_whitelist_root(
+        ctx: Context<SetWhitelistRoot>,
+        merkle_root: [u8; 32],
+    ) -> Result<()> {
+        let rift = &mut ctx.accounts.rift;
+
+        let old_root = rift.whitelist_merkle_root;
+        rift.whitelist_merkle_root = Some(merkle_root);
+        rift.last_governance_update = Clock::get()?.unix_timestamp;
+
+        emit!(WhitelistRootChanged {
+            rift: rift.key(),
+            old_root,
+            new_root: rift.whitelist_merkle_root,
+        });
+
+        Ok(())
+    }
+
+    /// End the private phase and open wrapping to everyone (creator only)
+    pub fn disable_whitelist(
+        ctx: Context<SetWhitelistRoot>,
+    ) -> Result<()> {
+        let rift = &mut ctx.accounts.rift;
+
+        let old_root = rift.whitelist_merkle_root;
+        rift.whitelist_merkle_root = None;
+        rift.last_governance_update = Clock::get()?.unix_timestamp;
+
+        emit!(WhitelistRootChanged {
+            rift: rift.key(),
+            old_root,
+            new_root: None,
+        });
+
+        Ok(())
+    }
+
+    /// Set the minimum staking rewards a claim must reach before minting (creator only)
+    pub fn set_min_claimable_rewards(
+        ctx: Context<SetMinClaimableRewards>,
+        min_claimable_rewards: u64,
+    ) -> Result<()> {
+        let rift = &mut ctx.accounts.rift;
+
+        rift.min_claimable_rewards = min_claimable_rewards;
+        rift.last_governance_update = Clock::get()?.unix_timestamp;
+
+        msg!("Minimum claimable rewards set: {}", min_claimable_rewards);
+
+        Ok(())
+    }
+
+    /// Configure the wrap/unwrap fee discount for LP stakers (creator only)
+    pub fn set_staker_fee_discount(
+        ctx: Context<SetStakerFeeDiscount>,
+        discount_bps: u16,
+        min_staked_amount: u64,
+    ) -> Result<()> {
+        require!(discount_bps <= 10000, ErrorCode::InvalidFeeDiscount);
+
+        let rift = &mut ctx.accounts.rift;
+
+        rift.staker_fee_discount_bps = discount_bps;
+        rift.staker_discount_min_stake = min_staked_amount;
+        rift.last_governance_update = Clock::get()?.unix_timestamp;
+
+        msg!("Staker fee discount set: {} bps for stakes >= {}", discount_bps, min_staked_amount);
+
+        Ok(())
+    }
+
+    /// Report whether rift accounting matches on-chain state (read-only, for monitoring)
+    pub fn check_invariants(ctx: Context<CheckInvariants>) -> Result<()> {
+        let rift = &ctx.accounts.rift;
+
+        let status = InvariantStatus {
+            rift: rift.key(),
+            rift_mint_supply: ctx.accounts.rift_mint.supply,
+            total_rift_minted: rift.total_rift_minted,
+            supply_within_accounting: ctx.accounts.rift_mint.supply <= rift.expected_mint_supply()?,
+        };
+
+        msg!("Invariants: supply={}, total_rift_minted={}, supply_within_accounting={}",
+             status.rift_mint_supply, status.total_rift_minted, status.supply_within_accounting);
+
+        anchor_lang::solana_program::program::set_return_data(&status.try_to_vec()?);
+
+        Ok(())
+    }
+
+    /// Return the rift's backing ratio high-water mark (u64) via return data
+    pub fn get_backing_ratio_high_water(ctx: Context<GetBackingRatioHighWater>) -> Result<()> {
+        let high_water = ctx.accounts.rift.backing_ratio_high_water;
+
+        msg!("Backing ratio high-water mark: {}", high_water);
+
+        anchor_lang::solana_program::program::set_return_data(&high_water.try_to_vec()?);
+
+        Ok(())
+    }
+
+    /// Read-only: return the oracle price buffer ordered oldest to newest
+    /// Result is returned via set_return_data as a borsh-encoded OracleHistory
+    pub fn get_oracle_history(ctx: Context<GetOracleHistory>) -> Result<()> {
+        let history = ctx.accounts.rift.get_oracle_history();
+
+        msg!("Oracle history: price_index={}", history.price_index);
+
+        anchor_lang::solana_program::program::set_return_data(&history.try_to_vec()?);
+
+        Ok(())
+    }
+
+    /// Read-only: seconds until the next oracle update and forced rebalance are due
+    /// Result is returned via set_return_data as a borsh-encoded Countdowns
+    pub fn get_countdowns(ctx: Context<GetCountdowns>) -> Result<()> {
+        let rift = &ctx.accounts.rift;
+        let current_time = Clock::get()?.unix_timestamp;
+
+        let countdowns = Countdowns {
+            oracle_countdown: rift.get_oracle_countdown(current_time),
+            rebalance_countdown: rift.get_rebalance_countdown(current_time),
+            last_oracle_update: rift.last_oracle_update,
+            last_rebalance: rift.last_rebalance,
+            rebalance_count: rift.rebalance_count,
+        };
+
+        msg!("Countdowns: oracle={}s, rebalance={}s",
+             countdowns.oracle_countdown, countdowns.rebalance_countdown);
+
+        anchor_lang::solana_program::program::set_return_data(&countdowns.try_to_vec()?);
+
+        Ok(())
+    }
+
+    /// Check a rift's configuration for internal consistency without mutating it
+    /// Returns a CONFIG_VIOLATION_* bitmask via return data (0 = all checks pass)
+    pub fn validate_rift_config(ctx: Context<ValidateRiftConfig>) -> Result<()> {
+        let violations = ctx.accounts.rift.config_violations();
+
+        msg!("Rift config violations: {:#06b}", violations);
+
+        anchor_lang::solana_program::program::set_return_data(&violations.try_to_vec()?);
+
+        Ok(())
+    }
+
+    /// Set the LP staking reward rate for a rift (governance authority only)
+    pub fn set_reward_rate(
+        ctx: Context<SetRewardRate>,
+        reward_rate_per_hour: u64,
+    ) -> Result<()> {
+        let rift = &mut ctx.accounts.rift;
+        let governance = &ctx.accounts.governance;
+
+        let signer = ctx.accounts.governance_authority.key();
+        let is_primary = signer == governance.authority;
+        let is_additional = governance.additional_authorities.contains(&signer);
+        require!(
+            is_primary || is_additional,
+            ErrorCode::UnauthorizedGovernance
+        );
+
+        require!(
+            reward_rate_per_hour <= MAX_REWARD_RATE_PER_HOUR,
+            ErrorCode::InvalidRewardRate
+        );
+
+        rift.reward_rate_per_hour = reward_rate_per_hour;
+        rift.last_governance_update = Clock::get()?.unix_timestamp;
+
+        msg!("Reward rate per hour updated: {}", reward_rate_per_hour);
+
+        Ok(())
+    }
+
+    /// Set the linear vesting period for claimed staking rewards (creator only, 0 = no vesting)
+    pub fn set_reward_vest_period(
+        ctx: Context<SetRewardVestPeriod>,
+        reward_vest_period: i64,
+    ) -> Result<()> {
+        require!(
+            (0..=MAX_REWARD_VEST_PERIOD).contains(&reward_vest_period),
+            ErrorCode::InvalidVestPeriod
+        );
+
+        let rift = &mut ctx.accounts.rift;
+
+        rift.reward_vest_period = reward_vest_period;
+        rift.last_governance_update = Clock::get()?.unix_timestamp;
+
+        msg!("Reward vest period set: {} seconds", reward_vest_period);
+
+        Ok(())
+    }
+
+    /// Read-only: report oracle freshness so keepers know when to push a new price
+    /// Result is returned via set_return_data as a borsh-encoded OracleHealth
+    pub fn oracle_health(ctx: Context<OracleHealthCheck>) -> Result<()> {
+        let rift = &ctx.accounts.rift;
+        let current_time = Clock::get()?.unix_timestamp;
+
+        let health = rift.get_oracle_health(rift.key(), current_time);
+
+        msg!("Oracle health: age={}s, countdown={}s, fresh={}, avg_price={}, samples={}",
+             health.seconds_since_update, health.oracle_countdown,
+             health.newest_sample_fresh, health.average_price, health.sample_count);
+
+        emit!(OracleHealthReported {
+            rift: health.rift,
+            seconds_since_update: health.seconds_since_update,
+            oracle_countdown: health.oracle_countdown,
+            newest_sample_fresh: health.newest_sample_fresh,
+            average_price: health.average_price,
+            timestamp: current_time,
+        });
+
+        anchor_lang::solana_program::program::set_return_data(&health.try_to_vec()?);
+
+        Ok(())
+    }
+
+    /// Set the maximum oracle price age for a rift (governance authority only)
+    pub fn set_max_oracle_staleness(
+        ctx: Context<SetMaxOracleStaleness>,
+        max_oracle_staleness: i64,
+    ) -> Result<()> {
+        let rift = &mut ctx.accounts.rift;
+        let governance = &ctx.accounts.governance;
+
+        let signer = ctx.accounts.governance_authority.key();
+        let is_primary = signer == governance.authority;
+        let is_additional = governance.additional_authorities.contains(&signer);
+        require!(
+            is_primary || is_additional,
+            ErrorCode::UnauthorizedGovernance
+        );
+
+        require!(
+            (MIN_MAX_ORACLE_STALENESS..=MAX_MAX_ORACLE_STALENESS).contains(&max_oracle_staleness),
+            ErrorCode::InvalidOracleStaleness
+        );
+
+        rift.max_oracle_staleness = max_oracle_staleness;
+        rift.last_governance_update = Clock::get()?.unix_timestamp;
+
+        msg!("Max oracle staleness updated: {} seconds", max_oracle_staleness);
+
+        Ok(())
+    }
+
+    /// Toggle confidence-weighted oracle aggregation for rebalances (creator only)
+    pub fn set_confidence_weighting(
+        ctx: Context<SetConfidenceWeighting>,
+        enabled: bool,
+    ) -> Result<()> {
+        let rift = &mut ctx.accounts.rift;
+
+        rift.use_confidence_weighting = enabled;
+        rift.last_governance_update = Clock::get()?.unix_timestamp;
+
+        msg!("Confidence-weighted oracle aggregation: {}", enabled);
+
+        Ok(())
+    }
+
+    /// Top up the RIFTS staking reward budget by depositing RIFTS into the reward budget vault
+    /// (creator or governance authority)
+    pub fn fund_reward_budget(
+        ctx: Context<FundRewardBudget>,
+        amount: u64,
+    ) -> Result<()> {
+        require!(amount > 0, ErrorCode::InvalidAmount);
+
+        let rift = &mut ctx.accounts.rift;
+        let governance = &ctx.accounts.governance;
+
+        let signer = ctx.accounts.authority.key();
+        let is_creator = signer == rift.creator;
+        let is_governance = signer == governance.authority
+            || governance.additional_authorities.contains(&signer);
+        require!(is_creator || is_governance, ErrorCode::Unauthorized);
+
+        // The budget is only ever what the vault actually holds
+        token::transfer(
+            CpiContext::new(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.funder_rifts_tokens.to_account_info(),
+                    to: ctx.accounts.reward_budget_vault.to_account_info(),
+                    authority: ctx.accounts.authority.to_account_info(),
+                },
+            ),
+            amount,
+        )?;
+
+        rift.rifts_reward_budget = rift.rifts_reward_budget
+            .checked_add(amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+        rift.last_governance_update = Clock::get()?.unix_timestamp;
+
+        msg!("Reward budget funded: +{} (now {})", amount, rift.rifts_reward_budget);
+
+        emit!(RewardBudgetFunded {
+            rift: rift.key(),
+            amount,
+            new_budget: rift.rifts_reward_budget,
+            funded_by: signer,
+        });
+
+        Ok(())
+    }
+
+    /// Set the per-transaction cap on Meteora liquidity removed by unwraps (creator only, 0 = uncapped)
+    pub fn set_max_liquidity_removal(
+        ctx: Context<SetMaxLiquidityRemoval>,
+        max_liquidity_removal_per_tx: u128,
+    ) -> Result<()> {
+        let rift = &mut ctx.accounts.rift;
+
+        rift.max_liquidity_removal_per_tx = max_liquidity_removal_per_tx;
+        rift.last_governance_update = Clock::get()?.unix_timestamp;
+
+        msg!("Max liquidity removal per tx: {}", max_liquidity_removal_per_tx);
+
+        Ok(())
+    }
+
+    /// Approve a Meteora config for pool creation (governance authority only)
+    /// The allowlist is created on first use, seeded with DEFAULT_METEORA_CONFIG
+    pub fn add_meteora_config(
+        ctx: Context<ManageMeteoraConfigs>,
+        config: Pubkey,
+    ) -> Result<()> {
+        let governance = &ctx.accounts.governance;
+        let signer = ctx.accounts.governance_authority.key();
+        require!(
+            signer == governance.authority || governance.additional_authorities.contains(&signer),
+            ErrorCode::UnauthorizedGovernance
+        );
+
+        let allowlist = &mut ctx.accounts.meteora_config_allowlist;
+        if !allowlist.initialized {
+            allowlist.initialized = true;
+            allowlist.bump = ctx.bumps.meteora_config_allowlist;
+            allowlist.configs.push(DEFAULT_METEORA_CONFIG);
+        }
+
+        if !allowlist.is_allowed(&config) {
+            require!(
+                allowlist.configs.len() < MAX_METEORA_CONFIGS,
+                ErrorCode::TooManyMeteoraConfigs
+            );
+            allowlist.configs.push(config);
+        }
+
+        msg!("Meteora config allowed: {}", config);
+
+        emit!(MeteoraConfigAllowlistUpdated {
+            config,
+            allowed: true,
+            updated_by: signer,
+        });
+
+        Ok(())
+    }
+
+    /// Revoke a Meteora config; existing pools are unaffected (governance authority only)
+    pub fn remove_meteora_config(
+        ctx: Context<ManageMeteoraConfigs>,
+        config: Pubkey,
+    ) -> Result<()> {
+        let governance = &ctx.accounts.governance;
+        let signer = ctx.accounts.governance_authority.key();
+        require!(
+            signer == governance.authority || governance.additional_authorities.contains(&signer),
+            ErrorCode::UnauthorizedGovernance
+        );
+
+        let allowlist = &mut ctx.accounts.meteora_config_allowlist;
+        let index = allowlist.configs
+            .iter()
+            .position(|allowed| *allowed == config)
+            .ok_or(ErrorCode::MeteoraConfigNotAllowed)?;
+        allowlist.configs.remove(index);
+
+        msg!("Meteora config removed: {}", config);
+
+        emit!(MeteoraConfigAllowlistUpdated {
+            config,
+            allowed: false,
+            updated_by: signer,
+        });
+
+        Ok(())
+    }
+
+    /// Choose the Meteora config create_meteora_pool uses for this rift (creator or governance authority)
+    /// Passing no config account resets to DEFAULT_METEORA_CONFIG
+    pub fn set_meteora_config(ctx: Context<SetMeteoraConfig>) -> Result<()> {
+        let governance = &ctx.accounts.governance;
+        let signer = ctx.accounts.authority.key();
+        let is_creator = signer == ctx.accounts.rift.creator;
+        let is_governance = signer == governance.authority
+            || governance.additional_authorities.contains(&signer);
+        require!(is_creator || is_governance, ErrorCode::Unauthorized);
+
+        let meteora_config_key = match &ctx.accounts.meteora_config {
+            Some(config) => {
+                require!(
+                    config.owner == &METEORA_DAMM_V2_PROGRAM_ID,
+                    ErrorCode::InvalidMeteoraConfig
+                );
+                require!(
+                    ctx.accounts.meteora_config_allowlist.is_allowed(&config.key()),
+                    ErrorCode::MeteoraConfigNotAllowed
+                );
+                Some(config.key())
+            }
+            None => None,
+        };
+
+        let rift = &mut ctx.accounts.rift;
+        let old_config = rift.meteora_config_key.unwrap_or(DEFAULT_METEORA_CONFIG);
+        rift.meteora_config_key = meteora_config_key;
+        rift.last_governance_update = Clock::get()?.unix_timestamp;
+
+        let new_config = meteora_config_key.unwrap_or(DEFAULT_METEORA_CONFIG);
+        msg!("Meteora config for rift {} set to {}", rift.key(), new_config);
+
+        emit!(RiftMeteoraConfigUpdated {
+            rift: rift.key(),
+            old_config,
+            new_config,
+            updated_by: signer,
+        });
+
+        Ok(())
+    }
+
+    /// Sync stored pool fields with the bound Meteora pool (permissionless, reads on-chain state)
+    /// DAMM v2 pools are full-range constant product with no bins, so bin fields are cleared
+    pub fn sync_pool_metadata(ctx: Context<SyncPoolMetadata>) -> Result<()> {
+        let rift = &mut ctx.accounts.rift;
+
+        require!(rift.liquidity_pool.is_some(), ErrorCode::PoolNotInitialized);
+        require!(
+            rift.liquidity_pool == Some(ctx.accounts.pool.key()),
+            ErrorCode::InvalidPoolAccount
+        );
+
+        let pool_state = read_meteora_pool_state(&ctx.accounts.pool)?;
+        require!(
+            (pool_state.token_a_mint == rift.rift_mint && pool_state.token_b_mint == rift.underlying_mint)
+                || (pool_state.token_a_mint == rift.underlying_mint && pool_state.token_b_mint == rift.rift_mint),
+            ErrorCode::InvalidPoolMints
+        );
+
+        let current_time = Clock::get()?.unix_timestamp;
+        rift.pool_sqrt_price = pool_state.sqrt_price;
+        rift.pool_liquidity = pool_state.liquidity;
+        rift.bin_step = 0;
+        rift.active_bin_id = 0;
+        rift.last_pool_sync = current_time;
+
+        msg!("Pool metadata synced: sqrt_price={}, liquidity={}", pool_state.sqrt_price, pool_state.liquidity);
+
+        emit!(PoolMetadataSynced {
+            rift: rift.key(),
+            pool: ctx.accounts.pool.key(),
+            sqrt_price: pool_state.sqrt_price,
+            liquidity: pool_state.liquidity,
+            timestamp: current_time,
+        });
+
+        Ok(())
+    }
+
+    /// Read-only: re-read the live Meteora pool and compare it with the stored pool state
+    /// Returns a PoolStateReport via return data so operators can detect drift
+    pub fn verify_pool_state(ctx: Context<VerifyPoolState>) -> Result<()> {
+        let rift = &ctx.accounts.rift;
+
+        require!(rift.liquidity_pool.is_some(), ErrorCode::PoolNotInitialized);
+        require!(
+            rift.liquidity_pool == Some(ctx.accounts.pool.key()),
+            ErrorCode::InvalidPoolAccount
+        );
+
+        let pool_state = read_meteora_pool_state(&ctx.accounts.pool)?;
+        let live_authority = meteora_pool_authority();
+
+        let report = PoolStateReport {
+            authority_matches: rift.pool_authority == Some(live_authority),
+            token_a_vault_matches: rift.pool_token_a_vault == Some(pool_state.token_a_vault),
+            token_b_vault_matches: rift.pool_token_b_vault == Some(pool_state.token_b_vault),
+            live_authority,
+            live_token_a_vault: pool_state.token_a_vault,
+            live_token_b_vault: pool_state.token_b_vault,
+        };
+
+        if !report.is_consistent() {
+            msg!("⚠️ Pool state drift: authority={}, vault_a={}, vault_b={}",
+                 report.authority_matches, report.token_a_vault_matches, report.token_b_vault_matches);
+        }
+
+        anchor_lang::solana_program::program::set_return_data(&report.try_to_vec()?);
+
+        Ok(())
+    }
+
+    /// Create the protocol config PDA with INITIAL_PROTOCOL_ADMIN (signed and paid for by that admin)
+    /// Records the canonical governance every governance-gated instruction is checked against
+    pub fn initialize_protocol_config(ctx: Context<InitializeProtocolConfig>) -> Result<()> {
+        // The initial admin picks the governance account that may later rotate the admin
+        require!(
+            ctx.accounts.payer.key() == INITIAL_PROTOCOL_ADMIN,
+            ErrorCode::Unauthorized
+        );
+
+        let protocol_config = &mut ctx.accounts.protocol_config;
+
+        protocol_config.protocol_admin = INITIAL_PROTOCOL_ADMIN;
+        protocol_config.governance = ctx.accounts.governance.key();
+        protocol_config.bump = ctx.bumps.protocol_config;
+
+        msg!("Protocol config initialized, admin: {}, governance: {}",
+             INITIAL_PROTOCOL_ADMIN, protocol_config.governance);
+
+        Ok(())
+    }
+
+    /// Set or clear the collection NFT required to create rifts (protocol admin only)
+    pub fn set_rift_factory_gate(
+        ctx: Context<SetRiftFactoryGate>,
+        required_collection_mint: Option<Pubkey>,
+    ) -> Result<()> {
+        require!(
+            ctx.accounts.program_authority.key() == ctx.accounts.protocol_config.protocol_admin,
+            ErrorCode::Unauthorized
+        );
+
+        let factory_config = &mut ctx.accounts.factory_config;
+        factory_config.required_collection_mint = required_collection_mint;
+        factory_config.bump = ctx.bumps.factory_config;
+
+        emit!(RiftFactoryGateUpdated {
+            required_collection_mint,
+            admin: ctx.accounts.program_authority.key(),
+            timestamp: Clock::get()?.unix_timestamp,
+        });
+
+        Ok(())
+    }
+
+    /// Apply a governance-approved protocol admin rotation
+    pub fn execute_protocol_admin_change(
+        ctx: Context<ExecuteProtocolAdminChange>,
+        proposal_id: u64,
+    ) -> Result<()> {
+        let governance = &ctx.accounts.governance;
+        let proposal = &ctx.accounts.proposal;
+
+        // Same proposal binding checks as execute_governance_proposal
+        require!(
+            governance.parameter_change_proposal_id == proposal_id,
+            ErrorCode::ProposalNotBoundToGovernance
+        );
+        require!(
+            proposal.id == proposal_id,
+            ErrorCode::ProposalMismatch
+        );
+        require!(
+            proposal.proposal_type == governance::ProposalType::ParameterChange,
+            ErrorCode::InvalidProposalType
+        );
+        require!(
+            proposal.status == governance::ProposalStatus::Executed,
+            ErrorCode::ProposalNotApproved
+        );
+
+        let new_admin = governance.pending_parameter_changes
+            .as_ref()
+            .and_then(|param_changes| param_changes.protocol_admin)
+            .ok_or(ErrorCode::NoPendingParameterChanges)?;
+        require!(new_admin != Pubkey::default(), ErrorCode::InvalidPublicKey);
+
+        let protocol_config = &mut ctx.accounts.protocol_config;
+        let old_admin = protocol_config.protocol_admin;
+        protocol_config.protocol_admin = new_admin;
+
+        msg!("Protocol admin rotated: {} → {}", old_admin, new_admin);
+
+        emit!(ProtocolAdminChanged {
+            old_admin,
+            new_admin,
+            proposal_id,
+            executor: ctx.accounts.executor.key(),
+        });
+
+        Ok(())
+    }
+
+    /// Apply a governance-approved manual backing_ratio (e.g. while oracles are down)
+    /// The ratio may move at most MAX_MANUAL_BACKING_RATIO_CHANGE_BPS per call
+    pub fn set_backing_ratio(ctx: Context<SetBackingRatio>, proposal_id: u64) -> Result<()> {
+        let rift = &mut ctx.accounts.rift;
+        let governance = &ctx.accounts.governance;
+        let proposal = &ctx.accounts.proposal;
+
+        // Same proposal binding checks as execute_governance_proposal
+        require!(
+            governance.parameter_change_proposal_id == proposal_id,
+            ErrorCode::ProposalNotBoundToGovernance
+        );
+        require!(
+            proposal.id == proposal_id,
+            ErrorCode::ProposalMismatch
+        );
+
+        // Backing ratio proposals from this governance apply at most once and in increasing id order
+        ctx.accounts.applied_proposal.record(proposal_id, ctx.bumps.applied_proposal)?;
+
+        require!(
+            proposal.proposal_type == governance::ProposalType::ParameterChange,
+            ErrorCode::InvalidProposalType
+        );
+        require!(
+            proposal.status == governance::ProposalStatus::Executed,
+            ErrorCode::ProposalNotApproved
+        );
+
+        let param_changes = governance.pending_parameter_changes
+            .as_ref()
+            .ok_or(ErrorCode::NoPendingParameterChanges)?;
+        let new_ratio = param_changes.backing_ratio.ok_or(ErrorCode::NoPendingParameterChanges)?;
+        require!(new_ratio > 0, ErrorCode::InvalidBackingRatio);
+        // A ratio proposal targets exactly one rift and cannot be replayed against others
+        require!(
+            param_changes.backing_ratio_rift == Some(rift.key()),
+            ErrorCode::ProposalNotForThisRift
+        );
+
+        // Bound the per-call change relative to the current ratio
+        let old_ratio = rift.backing_ratio;
+        let max_delta = (old_ratio as u128)
+            .checked_mul(MAX_MANUAL_BACKING_RATIO_CHANGE_BPS as u128)
+            .ok_or(ErrorCode::MathOverflow)?
+            / 10000;
+        require!(
+            (new_ratio.abs_diff(old_ratio) as u128) <= max_delta,
+            ErrorCode::BackingRatioChangeTooLarge
+        );
+
+        rift.backing_ratio = new_ratio;
+        rift.last_governance_update = Clock::get()?.unix_timestamp;
+
+        msg!("Backing ratio manually set: {} → {}", old_ratio, new_ratio);
+
+        emit!(BackingRatioManuallySet {
+            rift: rift.key(),
+            old_backing_ratio: old_ratio,
+            new_backing_ratio: new_ratio,
+            proposal_id,
+            executor: ctx.accounts.executor.key(),
+        });
+
+        Ok(())
+    }
+
+    /// Move underlying held in the vault beyond the backing still owed to holders to the rift treasury
+    /// Recovers over-sent or surplus tokens (creator or governance authority)
+    pub fn sweep_excess_underlying(ctx: Context<SweepExcessUnderlying>) -> Result<()> {
+        let rift = &ctx.accounts.rift;
+        let governance = &ctx.accounts.governance;
+
+        let signer = ctx.accounts.authority.key();
+        let is_creator = signer == rift.creator;
+        let is_governance = signer == governance.authority
+            || governance.additional_authorities.contains(&signer);
+        require!(is_creator || is_governance, ErrorCode::Unauthorized);
+
+        // Unwraps through the pool lower total_underlying_wrapped without a vault outflow,
+        // so the backing kept is reconciled against the live RIFT supply as well
+        let vault_balance = ctx.accounts.vault.amount;
+        let rift_supply = ctx.accounts.rift_mint.supply;
+        let excess = sweepable_excess(vault_balance, rift.total_underlying_wrapped, rift_supply);
+        require!(excess > 0, ErrorCode::NoExcessToSweep);
+
+        let rift_key = rift.key();
+        let bump = [ctx.bumps.vault_authority];
+        let vault_seeds: &[&[u8]] = &[b"vault_auth", rift_key.as_ref(), &bump];
+        let signers = &[vault_seeds];
+
+        let transfer_ctx = CpiContext::new_with_signer(
+            ctx.accounts.token_program.to_account_info(),
+            Transfer {
+                from: ctx.accounts.vault.to_account_info(),
+                to: ctx.accounts.treasury.to_account_info(),
+                authority: ctx.accounts.vault_authority.to_account_info(),
+            },
+            signers,
+        );
+        token::transfer(transfer_ctx, excess)?;
+
+        msg!("Swept {} excess underlying (vault {} vs wrapped {}, RIFT supply {})",
+             excess, vault_balance, rift.total_underlying_wrapped, rift_supply);
+
+        emit!(ExcessSwept {
+            rift: rift_key,
+            amount: excess,
+            vault_balance,
+            total_underlying_wrapped: rift.total_underlying_wrapped,
+            treasury: ctx.accounts.treasury.key(),
+            swept_by: signer,
+        });
+
+        Ok(())
+    }
+
+    /// Trade the pool back toward the oracle price with vault funds and keep the spread
+    /// RIFT rich in the pool: mint and sell RIFT; RIFT cheap: buy RIFT with vault underlying and burn it.
+    /// RIFT stays redeemable 1:1, so anything beyond that backing is profit routed to the fee vault.
+    pub fn execute_protocol_arbitrage(
+        ctx: Context<ExecuteProtocolArbitrage>,
+        amount_in: u64,
+        max_slippage_bps: u16,
+    ) -> Result<()> {
+        let rift = &mut ctx.accounts.rift;
+        let governance = &ctx.accounts.governance;
+
+        let signer = ctx.accounts.authority.key();
+        let is_creator = signer == rift.creator;
+        let is_governance = signer == governance.authority
+            || governance.additional_authorities.contains(&signer);
+        require!(is_creator || is_governance, ErrorCode::Unauthorized);
+
+        let current_time = Clock::get()?.unix_timestamp;
+        require!(!rift.is_paused_at(current_time), ErrorCode::RiftPaused);
+        require!(amount_in > 0, ErrorCode::InvalidAmount);
+        require!(
+            max_slippage_bps <= MAX_ARBITRAGE_SLIPPAGE_BPS,
+            ErrorCode::InvalidSlippageBps
+        );
+
+        // Pool accounts must match the live pool state
+        let pool_state = read_meteora_pool_state(&ctx.accounts.pool)?;
+        require!(
+            ctx.accounts.token_a_vault.key() == pool_state.token_a_vault
+                && ctx.accounts.token_b_vault.key() == pool_state.token_b_vault,
+            ErrorCode::InvalidPoolVault
+        );
+        let rift_is_token_a = pool_state.token_a_mint == rift.rift_mint;
+        require!(
+            rift_is_token_a || pool_state.token_b_mint == rift.rift_mint,
+            ErrorCode::InvalidPoolAccount
+        );
+
+        // Only act on a deviation beyond the rift's threshold
+        let pool_price_before = parse_meteora_pool_price(&ctx.accounts.pool, &rift.rift_mint)?;
+        let oracle_price = rift.get_average_oracle_price()?;
+        let deviation_bps = rift.compute_arbitrage_bps(pool_price_before)?;
+        require!(
+            deviation_bps > rift.arbitrage_threshold_bps,
+            ErrorCode::ArbitrageBelowThreshold
+        );
+        let sell_rift = pool_price_before > oracle_price;
+
+        // Bound the trade by the pool's reserve of the token going in
+        let (rift_reserve, underlying_reserve) = if rift_is_token_a {
+            (ctx.accounts.token_a_vault.amount, ctx.accounts.token_b_vault.amount)
+        } else {
+            (ctx.accounts.token_b_vault.amount, ctx.accounts.token_a_vault.amount)
+        };
+        let input_reserve = if sell_rift { rift_reserve } else { underlying_reserve };
+        let max_trade = input_reserve
+            .checked_mul(MAX_ARBITRAGE_TRADE_BPS)
+            .ok_or(ErrorCode::MathOverflow)?
+            .checked_div(10000)
+            .ok_or(ErrorCode::MathOverflow)?;
+        require!(amount_in <= max_trade, ErrorCode::ArbitrageTradeTooLarge);
+
+        // Output must clear both the oracle quote less slippage and the 1:1 backing of the input
+        let (source_mint, destination_mint) = if sell_rift {
+            (rift.rift_mint, rift.underlying_mint)
+        } else {
+            (rift.underlying_mint, rift.rift_mint)
+        };
+        let oracle_min_out = rift.oracle_min_amount_out(
+            source_mint,
+            destination_mint,
+            amount_in,
+            max_slippage_bps,
+        )?;
+        let minimum_amount_out = oracle_min_out.max(
+            amount_in.checked_add(1).ok_or(ErrorCode::MathOverflow)?
+        );
+
+        require!(!rift.reentrancy_guard, ErrorCode::ReentrancyDetected);
+        rift.reentrancy_guard = true;
+
+        let rift_key = rift.key();
+        let vault_bump = [ctx.bumps.vault_authority];
+        let vault_seeds: &[&[u8]] = &[b"vault_auth", rift_key.as_ref(), &vault_bump];
+        let mint_bump = [ctx.bumps.rift_mint_authority];
+        let mint_seeds: &[&[u8]] = &[b"rift_mint_auth", rift_key.as_ref(), &mint_bump];
+
+        // Selling RIFT: mint the input into the protocol's RIFT account first
+        if sell_rift {
+            token::mint_to(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    MintTo {
+                        mint: ctx.accounts.rift_mint.to_account_info(),
+                        to: ctx.accounts.arbitrage_rift_account.to_account_info(),
+                        authority: ctx.accounts.rift_mint_authority.to_account_info(),
+                    },
+                    &[mint_seeds],
+                ),
+                amount_in,
+            )?;
+        }
+
+        let vault_before = ctx.accounts.vault.amount;
+        let rift_account_before = ctx.accounts.arbitrage_rift_account.amount;
+
+        let (input_token_account, output_token_account) = if sell_rift {
+            (ctx.accounts.arbitrage_rift_account.to_account_info(), ctx.accounts.vault.to_account_info())
+        } else {
+            (ctx.accounts.vault.to_account_info(), ctx.accounts.arbitrage_rift_account.to_account_info())
+        };
+        let (token_a_mint, token_b_mint) = if rift_is_token_a {
+            (ctx.accounts.rift_mint.to_account_info(), ctx.accounts.underlying_mint.to_account_info())
+        } else {
+            (ctx.accounts.underlying_mint.to_account_info(), ctx.accounts.rift_mint.to_account_info())
+        };
+
+        let swap_accounts = SwapCtx {
+            pool_authority: ctx.accounts.pool_authority.to_account_info(),
+            pool: ctx.accounts.pool.to_account_info(),
+            input_token_account,
+            output_token_account,
+            token_a_vault: ctx.accounts.token_a_vault.to_account_info(),
+            token_b_vault: ctx.accounts.token_b_vault.to_account_info(),
+            token_a_mint,
+            token_b_mint,
+            payer: ctx.accounts.vault_authority.to_account_info(),
+            token_a_program: ctx.accounts.token_program.to_account_info(),
+            token_b_program: ctx.accounts.token_program.to_account_info(),
+            referral_token_account: None,
+            event_authority: ctx.accounts.event_authority.to_account_info(),
+            program: ctx.accounts.meteora_program.to_account_info(),
+        };
+        let vault_signers = &[vault_seeds];
+        let meteora_ctx = CpiContext::new_with_signer(
+            ctx.accounts.meteora_program.to_account_info(),
+            swap_accounts,
+            vault_signers,
+        );
+        cpi::swap(meteora_ctx, SwapParameters { amount_in, minimum_amount_out })?;
+
+        ctx.accounts.vault.reload()?;
+        ctx.accounts.arbitrage_rift_account.reload()?;
+
+        // Realized output from balance deltas, not the quote
+        let (amount_out, profit) = if sell_rift {
+            let underlying_out = ctx.accounts.vault.amount
+                .checked_sub(vault_before)
+                .ok_or(ErrorCode::MathOverflow)?;
+            // Minted RIFT keeps 1:1 backing in the vault
+            rift.total_rift_minted = rift.total_rift_minted
+                .checked_add(amount_in).ok_or(ErrorCode::MathOverflow)?;
+            rift.total_underlying_wrapped = rift.total_underlying_wrapped
+                .checked_add(amount_in).ok_or(ErrorCode::MathOverflow)?;
+            (underlying_out, underlying_out.checked_sub(amount_in).ok_or(ErrorCode::ArbitrageNotProfitable)?)
+        } else {
+            let rift_out = ctx.accounts.arbitrage_rift_account.amount
+                .checked_sub(rift_account_before)
+                .ok_or(ErrorCode::MathOverflow)?;
+            // Burning the bought RIFT releases its 1:1 backing, of which amount_in already paid for it
+            anchor_spl::token::burn(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    anchor_spl::token::Burn {
+                        mint: ctx.accounts.rift_mint.to_account_info(),
+                        from: ctx.accounts.arbitrage_rift_account.to_account_info(),
+                        authority: ctx.accounts.vault_authority.to_account_info(),
+                    },
+                    &[vault_seeds],
+                ),
+                rift_out,
+            )?;
+            rift.total_rift_minted = rift.total_rift_minted
+                .checked_sub(rift_out).ok_or(ErrorCode::MathOverflow)?;
+            rift.total_underlying_wrapped = rift.total_underlying_wrapped
+                .checked_sub(rift_out).ok_or(ErrorCode::MathOverflow)?;
+            (rift_out, rift_out.checked_sub(amount_in).ok_or(ErrorCode::ArbitrageNotProfitable)?)
+        };
+        require!(profit > 0, ErrorCode::ArbitrageNotProfitable);
+
+        // Move the spread out of backing into the fee vault
+        token::transfer(
+            CpiContext::new_with_signer(
+                ctx.accounts.token_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.vault.to_account_info(),
+                    to: ctx.accounts.fee_vault.to_account_info(),
+                    authority: ctx.accounts.vault_authority.to_account_info(),
+                },
+                &[vault_seeds],
+            ),
+            profit,
+        )?;
+        rift.accrued_underlying_fees = rift.accrued_underlying_fees
+            .checked_add(profit).ok_or(ErrorCode::MathOverflow)?;
+
+        rift.assert_supply_invariant(&mut ctx.accounts.rift_mint)?;
+
+        // Refresh backing_ratio from the oracles, then re-measure the deviation after the trade
+        rift.trigger_automatic_rebalance(rift_key, current_time)?;
+        let pool_price_after = parse_meteora_pool_price(&ctx.accounts.pool, &rift.rift_mint)?;
+        let deviation_after_bps = rift.compute_arbitrage_bps(pool_price_after)?;
+
+        rift.reentrancy_guard = false;
+
+        msg!("Arbitrage {}: {} in → {} out, {} profit to fee vault ({} → {} bps)",
+             if sell_rift { "sold RIFT" } else { "bought RIFT" },
+             amount_in, amount_out, profit, deviation_bps, deviation_after_bps);
+
+        emit!(ArbitrageCaptured {
+            rift: rift_key,
+            sold_rift: sell_rift,
+            amount_in,
+            amount_out,
+            profit,
+            pool_price_before,
+            pool_price_after,
+            oracle_price,
+            backing_ratio: rift.backing_ratio,
+            executor: signer,
+        });
+
+        Ok(())
+    }
+
+    /// Claim trading fees accrued on a protocol-owned Meteora position into the fee vault (permissionless)
+    /// The position NFT must be held by the ["protocol_position", rift] PDA. Underlying fees land in the fee vault;
+    /// RIFT fees are burned and their 1:1 backing moves from the vault to the fee vault.
+    /// Only runs while rift.protocol_fee_mode is Claim, so it never races compound_protocol_liquidity.
+    pub fn claim_meteora_fees(ctx: Context<ClaimMeteoraFees>) -> Result<()> {
+        let rift = &mut ctx.accounts.rift;
+        require!(
+            rift.protocol_fee_mode == ProtocolFeeMode::Claim,
+            ErrorCode::WrongProtocolFeeMode
+        );
+
+        // Pool accounts must match the live pool state
+        let pool_state = read_meteora_pool_state(&ctx.accounts.pool)?;
+        require!(
+            ctx.accounts.token_a_vault.key() == pool_state.token_a_vault
+                && ctx.accounts.token_b_vault.key() == pool_state.token_b_vault,
+            ErrorCode::InvalidPoolVault
+        );
+        let rift_is_token_a = pool_state.token_a_mint == rift.rift_mint;
+        require!(
+            rift_is_token_a || pool_state.token_b_mint == rift.rift_mint,
+            ErrorCode::InvalidPoolAccount
+        );
+
+        // Position PDA is derived from its NFT mint and must belong to this pool
+        check_pool_position(
+            &ctx.accounts.position,
+            &ctx.accounts.position_nft_mint.key(),
+            &ctx.accounts.pool.key(),
+        )?;
+
+        require!(!rift.reentrancy_guard, ErrorCode::ReentrancyDetected);
+        rift.reentrancy_guard = true;
+
+        let fee_vault_before = ctx.accounts.fee_vault.amount;
+        let rift_account_before = ctx.accounts.protocol_rift_account.amount;
+
+        let (token_a_account, token_b_account, token_a_mint, token_b_mint) = if rift_is_token_a {
+            (
+                ctx.accounts.protocol_rift_account.to_account_info(),
+                ctx.accounts.fee_vault.to_account_info(),
+                ctx.accounts.rift_mint.to_account_info(),
+                ctx.accounts.underlying_mint.to_account_info(),
+            )
+        } else {
+            (
+                ctx.accounts.fee_vault.to_account_info(),
+                ctx.accounts.protocol_rift_account.to_account_info(),
+                ctx.accounts.underlying_mint.to_account_info(),
+                ctx.accounts.rift_mint.to_account_info(),
+            )
+        };
+
+        let claim_accounts = ClaimPositionFeeCtx {
+            pool_authority: ctx.accounts.pool_authority.to_account_info(),
+            pool: ctx.accounts.pool.to_account_info(),
+            position: ctx.accounts.position.to_account_info(),
+            token_a_account,
+            token_b_account,
+            token_a_vault: ctx.accounts.token_a_vault.to_account_info(),
+            token_b_vault: ctx.accounts.token_b_vault.to_account_info(),
+            token_a_mint,
+            token_b_mint,
+            position_nft_account: ctx.accounts.position_nft_account.to_account_info(),
+            owner: ctx.accounts.position_owner.to_account_info(),
+            token_a_program: ctx.accounts.token_program.to_account_info(),
+            token_b_program: ctx.accounts.token_program.to_account_info(),
+            event_authority: ctx.accounts.event_authority.to_account_info(),
+            program: ctx.accounts.meteora_program.to_account_info(),
+        };
+
+        let rift_key = rift.key();
+        let bump = [ctx.bumps.vault_authority];
+        let vault_seeds: &[&[u8]] = &[b"vault_auth", rift_key.as_ref(), &bump];
+        let vault_signers = &[vault_seeds];
+        let position_bump = [ctx.bumps.position_owner];
+        let position_seeds: &[&[u8]] = &[b"protocol_position", rift_key.as_ref(), &position_bump];
+
+        cpi::claim_position_fee(CpiContext::new_with_signer(
+            ctx.accounts.meteora_program.to_account_info(),
+            claim_accounts,
+            &[position_seeds],
+        ))?;
+
+        ctx.accounts.fee_vault.reload()?;
+        ctx.accounts.protocol_rift_account.reload()?;
+
+        let underlying_fees = ctx.accounts.fee_vault.amount
+            .checked_sub(fee_vault_before)
+            .ok_or(ErrorCode::MathOverflow)?;
+        let rift_fees = ctx.accounts.protocol_rift_account.amount
+            .checked_sub(rift_account_before)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        // Redeem RIFT fees 1:1: burn them and move their backing into the fee vault
+        if rift_fees > 0 {
+            anchor_spl::token::burn(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    anchor_spl::token::Burn {
+                        mint: ctx.accounts.rift_mint.to_account_info(),
+                        from: ctx.accounts.protocol_rift_account.to_account_info(),
+                        authority: ctx.accounts.vault_authority.to_account_info(),
+                    },
+                    vault_signers,
+                ),
+                rift_fees,
+            )?;
+            token::transfer(
+                CpiContext::new_with_signer(
+                    ctx.accounts.token_program.to_account_info(),
+                    Transfer {
+                        from: ctx.accounts.vault.to_account_info(),
+                        to: ctx.accounts.fee_vault.to_account_info(),
+                        authority: ctx.accounts.vault_authority.to_account_info(),
+                    },
+                    vault_signers,
+                ),
+                rift_fees,
+            )?;
+            rift.total_rift_minted = rift.total_rift_minted
+                .checked_sub(rift_fees).ok_or(ErrorCode::MathOverflow)?;
+            rift.total_underlying_wrapped = rift.total_underlying_wrapped
+                .checked_sub(rift_fees).ok_or(ErrorCode::MathOverflow)?;
+            rift.total_burned = rift.total_burned
+                .checked_add(rift_fees).ok_or(ErrorCode::MathOverflow)?;
+            rift.assert_supply_invariant(&mut ctx.accounts.rift_mint)?;
+        }
+
+        let total_fees = underlying_fees
+            .checked_add(rift_fees)
+            .ok_or(ErrorCode::MathOverflow)?;
+        if total_fees > 0 {
+            rift.accrued_underlying_fees = rift.accrued_underlying_fees
+                .checked_add(total_fees).ok_or(ErrorCode::MathOverflow)?;
+            rift.total_fees_collected = rift.total_fees_collected
+                .checked_add(total_fees).ok_or(ErrorCode::MathOverflow)?;
+            rift.process_fee_immediately(total_fees)?;
+        }
+
+        rift.reentrancy_guard = false;
+
+        msg!("Claimed Meteora fees: {} underlying + {} RIFT redeemed → fee vault",
+             underlying_fees, rift_fees);
+
+        emit!(MeteoraFeesClaimed {
+            rift: rift_key,
+            pool: ctx.accounts.pool.key(),
+            position: ctx.accounts.position.key(),
+            underlying_fees,
+            rift_fees,
+            total_fees,
+        });
+
+        Ok(())
+    }
+
+    /// Claim fees on the protocol-owned Meteora position and add them back as liquidity (permissionless)
+    /// Fees are claimed into protocol-held accounts; the balanced part of their balances is re-added and
+    /// any remainder is kept for the next compound.
+    /// Only runs while rift.protocol_fee_mode is Compound, so it never races claim_meteora_fees.
+    pub fn compound_protocol_liquidity(ctx: Context<CompoundProtocolLiquidity>) -> Result<()> {
+        let rift = &mut ctx.accounts.rift;
+        require!(!rift.is_paused_at(Clock::get()?.unix_timestamp), ErrorCode::RiftPaused);
+        require!(
+            rift.protocol_fee_mode == ProtocolFeeMode::Compound,
+            ErrorCode::WrongProtocolFeeMode
+        );
+
+        // Pool accounts must match the live pool state and the recorded pool authority
+        let pool_state = read_meteora_pool_state(&ctx.accounts.pool)?;
+        require!(
+            ctx.accounts.token_a_vault.key() == pool_state.token_a_vault
+                && ctx.accounts.token_b_vault.key() == pool_state.token_b_vault,
+            ErrorCode::InvalidPoolVault
+        );
+        let rift_is_token_a = pool_state.token_a_mint == rift.rift_mint;
+        require!(
+            rift_is_token_a || pool_state.token_b_mint == rift.rift_mint,
+            ErrorCode::InvalidPoolAccount
+        );
+        if let Some(expected_authority) = rift.pool_authority {
+            require!(
+                ctx.accounts.pool_authority.key() == expected_authority,
+                ErrorCode::InvalidPoolAuthority
+            );
+        }
+
+        check_pool_position(
+            &ctx.accounts.position,
+            &ctx.accounts.position_nft_mint.key(),
+            &ctx.accounts.pool.key(),
+        )?;
+
+        require!(!rift.reentrancy_guard, ErrorCode::ReentrancyDetected);
+        rift.reentrancy_guard = true;
+
+        let underlying_before = ctx.accounts.protocol_underlying_account.amount;
+        let rift_before = ctx.accounts.protocol_rift_account.amount;
+
+        let (token_a_account, token_b_account, token_a_mint, token_b_mint) = if rift_is_token_a {
+            (
+                ctx.accounts.protocol_rift_account.to_account_info(),
+                ctx.accounts.protocol_underlying_account.to_account_info(),
+                ctx.accounts.rift_mint.to_account_info(),
+                ctx.accounts.underlying_mint.to_account_info(),
+            )
+        } else {
+            (
+                ctx.accounts.protocol_underlying_account.to_account_info(),
+                ctx.accounts.protocol_rift_account.to_account_info(),
+                ctx.accounts.underlying_mint.to_account_info(),
+                ctx.accounts.rift_mint.to_account_info(),
+            )
+        };
+
+        let rift_key = rift.key();
+        let bump = [ctx.bumps.position_owner];
+        let position_seeds: &[&[u8]] = &[b"protocol_position", rift_key.as_ref(), &bump];
+        let position_signers = &[position_seeds];
+
+        let claim_accounts = ClaimPositionFeeCtx {
+            pool_authority: ctx.accounts.pool_authority.to_account_info(),
+            pool: ctx.accounts.pool.to_account_info(),
+            position: ctx.accounts.position.to_account_info(),
+            token_a_account: token_a_account.clone(),
+            token_b_account: token_b_account.clone(),
+            token_a_vault: ctx.accounts.token_a_vault.to_account_info(),
+            token_b_vault: ctx.accounts.token_b_vault.to_account_info(),
+            token_a_mint: token_a_mint.clone(),
+            token_b_mint: token_b_mint.clone(),
+            position_nft_account: ctx.accounts.position_nft_account.to_account_info(),
+            owner: ctx.accounts.position_owner.to_account_info(),
+            token_a_program: ctx.accounts.token_program.to_account_info(),
+            token_b_program: ctx.accounts.token_program.to_account_info(),
+            event_authority: ctx.accounts.event_authority.to_account_info(),
+            program: ctx.accounts.meteora_program.to_account_info(),
+        };
+        cpi::claim_position_fee(CpiContext::new_with_signer(
+            ctx.accounts.meteora_program.to_account_info(),
+            claim_accounts,
+            position_signers,
+        ))?;
+
+        ctx.accounts.protocol_underlying_account.reload()?;
+        ctx.accounts.protocol_rift_account.reload()?;
+
+        let underlying_fees = ctx.accounts.protocol_underlying_account.amount
+            .checked_sub(underlying_before)
+            .ok_or(ErrorCode::MathOverflow)?;
+        let rift_fees = ctx.accounts.protocol_rift_account.amount
+            .checked_sub(rift_before)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        // Meteora uses liquidity = sqrt(amount_a * amount_b); only the balanced part is added
+        let underlying_available = ctx.accounts.protocol_underlying_account.amount;
+        let rift_available = ctx.accounts.protocol_rift_account.amount;
+        let deposit = underlying_available.min(rift_available) as u128;
+        let liquidity_delta = integer_sqrt(
+            deposit.checked_mul(deposit).ok_or(ErrorCode::MathOverflow)?
+        );
+        require!(liquidity_delta > 0, ErrorCode::NothingToCompound);
+
+        // Thresholds cap what Meteora may pull at the protocol-held balances
+        let (token_a_amount_threshold, token_b_amount_threshold) = if rift_is_token_a {
+            (rift_available, underlying_available)
+        } else {
+            (underlying_available, rift_available)
+        };
+
+        let add_liquidity_accounts = AddLiquidityCtx {
+            pool: ctx.accounts.pool.to_account_info(),
+            position: ctx.accounts.position.to_account_info(),
+            token_a_account,
+            token_b_account,
+            token_a_vault: ctx.accounts.token_a_vault.to_account_info(),
+            token_b_vault: ctx.accounts.token_b_vault.to_account_info(),
+            token_a_mint,
+            token_b_mint,
+            position_nft_account: ctx.accounts.position_nft_account.to_account_info(),
+            owner: ctx.accounts.position_owner.to_account_info(),
+            token_a_program: ctx.accounts.token_program.to_account_info(),
+            token_b_program: ctx.accounts.token_program.to_account_info(),
+            event_authority: ctx.accounts.event_authority.to_account_info(),
+            program: ctx.accounts.meteora_program.to_account_info(),
+        };
+        cpi::add_liquidity(
+            CpiContext::new_with_signer(
+                ctx.accounts.meteora_program.to_account_info(),
+                add_liquidity_accounts,
+                position_signers,
+            ),
+            AddLiquidityParameters {
+                liquidity_delta,
+                token_a_amount_threshold,
+                token_b_amount_threshold,
+            },
+        )?;
+
+        ctx.accounts.protocol_underlying_account.reload()?;
+        ctx.accounts.protocol_rift_account.reload()?;
+
+        let underlying_added = underlying_available
+            .checked_sub(ctx.accounts.protocol_underlying_account.amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+        let rift_added = rift_available
+            .checked_sub(ctx.accounts.protocol_rift_account.amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        rift.total_liquidity_underlying = rift.total_liquidity_underlying
+            .checked_add(underlying_added).ok_or(ErrorCode::MathOverflow)?;
+        rift.total_liquidity_rift = rift.total_liquidity_rift
+            .checked_add(rift_added).ok_or(ErrorCode::MathOverflow)?;
+
+        rift.reentrancy_guard = false;
+
+        msg!("Compounded protocol liquidity: {} underlying + {} RIFT (liquidity {})",
+             underlying_added, rift_added, liquidity_delta);
+
+        emit!(ProtocolLiquidityCompounded {
+            rift: rift_key,
+            pool: ctx.accounts.pool.key(),
+            position: ctx.accounts.position.key(),
+            underlying_fees,
+            rift_fees,
+            underlying_added,
+            rift_added,
+            liquidity_delta,
+        });
+
+        Ok(())
+    }
+
+    /// Set the wrap → unwrap cooldown (creator only, 0 = disabled)
+    pub fn set_wrap_cooldown(
+        ctx: Context<SetWrapCooldown>,
+        wrap_cooldown_seconds: i64,
+    ) -> Result<()> {
+        require!(
+            (0..=MAX_WRAP_COOLDOWN_SECONDS).contains(&wrap_cooldown_seconds),
+            ErrorCode::InvalidWrapCooldown
+        );
+
+        let rift = &mut ctx.accounts.rift;
+
+        rift.wrap_cooldown_seconds = wrap_cooldown_seconds;
+        rift.last_governance_update = Clock::get()?.unix_timestamp;
+
+        msg!("Wrap cooldown set to {} seconds", wrap_cooldown_seconds);
+
+        Ok(())
+    }
+
+    /// Set the minimum wrap amount (creator only)
+    /// Bounded below by DEFAULT_MIN_WRAP_AMOUNT and above by one whole underlying token
+    pub fn set_min_wrap_amount(
+        ctx: Context<SetMinWrapAmount>,
+        min_wrap_amount: u64,
+    ) -> Result<()> {
+        let one_token = 10u64
+            .checked_pow(u32::from(ctx.accounts.underlying_mint.decimals))
+            .ok_or(ErrorCode::MathOverflow)?;
+        require!(
+            min_wrap_amount >= DEFAULT_MIN_WRAP_AMOUNT
+                && min_wrap_amount <= one_token.max(DEFAULT_MIN_WRAP_AMOUNT),
+            ErrorCode::InvalidMinWrapAmount
+        );
+
+        let rift = &mut ctx.accounts.rift;
+
+        rift.min_wrap_amount = min_wrap_amount;
+        rift.last_governance_update = Clock::get()?.unix_timestamp;
+
+        msg!("Minimum wrap amount set to {}", min_wrap_amount);
+
+        Ok(())
+    }
+
+    /// Set the minimum LP stake duration before unstaking (creator only, 0 = disabled)
+    pub fn set_min_stake_duration(
+        ctx: Context<SetMinStakeDuration>,
+        min_stake_duration: i64,
+    ) -> Result<()> {
+        require!(
+            (0..=MAX_MIN_STAKE_DURATION).contains(&min_stake_duration),
+            ErrorCode::InvalidStakeDuration
+        );
+
+        let rift = &mut ctx.accounts.rift;
+
+        rift.min_stake_duration = min_stake_duration;
+        rift.last_governance_update = Clock::get()?.unix_timestamp;
+
+        msg!("Minimum stake duration set to {} seconds", min_stake_duration);
+
+        Ok(())
+    }
+
+    /// Set the fee-free bootstrap_mint allowance (only before the first public wrap)
+    pub fn set_bootstrap_cap(
+        ctx: Context<SetBootstrapCap>,
+        bootstrap_cap: u64,
+    ) -> Result<()> {
+        let rift = &mut ctx.accounts.rift;
+
+        require!(!rift.bootstrap_complete, ErrorCode::BootstrapComplete);
+        require!(bootstrap_cap >= rift.bootstrap_minted, ErrorCode::BootstrapCapExceeded);
+
+        rift.bootstrap_cap = bootstrap_cap;
+        rift.last_governance_update = Clock::get()?.unix_timestamp;
+
+        msg!("Bootstrap cap set to {}", bootstrap_cap);
+
+        Ok(())
+    }
+
+    /// Set the oracle confidence ceiling in bps of price (creator only)
+    pub fn set_max_confidence_bps(
+        ctx: Context<SetMaxConfidenceBps>,
+        max_confidence_bps: u16,
+    ) -> Result<()> {
+        require!(
+            max_confidence_bps > 0 && max_confidence_bps <= MAX_CONFIDENCE_BPS,
+            ErrorCode::InvalidMaxConfidence
+        );
+
+        let rift = &mut ctx.accounts.rift;
+
+        rift.max_confidence_bps = max_confidence_bps;
+        rift.last_governance_update = Clock::get()?.unix_timestamp;
+
+        msg!("Oracle confidence ceiling set to {} bps", max_confidence_bps);
+
+        Ok(())
+    }
+
+    /// Set how many distinct fresh oracle feeds a rebalance requires (1 = no quorum)
+    pub fn set_oracle_min_sources(
+        ctx: Context<SetOracleMinSources>,
+        oracle_min_sources: u8,
+    ) -> Result<()> {
+        require!(
+            (1..=MAX_ORACLE_SOURCES).contains(&oracle_min_sources),
+            ErrorCode::InvalidOracleMinSources
+        );
+
+        let rift = &mut ctx.accounts.rift;
+
+        rift.oracle_min_sources = oracle_min_sources;
+        rift.last_governance_update = Clock::get()?.unix_timestamp;
+
+        msg!("Oracle quorum set to {} sources", oracle_min_sources);
+
+        Ok(())
+    }
+
+    /// Set the RIFTS bounty paid to callers of trigger_rebalance (0 disables it)
+    pub fn set_keeper_reward(
+        ctx: Context<SetKeeperReward>,
+        keeper_reward: u64,
+    ) -> Result<()> {
+        let rift = &mut ctx.accounts.rift;
+
+        rift.keeper_reward = keeper_reward;
+        rift.last_governance_update = Clock::get()?.unix_timestamp;
+
+        msg!("Keeper reward set to {} RIFTS", keeper_reward);
+
+        Ok(())
+    }
+
+    /// Set the undercollateralization floor and whether breaching it auto-pauses the rift
+    pub fn set_undercollateralization_config(
+        ctx: Context<SetUndercollateralizationConfig>,
+        floor: u64,
+        pause_on_breach: bool,
+    ) -> Result<()> {
+        require!(
+            floor > 0 && floor <= 1_000_000,
+            ErrorCode::InvalidUndercollateralizationFloor
+        );
+
+        let rift = &mut ctx.accounts.rift;
+
+        rift.undercollateralization_floor = floor;
+        rift.pause_on_undercollateralization = pause_on_breach;
+        rift.last_governance_update = Clock::get()?.unix_timestamp;
+
+        msg!("Undercollateralization floor set to {} (auto-pause: {})", floor, pause_on_breach);
+
+        Ok(())
+    }
+
+}
+
+// SIMPLIFIED ACCOUNT STRUCTS TO REDUCE STACK USAGE
+
+#[derive(Accounts)]
+#[instruction(vanity_seed: [u8; 32], seed_len: u8, mint_bump: u8, burn_fee_bps: u16, partner_fee_bps: u16, partner_wallet: Option<Pubkey>, rift_name: [u8; 32], name_len: u8)]
+pub struct CreateRiftWithVanityPDA<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + std::mem::size_of::<Rift>(),
+        seeds = [b"rift", underlying_mint.key().as_ref(), creator.key().as_ref(), &vanity_seed[..seed_len as usize]],
+        bump,
+    )]
+    pub rift: Account<'info, Rift>,
+
+    pub underlying_mint: Account<'info, Mint>,
+
+    /// The PDA-derived mint account for vanity address
+    #[account(
+        init,
+        payer = creator,
+        mint::decimals = underlying_mint.decimals,
+        mint::authority = rift_mint_authority,
+        seeds = [b"rift_mint", creator.key().as_ref(), underlying_mint.key().as_ref(), &vanity_seed[..seed_len as usize]],
+        bump,
+    )]
+    pub rift_mint: Account<'info, Mint>,
+
+    /// CHECK: PDA vault for tokens
+    #[account(
+        seeds = [b"vault", rift.key().as_ref()],
+        bump
+    )]
+    pub vault: UncheckedAccount<'info>,
+
+    /// CHECK: PDA for rift mint authority
+    #[account(
+        seeds = [b"rift_mint_auth", rift.key().as_ref()],
+        bump
+    )]
+    pub rift_mint_authority: UncheckedAccount<'info>,
+
+    /// Fee vault holding wrap fees apart from the backing vault
+    #[account(
+        init,
+        payer = creator,
+        token::mint = underlying_mint,
+        token::authority = vault_authority,
+        seeds = [b"fee_vault", rift.key().as_ref()],
+        bump
+    )]
+    pub fee_vault: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Vault authority PDA - validated by seeds constraint
+    #[account(
+        seeds = [b"vault_auth", rift.key().as_ref()],
+        bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// Global rift registry, created by the first rift
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = RiftRegistry::LEN,
+        seeds = [b"rift_registry"],
+        bump
+    )]
+    pub registry: Box<Account<'info, RiftRegistry>>,
+
+    /// Registry page the new rift is appended to; a new page is created once the current one fills
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = RiftRegistryPage::LEN,
+        seeds = [b"rift_registry_page".as_ref(), registry.current_page().to_le_bytes().as_ref()],
+        bump
+    )]
+    pub registry_page: Box<Account<'info, RiftRegistryPage>>,
+
+    /// CHECK: Factory gate config PDA; creation stays permissionless while it is uninitialized
+    #[account(
+        seeds = [b"rift_factory_config"],
+        bump
+    )]
+    pub factory_config: UncheckedAccount<'info>,
+
+    /// CHECK: Creator's collection NFT token account, verified by check_collection_gate
+    pub gate_nft_account: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Metaplex metadata of the gate NFT, verified by check_collection_gate
+    pub gate_nft_metadata: Option<UncheckedAccount<'info>>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+#[instruction(burn_fee_bps: u16, partner_fee_bps: u16, partner_wallet: Option<Pubkey>, rift_name: [u8; 32], name_len: u8)]
+pub struct CreateRift<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+    
+    #[account(
+        init,
+        payer = creator,
+        space = 8 + std::mem::size_of::<Rift>() + 36, // Extra 36 bytes for String (4 bytes length + 32 bytes max string)
+        seeds = [b"rift", underlying_mint.key().as_ref(), creator.key().as_ref()],
+        constraint = underlying_mint.key() != Pubkey::default() && creator.key() != Pubkey::default() @ ErrorCode::InvalidSeedComponent,
+        bump,
+        constraint = creator.lamports() >= Rent::get()?.minimum_balance(8 + std::mem::size_of::<Rift>() + 36) @ ErrorCode::InsufficientRentExemption
+    )]
+    pub rift: Account<'info, Rift>,
+    
+    pub underlying_mint: Account<'info, Mint>,
+    
+    #[account(
+        init_if_needed,
+        payer = creator,
+        mint::decimals = underlying_mint.decimals,
+        mint::authority = rift_mint_authority,
+        seeds = [b"rift_mint", underlying_mint.key().as_ref(), creator.key().as_ref()],
+        constraint = underlying_mint.key() != Pubkey::default() && creator.key() != Pubkey::default() @ ErrorCode::InvalidSeedComponent,
+        bump,
+        constraint = creator.lamports() >= Rent::get()?.minimum_balance(Mint::LEN) @ ErrorCode::InsufficientRentExemption
+    )]
+    pub rift_mint: Account<'info, Mint>,
+    
+    /// CHECK: PDA for mint authority
+    #[account(
+        seeds = [b"rift_mint_auth", rift.key().as_ref()],
+        bump
+    )]
+    pub rift_mint_authority: UncheckedAccount<'info>,
+
+    /// Fee vault holding wrap fees apart from the backing vault
+    #[account(
+        init,
+        payer = creator,
+        token::mint = underlying_mint,
+        token::authority = vault_authority,
+        seeds = [b"fee_vault", rift.key().as_ref()],
+        bump
+    )]
+    pub fee_vault: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Vault authority PDA - validated by seeds constraint
+    #[account(
+        seeds = [b"vault_auth", rift.key().as_ref()],
+        bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// Global rift registry, created by the first rift
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = RiftRegistry::LEN,
+        seeds = [b"rift_registry"],
+        bump
+    )]
+    pub registry: Box<Account<'info, RiftRegistry>>,
+
+    /// Registry page the new rift is appended to; a new page is created once the current one fills
+    #[account(
+        init_if_needed,
+        payer = creator,
+        space = RiftRegistryPage::LEN,
+        seeds = [b"rift_registry_page".as_ref(), registry.current_page().to_le_bytes().as_ref()],
+        bump
+    )]
+    pub registry_page: Box<Account<'info, RiftRegistryPage>>,
+
+    /// CHECK: Factory gate config PDA; creation stays permissionless while it is uninitialized
+    #[account(
+        seeds = [b"rift_factory_config"],
+        bump
+    )]
+    pub factory_config: UncheckedAccount<'info>,
+
+    /// CHECK: Creator's collection NFT token account, verified by check_collection_gate
+    pub gate_nft_account: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Metaplex metadata of the gate NFT, verified by check_collection_gate
+    pub gate_nft_metadata: Option<UncheckedAccount<'info>>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeVault<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(mut)]
+    pub rift: Account<'info, Rift>,
+
+    /// Vault token account
+    #[account(
+        init,
+        payer = user,
+        token::mint = underlying_mint,
+        token::authority = rift_mint_authority,
+        seeds = [b"vault", rift.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    pub underlying_mint: Account<'info, Mint>,
+
+    /// CHECK: PDA
+    #[account(
+        seeds = [b"rift_mint_auth", rift.key().as_ref()],
+        bump
+    )]
+    pub rift_mint_authority: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateRift<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// CHECK: Pre-migration Rift that no longer deserializes as the current layout;
+    /// ownership is checked here, the discriminator and legacy layout in the handler
+    #[account(mut, owner = crate::ID @ ErrorCode::InvalidLegacyRift)]
+    pub rift: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeFeeVault<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(mut)]
+    pub rift: Account<'info, Rift>,
+
+    /// Fee vault token account, owned by the vault authority PDA that signs fee distribution
+    #[account(
+        init,
+        payer = user,
+        token::mint = underlying_mint,
+        token::authority = vault_authority,
+        seeds = [b"fee_vault", rift.key().as_ref()],
+        bump
+    )]
+    pub fee_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        constraint = underlying_mint.key() == rift.underlying_mint @ ErrorCode::InvalidMint
+    )]
+    pub underlying_mint: Account<'info, Mint>,
+
+    /// CHECK: Vault authority PDA - validated by seeds constraint
+    #[account(
+        seeds = [b"vault_auth", rift.key().as_ref()],
+        bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct WrapTokens<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(mut)]
+    pub rift: Account<'info, Rift>,
+
+    /// **SECURITY FIX #49**: Validate user's underlying token account
+    #[account(
+        mut,
+        constraint = user_underlying.mint == rift.underlying_mint @ ErrorCode::InvalidMint,
+        constraint = user_underlying.owner == user.key() @ ErrorCode::UnauthorizedTokenAccount
+    )]
+    pub user_underlying: Account<'info, TokenAccount>,
+
+    /// **SECURITY FIX #49**: Validate user's RIFT token account
+    #[account(
+        mut,
+        constraint = user_rift_tokens.mint == rift.rift_mint @ ErrorCode::InvalidMint,
+        constraint = user_rift_tokens.owner == user.key() @ ErrorCode::UnauthorizedTokenAccount
+    )]
+    pub user_rift_tokens: Account<'info, TokenAccount>,
+
+    /// **SECURITY FIX #49**: Validate vault matches rift state
+    #[account(
+        mut,
+        constraint = vault.key() == rift.vault @ ErrorCode::InvalidVault,
+        constraint = vault.mint == rift.underlying_mint @ ErrorCode::InvalidMint,
+        seeds = [b"vault", rift.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    /// Fee vault receiving the wrap fee (see initialize_fee_vault)
+    #[account(
+        mut,
+        constraint = fee_vault.key() == rift.fee_vault @ ErrorCode::InvalidVault,
+        seeds = [b"fee_vault", rift.key().as_ref()],
+        bump
+    )]
+    pub fee_vault: Account<'info, TokenAccount>,
+
+    /// **SECURITY FIX #49**: Validate rift mint matches rift state
+    #[account(
+        mut,
+        constraint = rift_mint.key() == rift.rift_mint @ ErrorCode::InvalidMint
+    )]
+    pub rift_mint: Account<'info, Mint>,
+
+    /// CHECK: PDA
+    #[account(
+        seeds = [b"rift_mint_auth", rift.key().as_ref()],
+        bump
+    )]
+    pub rift_mint_authority: UncheckedAccount<'info>,
+
+    /// **SECURITY FIX #49**: Add underlying mint for validation
+    pub underlying_mint: Account<'info, Mint>,
+
+    /// Optional LP staker account for the wrap/unwrap fee discount
+    #[account(
+        seeds = [b"staker", rift.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub staker_account: Option<Account<'info, StakerAccount>>,
+
+    /// Per-user last wrap time for the unwrap cooldown
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = WrapRecord::LEN,
+        seeds = [b"wrap_record", rift.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub wrap_record: Account<'info, WrapRecord>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct BootstrapMint<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = rift.creator == creator.key() @ ErrorCode::Unauthorized
+    )]
+    pub rift: Account<'info, Rift>,
+
+    #[account(
+        mut,
+        constraint = creator_underlying.mint == rift.underlying_mint @ ErrorCode::InvalidMint,
+        constraint = creator_underlying.owner == creator.key() @ ErrorCode::UnauthorizedTokenAccount
+    )]
+    pub creator_underlying: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = creator_rift_tokens.mint == rift.rift_mint @ ErrorCode::InvalidMint
+    )]
+    pub creator_rift_tokens: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = vault.key() == rift.vault @ ErrorCode::InvalidVault,
+        seeds = [b"vault", rift.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = rift_mint.key() == rift.rift_mint @ ErrorCode::InvalidMint
+    )]
+    pub rift_mint: Account<'info, Mint>,
+
+    /// CHECK: PDA
+    #[account(
+        seeds = [b"rift_mint_auth", rift.key().as_ref()],
+        bump
+    )]
+    pub rift_mint_authority: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+#[instruction(beneficiary: Pubkey)]
+pub struct WrapTokensFor<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(mut)]
+    pub rift: Account<'info, Rift>,
+
+    /// Payer's underlying token account (source of the deposit)
+    #[account(
+        mut,
+        constraint = payer_underlying.mint == rift.underlying_mint @ ErrorCode::InvalidMint,
+        constraint = payer_underlying.owner == payer.key() @ ErrorCode::UnauthorizedTokenAccount
+    )]
+    pub payer_underlying: Account<'info, TokenAccount>,
+
+    /// Beneficiary's RIFT token account (receives the minted RIFT)
+    #[account(
+        mut,
+        constraint = beneficiary_rift_tokens.mint == rift.rift_mint @ ErrorCode::InvalidMint,
+        constraint = beneficiary_rift_tokens.owner == beneficiary @ ErrorCode::UnauthorizedTokenAccount
+    )]
+    pub beneficiary_rift_tokens: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = vault.key() == rift.vault @ ErrorCode::InvalidVault,
+        constraint = vault.mint == rift.underlying_mint @ ErrorCode::InvalidMint,
+        seeds = [b"vault", rift.key().as_ref()],
+        bump
+    )]
+    pub vault: Account<'info, TokenAccount>,
+
+    /// Fee vault receiving the wrap fee (see initialize_fee_vault)
+    #[account(
+        mut,
+        constraint = fee_vault.key() == rift.fee_vault @ ErrorCode::InvalidVault,
+        seeds = [b"fee_vault", rift.key().as_ref()],
+        bump
+    )]
+    pub fee_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = rift_mint.key() == rift.rift_mint @ ErrorCode::InvalidMint
+    )]
+    pub rift_mint: Account<'info, Mint>,
+
+    /// CHECK: PDA
+    #[account(
+        seeds = [b"rift_mint_auth", rift.key().as_ref()],
+        bump
+    )]
+    pub rift_mint_authority: UncheckedAccount<'info>,
+
+    /// Per-user last wrap time for the unwrap cooldown
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = WrapRecord::LEN,
+        seeds = [b"wrap_record", rift.key().as_ref(), beneficiary.as_ref()],
+        bump
+    )]
+    pub wrap_record: Account<'info, WrapRecord>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct SetPoolAddress<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(mut)]
+    pub rift: Account<'info, Rift>,
+
+    /// Governance-approved Meteora configs
+    #[account(
+        seeds = [b"meteora_configs"],
+        bump = meteora_config_allowlist.bump
+    )]
+    pub meteora_config_allowlist: Account<'info, MeteoraConfigAllowlist>,
+
+    /// Meteora pool being bound
+    /// CHECK: Must equal pool_address; ownership, mints, vaults and config PDA checked in the handler
+    pub pool: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct UnsetPoolAddress<'info> {
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = rift.creator == creator.key() @ ErrorCode::Unauthorized
+    )]
+    pub rift: Account<'info, Rift>,
+}
+
+#[derive(Accounts)]
+pub struct WrapAndAddLiquidity<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(mut)]
+    pub rift: Account<'info, Rift>,
+
+    #[account(mut)]
+    pub user_underlying: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub user_rift_tokens: Account<'info, TokenAccount>,
+
+    /// Fee vault receiving the wrap fee (see initialize_fee_vault)
+    #[account(
+        mut,
+        constraint = fee_vault.key() == rift.fee_vault @ ErrorCode::InvalidVault,
+        seeds = [b"fee_vault", rift.key().as_ref()],
+        bump
+    )]
+    pub fee_vault: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub rift_mint: Account<'info, Mint>,
+
+    /// CHECK: PDA
+    #[account(
+        seeds = [b"rift_mint_auth", rift.key().as_ref()],
+        bump
+    )]
+    pub rift_mint_authority: UncheckedAccount<'info>,
+
+    pub underlying_mint: Account<'info, Mint>,
+
+    /// CHECK: Validated against rift.liquidity_pool
+    #[account(mut)]
+    pub pool: UncheckedAccount<'info>,
+
+    /// **PER-USER POSITION**: User's own position NFT mint (created by user)
+    #[account(mut)]
+    pub user_position_nft_mint: Signer<'info>,
+
+    /// **PER-USER POSITION**: User's position account (derived from their NFT)
+    /// CHECK: Meteora position PDA derived from user's NFT mint
+    #[account(mut)]
+    pub user_position: UncheckedAccount<'info>,
+
+    /// **PER-USER POSITION**: User's position NFT token account
+    /// CHECK: Must be owned by user and hold user_position_nft_mint
+    #[account(
+        mut,
+        constraint = position_nft_held_by(
+            &user_position_nft_account,
+            &user_position_nft_mint.key(),
+            &user.key()
+        ) @ ErrorCode::UnauthorizedPosition
+    )]
+    pub user_position_nft_account: UncheckedAccount<'info>,
+
+    /// CHECK: Meteora pool authority
+    pub pool_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Meteora token vaults
+    #[account(mut)]
+    pub token_a_vault: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub token_b_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Meteora event authority
+    pub event_authority: UncheckedAccount<'info>,
+
+    /// **SECURITY FIX #51**: Validate Meteora program ID against hardcoded constant
+    /// This prevents attackers from passing fake Meteora programs to steal funds
+    #[account(
+        constraint = meteora_program.key() == METEORA_DAMM_V2_PROGRAM_ID @ ErrorCode::InvalidProgramId,
+        constraint = meteora_program.executable @ ErrorCode::InvalidProgramId
+    )]
+    pub meteora_program: UncheckedAccount<'info>,
+
+    /// Per-user last wrap time for the unwrap cooldown
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = WrapRecord::LEN,
+        seeds = [b"wrap_record", rift.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub wrap_record: Account<'info, WrapRecord>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveLiquidityAndUnwrap<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(mut)]
+    pub rift: Account<'info, Rift>,
+
+    #[account(mut)]
+    pub user_underlying: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub user_rift_tokens: Account<'info, TokenAccount>,
+
+    #[account(mut)]
+    pub rift_mint: Account<'info, Mint>,
+
+    pub underlying_mint: Account<'info, Mint>,
+
+    /// CHECK: Validated against rift.liquidity_pool
+    #[account(mut)]
+    pub pool: UncheckedAccount<'info>,
+
+    /// **PER-USER POSITION**: User's own position NFT mint
+    /// CHECK: User's position NFT mint that they created when adding liquidity
+    pub user_position_nft_mint: UncheckedAccount<'info>,
+
+    /// **PER-USER POSITION**: User's position account (derived from their NFT)
+    /// CHECK: Meteora position PDA derived from user's NFT mint
+    #[account(mut)]
+    pub user_position: UncheckedAccount<'info>,
+
+    /// **PER-USER POSITION**: User's position NFT token account
+    /// CHECK: Must be owned by user and hold user_position_nft_mint
+    #[account(
+        mut,
+        constraint = position_nft_held_by(
+            &user_position_nft_account,
+            &user_position_nft_mint.key(),
+            &user.key()
+        ) @ ErrorCode::UnauthorizedPosition
+    )]
+    pub user_position_nft_account: UncheckedAccount<'info>,
+
+    /// CHECK: Meteora pool authority
+    pub pool_authority: UncheckedAccount<'info>,
+
+    /// CHECK: Meteora token vaults
+    #[account(mut)]
+    pub token_a_vault: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub token_b_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Meteora event authority
+    pub event_authority: UncheckedAccount<'info>,
+
+    /// **SECURITY FIX #51**: Validate Meteora program ID against hardcoded constant
+    /// This prevents attackers from passing fake Meteora programs to steal funds
+    #[account(
+        constraint = meteora_program.key() == METEORA_DAMM_V2_PROGRAM_ID @ ErrorCode::InvalidProgramId,
+        constraint = meteora_program.executable @ ErrorCode::InvalidProgramId
+    )]
+    pub meteora_program: UncheckedAccount<'info>,
+
+    /// CHECK: Per-user wrap record PDA; may be uninitialized if the user never wrapped
+    #[account(
+        seeds = [b"wrap_record", rift.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub wrap_record: UncheckedAccount<'info>,
+
+    /// Incinerator's RIFT account, required when rift.burn_mode is SendToIncinerator
+    #[account(
+        mut,
+        constraint = incinerator_rift_tokens.mint == rift.rift_mint @ ErrorCode::InvalidMint
+    )]
+    pub incinerator_rift_tokens: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct CreateMeteoraPool<'info> {
+    /// User creating the pool (maps to creator in Meteora)
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(mut)]
+    pub rift: Account<'info, Rift>,
+
+    /// Position NFT mint (required by Meteora)
+    #[account(mut)]
+    /// CHECK: This will be initialized by Meteora program
+    pub position_nft_mint: UncheckedAccount<'info>,
+
+    /// Position NFT account (required by Meteora)
+    #[account(mut)]
+    /// CHECK: This will be initialized by Meteora program
+    pub position_nft_account: UncheckedAccount<'info>,
+
+    /// Payer for the transaction (same as user in our case)
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// Meteora config account (maps to config in Meteora)
+    /// CHECK: Must equal rift.meteora_config_key (or the default), be Meteora-owned and allowlisted
+    pub config: UncheckedAccount<'info>,
+
+    /// Governance-approved Meteora configs
+    #[account(
+        seeds = [b"meteora_configs"],
+        bump = meteora_config_allowlist.bump
+    )]
+    pub meteora_config_allowlist: Account<'info, MeteoraConfigAllowlist>,
+
+    /// Pool authority (PDA)
+    /// CHECK: This will be a PDA derived by Meteora
+    pub pool_authority: UncheckedAccount<'info>,
+
+    /// The official Meteora pool account to be created
+    #[account(mut)]
+    /// CHECK: This will be initialized by Meteora program
+    pub pool: UncheckedAccount<'info>,
+
+    /// Position account for liquidity position
+    #[account(mut)]
+    /// CHECK: This will be initialized by Meteora program
+    pub position: UncheckedAccount<'info>,
+
+    /// Token A mint (underlying token)
+    pub token_a_mint: Account<'info, Mint>,
+
+    /// Token B mint (rift token)
+    #[account(mut)]
+    pub token_b_mint: Account<'info, Mint>,
+
+    /// Token A vault (underlying token vault)
+    #[account(mut)]
+    /// CHECK: This will be initialized by Meteora program
+    pub token_a_vault: UncheckedAccount<'info>,
+
+    /// Token B vault (rift token vault)
+    #[account(mut)]
+    /// CHECK: This will be initialized by Meteora program
+    pub token_b_vault: UncheckedAccount<'info>,
+
+    /// Payer's token A account
+    #[account(mut)]
+    pub payer_token_a: Account<'info, TokenAccount>,
+
+    /// Payer's token B account (rift tokens)
+    #[account(mut)]
+    pub payer_token_b: Account<'info, TokenAccount>,
+
+    /// CHECK: PDA for rift mint authority
+    #[account(
+        seeds = [b"rift_mint_auth", rift.key().as_ref()],
+        bump
+    )]
+    pub rift_mint_authority: UncheckedAccount<'info>,
+
+    /// Token program for token A
+    pub token_a_program: Program<'info, Token>,
+
+    /// Token program for token B
+    pub token_b_program: Program<'info, Token>,
+
+    /// Token 2022 program - Meteora expects the actual TOKEN_2022_PROGRAM_ID
+    /// CHECK: We don't validate this to allow Meteora to use TOKEN_2022_PROGRAM_ID
+    pub token_2022_program: UncheckedAccount<'info>,
+
+    /// The official Meteora DAMM v2 program
+    /// CHECK: This is the official Meteora program ID
+    #[account(
+        constraint = meteora_program.key() == METEORA_DAMM_V2_PROGRAM_ID @ ErrorCode::InvalidProgramId,
+        constraint = meteora_program.executable @ ErrorCode::InvalidProgramId
+    )]
+    pub meteora_program: UncheckedAccount<'info>,
+
+    /// Event authority for Meteora events
+    /// CHECK: PDA derived with ["__event_authority"] seeds from Meteora program
+    pub event_authority: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+
+/// **NEW ARCHITECTURE**: UnwrapTokens now removes liquidity from Meteora instead of vault
+#[derive(Accounts)]
+pub struct UnwrapTokens<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(mut)]
+    pub rift: Account<'info, Rift>,
+
+    /// **SECURITY FIX #49**: Validate user's underlying token account
+    #[account(
+        mut,
+        constraint = user_underlying.mint == rift.underlying_mint @ ErrorCode::InvalidMint,
+        constraint = user_underlying.owner == user.key() @ ErrorCode::UnauthorizedTokenAccount
+    )]
+    pub user_underlying: Account<'info, TokenAccount>,
 
     /// **SECURITY FIX #49**: Validate user's RIFT token account
     #[account(
         mut,
-        constraint = user_rift_tokens.mint == rift.rift_mint @ ErrorCode::InvalidMint,
-        constraint = user_rift_tokens.owner == user.key() @ ErrorCode::UnauthorizedTokenAccount
+        constraint = user_rift_tokens.mint == rift.rift_mint @ ErrorCode::InvalidMint,
+        constraint = user_rift_tokens.owner == user.key() @ ErrorCode::UnauthorizedTokenAccount
+    )]
+    pub user_rift_tokens: Account<'info, TokenAccount>,
+
+    /// **METEORA INTEGRATION**: Meteora pool account
+    #[account(mut)]
+    /// CHECK: Validated against rift.liquidity_pool
+    pub pool: UncheckedAccount<'info>,
+
+    /// **PER-USER POSITION**: User's own position NFT mint
+    pub user_position_nft_mint: UncheckedAccount<'info>,
+
+    /// **PER-USER POSITION**: User's position account (derived from their NFT)
+    #[account(mut)]
+    pub user_position: UncheckedAccount<'info>,
+
+    /// **PER-USER POSITION**: User's position NFT token account
+    /// CHECK: Must be owned by user and hold user_position_nft_mint
+    #[account(
+        mut,
+        constraint = position_nft_held_by(
+            &user_position_nft_account,
+            &user_position_nft_mint.key(),
+            &user.key()
+        ) @ ErrorCode::UnauthorizedPosition
+    )]
+    pub user_position_nft_account: UncheckedAccount<'info>,
+
+    /// **METEORA INTEGRATION**: Pool authority PDA
+    /// CHECK: Meteora-derived PDA
+    pub pool_authority: UncheckedAccount<'info>,
+
+    /// **METEORA INTEGRATION**: Pool's token A vault (underlying)
+    #[account(mut)]
+    /// CHECK: Meteora pool vault
+    pub token_a_vault: UncheckedAccount<'info>,
+
+    /// **METEORA INTEGRATION**: Pool's token B vault (RIFT)
+    #[account(mut)]
+    /// CHECK: Meteora pool vault
+    pub token_b_vault: UncheckedAccount<'info>,
+
+    /// **SECURITY FIX #49**: Validate rift mint matches rift state
+    #[account(
+        mut,
+        constraint = rift_mint.key() == rift.rift_mint @ ErrorCode::InvalidMint
+    )]
+    pub rift_mint: Account<'info, Mint>,
+
+    /// **SECURITY FIX #49**: Validate underlying mint matches rift state
+    #[account(
+        constraint = underlying_mint.key() == rift.underlying_mint @ ErrorCode::InvalidMint
+    )]
+    pub underlying_mint: Account<'info, Mint>,
+
+    /// RIFT mint authority PDA
+    /// CHECK: PDA
+    #[account(
+        seeds = [b"rift_mint_auth", rift.key().as_ref()],
+        bump
+    )]
+    pub rift_mint_authority: UncheckedAccount<'info>,
+
+    /// **METEORA INTEGRATION**: Meteora program
+    /// CHECK: Validated against METEORA_DAMM_V2_PROGRAM_ID
+    #[account(
+        constraint = meteora_program.key() == METEORA_DAMM_V2_PROGRAM_ID @ ErrorCode::InvalidProgramId,
+        constraint = meteora_program.executable @ ErrorCode::InvalidProgramId
+    )]
+    pub meteora_program: UncheckedAccount<'info>,
+
+    /// **METEORA INTEGRATION**: Event authority for Meteora events
+    /// CHECK: PDA derived with ["__event_authority"] seeds from Meteora program
+    pub event_authority: UncheckedAccount<'info>,
+
+    /// Optional LP staker account for the wrap/unwrap fee discount
+    #[account(
+        seeds = [b"staker", rift.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub staker_account: Option<Account<'info, StakerAccount>>,
+
+    /// CHECK: Per-user wrap record PDA; may be uninitialized if the user never wrapped
+    #[account(
+        seeds = [b"wrap_record", rift.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub wrap_record: UncheckedAccount<'info>,
+
+    /// Incinerator's RIFT account, required when rift.burn_mode is SendToIncinerator
+    #[account(
+        mut,
+        constraint = incinerator_rift_tokens.mint == rift.rift_mint @ ErrorCode::InvalidMint
+    )]
+    pub incinerator_rift_tokens: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+
+#[derive(Accounts)]
+pub struct AdminFixVaultConflict<'info> {
+    #[account(mut)]
+    pub program_authority: Signer<'info>,
+
+    /// Protocol config holding the current admin
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(mut)]
+    pub rift: Account<'info, Rift>,
+
+    /// CHECK: Vault PDA that may have wrong owner
+    #[account(
+        mut,
+        seeds = [b"vault", rift.key().as_ref()],
+        bump
+    )]
+    pub vault: UncheckedAccount<'info>,
+
+    /// CHECK: Expected vault authority PDA
+    #[account(
+        seeds = [b"vault_auth", rift.key().as_ref()],
+        bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializePool<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(mut)]
+    pub rift: Account<'info, Rift>,
+
+    #[account(mut)]
+    pub rift_mint: Account<'info, Mint>,
+
+    /// CHECK: PDA
+    #[account(
+        seeds = [b"rift_mint_auth", rift.key().as_ref()],
+        bump
+    )]
+    pub rift_mint_authority: UncheckedAccount<'info>,
+
+    /// Pool underlying token account
+    #[account(
+        init_if_needed,
+        payer = user,
+        token::mint = underlying_mint,
+        token::authority = pool_authority,
+        seeds = [b"pool_underlying", rift.key().as_ref()],
+        bump
+    )]
+    pub pool_underlying: Account<'info, TokenAccount>,
+
+    /// Pool rift token account
+    #[account(
+        init_if_needed,
+        payer = user,
+        token::mint = rift_mint,
+        token::authority = pool_authority,
+        seeds = [b"pool_rift", rift.key().as_ref()],
+        bump
+    )]
+    pub pool_rift: Account<'info, TokenAccount>,
+
+    /// CHECK: Pool authority PDA
+    #[account(
+        seeds = [b"pool_auth", rift.key().as_ref()],
+        bump
+    )]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    /// The rift's LP mint; the only token stake_lp_tokens accepts
+    #[account(
+        init_if_needed,
+        payer = user,
+        mint::decimals = rift_mint.decimals,
+        mint::authority = pool_authority,
+        seeds = [b"lp_mint", rift.key().as_ref()],
+        bump
+    )]
+    pub lp_mint: Box<Account<'info, Mint>>,
+
+    /// Creator's LP token account receiving the initial LP tokens
+    #[account(
+        init_if_needed,
+        payer = user,
+        token::mint = lp_mint,
+        token::authority = user,
+        seeds = [b"user_lp_tokens", rift.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub user_lp_tokens: Box<Account<'info, TokenAccount>>,
+
+    /// Creator's underlying token account funding the pool's underlying side
+    #[account(
+        mut,
+        constraint = user_underlying.mint == rift.underlying_mint @ ErrorCode::InvalidMint,
+        constraint = user_underlying.owner == user.key() @ ErrorCode::Unauthorized
+    )]
+    pub user_underlying: Box<Account<'info, TokenAccount>>,
+
+    #[account(constraint = underlying_mint.key() == rift.underlying_mint @ ErrorCode::InvalidMint)]
+    pub underlying_mint: Account<'info, Mint>,
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// **SECURITY FIX #50**: Account struct for updating Pyth oracle
+#[derive(Accounts)]
+pub struct UpdatePythOracle<'info> {
+    #[account(mut)]
+    pub rift: Account<'info, Rift>,
+
+    /// **SECURITY FIX #50**: Authority authorized to update oracle prices (creator or oracle pusher)
+    pub oracle_authority: Signer<'info>,
+
+    /// **SECURITY FIX #50**: Pyth price account - validated against rift.pyth_price_account
+    /// CHECK: Validated in instruction handler against stored pubkey and Pyth program ownership
+    pub pyth_price_account: UncheckedAccount<'info>,
+}
+
+/// **SECURITY FIX #50**: Account struct for updating Switchboard oracle
+#[derive(Accounts)]
+pub struct UpdateSwitchboardOracle<'info> {
+    #[account(mut)]
+    pub rift: Account<'info, Rift>,
+
+    /// **SECURITY FIX #50**: Authority authorized to update oracle prices (creator or oracle pusher)
+    pub oracle_authority: Signer<'info>,
+
+    /// **SECURITY FIX #50**: Switchboard aggregator feed - validated against rift.switchboard_feed_account
+    /// CHECK: Validated in instruction handler against stored pubkey and Switchboard program ownership
+    pub switchboard_feed: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct SetOraclePushers<'info> {
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = rift.creator == creator.key() @ ErrorCode::Unauthorized
+    )]
+    pub rift: Account<'info, Rift>,
+}
+
+/// Account struct for applying one oracle feed to many rifts (rifts passed as remaining_accounts)
+#[derive(Accounts)]
+pub struct UpdateOracleMulti<'info> {
+    /// Authority authorized to update oracle prices for every rift in the batch
+    pub oracle_authority: Signer<'info>,
+
+    /// Shared Pyth price account or Switchboard feed
+    /// CHECK: Validated against each rift's stored oracle account and by ownership in the parser
+    pub oracle_account: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct TriggerRebalance<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    #[account(mut)]
+    pub rift: Account<'info, Rift>,
+
+    /// Optional Meteora pool used to compute the live arbitrage opportunity
+    /// CHECK: Validated against rift.liquidity_pool and Meteora program ownership
+    pub pool: Option<UncheckedAccount<'info>>,
+
+    /// Protocol config naming the canonical governance, required for the keeper reward
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Option<Account<'info, ProtocolConfig>>,
+
+    /// Canonical governance; its rifts_mint is the only mint the keeper reward is paid in
+    pub governance: Option<Account<'info, governance::Governance>>,
+
+    /// Reward budget vault the keeper reward is paid from, required with the accounts below
+    #[account(
+        mut,
+        seeds = [b"reward_budget_vault", rift.key().as_ref()],
+        bump
+    )]
+    pub reward_budget_vault: Option<Account<'info, TokenAccount>>,
+
+    /// Keeper's RIFTS token account receiving the reward
+    #[account(mut)]
+    pub keeper_rifts_tokens: Option<Account<'info, TokenAccount>>,
+
+    /// CHECK: PDA owning the reward budget vault
+    #[account(
+        seeds = [b"rift_mint_auth", rift.key().as_ref()],
+        bump
+    )]
+    pub rifts_mint_authority: Option<UncheckedAccount<'info>>,
+
+    pub token_program: Option<Program<'info, Token>>,
+}
+
+
+/// Optimized fee distribution context - essential accounts only
+#[derive(Accounts)]
+pub struct ProcessFeeDistribution<'info> {
+    #[account(mut)]
+    pub fee_payer: Signer<'info>,
+
+    #[account(mut)]
+    pub rift: Account<'info, Rift>,
+
+    /// Fee vault holding accrued wrap fees (never the backing vault)
+    #[account(
+        mut,
+        constraint = fee_vault.key() == rift.fee_vault @ ErrorCode::InvalidVault,
+        seeds = [b"fee_vault", rift.key().as_ref()],
+        bump
+    )]
+    pub fee_vault: Account<'info, TokenAccount>,
+
+    /// CHECK: Vault authority PDA - validated by seeds constraint
+    #[account(
+        seeds = [b"vault_auth", rift.key().as_ref()],
+        bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// Treasury account for fee collection; must be the creator-configured rift.treasury
+    #[account(
+        mut,
+        constraint = rift.treasury == Some(treasury.key()) @ ErrorCode::InvalidTreasury
+    )]
+    pub treasury: Account<'info, TokenAccount>,
+
+    /// Fee collector vault (optional - only if fee_collector_amount > 0)
+    #[account(mut)]
+    pub fee_collector_vault: Option<Account<'info, TokenAccount>>,
+
+    /// Partner vault (optional - only if partner fees configured)
+    /// **SECURITY FIX**: Validate partner vault belongs to configured partner
+    pub partner_vault: Option<Account<'info, TokenAccount>>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct StakeLPTokens<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    #[account(mut)]
+    pub rift: Account<'info, Rift>,
+    
+    #[account(
+        init_if_needed,
+        payer = user,
+        space = 8 + std::mem::size_of::<StakerAccount>(),
+        seeds = [b"staker", rift.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub staker_account: Account<'info, StakerAccount>,
+
+    /// Protocol config naming the canonical governance
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// Governance state; frozen/paused time is excluded from reward accrual
+    #[account(
+        constraint = governance.key() == protocol_config.governance @ ErrorCode::InvalidGovernance
+    )]
+    pub governance: Account<'info, governance::Governance>,
+    
+    #[account(
+        mut,
+        constraint = rift.lp_token_mint == Some(user_lp_tokens.mint) @ ErrorCode::InvalidLpMint
+    )]
+    pub user_lp_tokens: Account<'info, TokenAccount>,
+    
+    /// Staking vault; must be held by the ["staking_vault", rift] PDA and hold the staked LP mint
+    #[account(
+        mut,
+        constraint = is_staking_vault(
+            &staking_vault.owner,
+            &staking_vault.mint,
+            &rift.key(),
+            &user_lp_tokens.mint
+        ) @ ErrorCode::InvalidVault
+    )]
+    pub staking_vault: Account<'info, TokenAccount>,
+    
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+/// Account struct for claiming staking rewards across many rifts (rift groups passed as remaining_accounts)
+#[derive(Accounts)]
+pub struct ClaimStakingRewardsBatch<'info> {
+    pub user: Signer<'info>,
+
+    /// Protocol config naming the canonical governance
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// Governance state; frozen/paused time is excluded from reward accrual
+    #[account(
+        constraint = governance.key() == protocol_config.governance @ ErrorCode::InvalidGovernance
+    )]
+    pub governance: Account<'info, governance::Governance>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimStakingRewards<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    #[account(mut)]
+    pub rift: Account<'info, Rift>,
+    
+    #[account(
+        mut,
+        seeds = [b"staker", rift.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub staker_account: Account<'info, StakerAccount>,
+
+    /// Protocol config naming the canonical governance
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// Governance state; frozen/paused time is excluded from reward accrual
+    #[account(
+        constraint = governance.key() == protocol_config.governance @ ErrorCode::InvalidGovernance
+    )]
+    pub governance: Account<'info, governance::Governance>,
+    
+    /// RIFTS token mint for rewards; the governance RIFTS mint, never the rift's own token
+    #[account(
+        constraint = rifts_mint.key() == governance.rifts_mint @ ErrorCode::InvalidMint,
+        constraint = rifts_mint.key() != rift.rift_mint @ ErrorCode::InvalidMint
+    )]
+    pub rifts_mint: Account<'info, Mint>,
+
+    /// Vault holding the funded reward budget that rewards are paid from
+    #[account(
+        mut,
+        seeds = [b"reward_budget_vault", rift.key().as_ref()],
+        bump,
+        constraint = reward_budget_vault.mint == rifts_mint.key() @ ErrorCode::InvalidMint
+    )]
+    pub reward_budget_vault: Account<'info, TokenAccount>,
+    
+    /// User's RIFTS token account
+    #[account(mut)]
+    pub user_rifts_tokens: Account<'info, TokenAccount>,
+    
+    /// RIFTS mint authority
+    /// CHECK: PDA owning the reward budget vault
+    #[account(
+        seeds = [b"rift_mint_auth", rift.key().as_ref()],
+        bump
+    )]
+    pub rifts_mint_authority: UncheckedAccount<'info>,
+
+    /// Vesting state, required when rift.reward_vest_period > 0
+    #[account(
+        mut,
+        seeds = [b"reward_vesting", rift.key().as_ref(), user.key().as_ref()],
+        bump = reward_vesting.bump
+    )]
+    pub reward_vesting: Option<Account<'info, RewardVesting>>,
+
+    /// Vesting escrow, required when rift.reward_vest_period > 0
+    #[account(
+        mut,
+        seeds = [b"vesting_escrow", rift.key().as_ref(), user.key().as_ref()],
+        bump,
+        constraint = vesting_escrow.mint == rifts_mint.key() @ ErrorCode::InvalidMint
+    )]
+    pub vesting_escrow: Option<Account<'info, TokenAccount>>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct CompoundRewards<'info> {
+    pub user: Signer<'info>,
+
+    #[account(mut)]
+    pub rift: Account<'info, Rift>,
+
+    #[account(
+        mut,
+        seeds = [b"staker", rift.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub staker_account: Account<'info, StakerAccount>,
+
+    /// Protocol config naming the canonical governance
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// Governance state; frozen/paused time is excluded from reward accrual
+    #[account(
+        constraint = governance.key() == protocol_config.governance @ ErrorCode::InvalidGovernance
+    )]
+    pub governance: Account<'info, governance::Governance>,
+
+    /// RIFTS token mint for rewards (never the rift's own wrapped mint)
+    #[account(
+        constraint = rifts_mint.key() == governance.rifts_mint @ ErrorCode::InvalidMint,
+        constraint = rifts_mint.key() != rift.rift_mint @ ErrorCode::InvalidMint
+    )]
+    pub rifts_mint: Account<'info, Mint>,
+
+    /// Vault holding the funded reward budget that rewards are paid from
+    #[account(
+        mut,
+        seeds = [b"reward_budget_vault", rift.key().as_ref()],
+        bump,
+        constraint = reward_budget_vault.mint == rifts_mint.key() @ ErrorCode::InvalidMint
+    )]
+    pub reward_budget_vault: Account<'info, TokenAccount>,
+
+    /// Staking vault receiving the compounded rewards; must be the rift's canonical LP staking vault
+    #[account(
+        mut,
+        constraint = rift.lp_token_mint.map_or(false, |lp_mint| is_staking_vault(
+            &staking_vault.owner,
+            &staking_vault.mint,
+            &rift.key(),
+            &lp_mint
+        )) @ ErrorCode::InvalidVault
+    )]
+    pub staking_vault: Account<'info, TokenAccount>,
+
+    /// CHECK: PDA for staking vault authority
+    #[account(
+        seeds = [b"staking_vault", rift.key().as_ref()],
+        bump
+    )]
+    pub staking_vault_authority: UncheckedAccount<'info>,
+
+    /// CHECK: PDA owning the reward budget vault
+    #[account(
+        seeds = [b"rift_mint_auth", rift.key().as_ref()],
+        bump
+    )]
+    pub rifts_mint_authority: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct FlushPendingRewards<'info> {
+    #[account(mut)]
+    pub rift: Account<'info, Rift>,
+
+    /// Protocol config naming the canonical governance
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// Governance state naming the RIFTS mint
+    #[account(
+        constraint = governance.key() == protocol_config.governance @ ErrorCode::InvalidGovernance
+    )]
+    pub governance: Account<'info, governance::Governance>,
+
+    /// RIFTS token mint for rewards; minted only by the staking pool's reward_authority PDA
+    #[account(
+        constraint = rifts_mint.key() == governance.rifts_mint @ ErrorCode::InvalidMint,
+        constraint = rifts_mint.key() != rift.rift_mint @ ErrorCode::InvalidMint,
+        constraint = rifts_mint.mint_authority == COption::Some(
+            Pubkey::find_program_address(
+                &[b"reward_authority", staking_pool.key().as_ref()],
+                &lp_staking::ID,
+            ).0
+        ) @ ErrorCode::InvalidMintAuthority
+    )]
+    pub rifts_mint: Account<'info, Mint>,
+
+    /// RIFTS authority PDA owning the reward budget vault, also the authorized depositor of the staking pool
+    /// CHECK: PDA for RIFTS mint authority
+    #[account(
+        seeds = [b"rift_mint_auth", rift.key().as_ref()],
+        bump
+    )]
+    pub rifts_mint_authority: UncheckedAccount<'info>,
+
+    /// Program-owned vault backing rifts_reward_budget; the deposit is drawn from it
+    #[account(
+        mut,
+        seeds = [b"reward_budget_vault", rift.key().as_ref()],
+        bump,
+        constraint = reward_budget_vault.mint == rifts_mint.key() @ ErrorCode::InvalidMint
+    )]
+    pub reward_budget_vault: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = staking_pool.rifts_protocol == rifts_mint_authority.key() @ ErrorCode::InvalidStakingPool,
+        constraint = staking_pool.reward_token_mint == rifts_mint.key() @ ErrorCode::InvalidMint
+    )]
+    pub staking_pool: Account<'info, lp_staking::StakingPool>,
+
+    #[account(
+        mut,
+        constraint = pool_reward_vault.key() == staking_pool.reward_token_vault @ ErrorCode::InvalidRewardVault
+    )]
+    pub pool_reward_vault: Account<'info, TokenAccount>,
+
+    pub lp_staking_program: Program<'info, lp_staking::program::LpStaking>,
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeRewardVesting<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    pub rift: Account<'info, Rift>,
+
+    #[account(
+        init,
+        payer = user,
+        space = 8 + std::mem::size_of::<RewardVesting>(),
+        seeds = [b"reward_vesting", rift.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub reward_vesting: Account<'info, RewardVesting>,
+
+    #[account(
+        init,
+        payer = user,
+        token::mint = rifts_mint,
+        token::authority = reward_vesting,
+        seeds = [b"vesting_escrow", rift.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub vesting_escrow: Account<'info, TokenAccount>,
+
+    /// RIFTS token mint for rewards
+    pub rifts_mint: Account<'info, Mint>,
+
+    pub token_program: Program<'info, Token>,
+    pub system_program: Program<'info, System>,
+    pub rent: Sysvar<'info, Rent>,
+}
+
+#[derive(Accounts)]
+pub struct ClaimVestedRewards<'info> {
+    pub user: Signer<'info>,
+
+    pub rift: Account<'info, Rift>,
+
+    #[account(
+        mut,
+        seeds = [b"reward_vesting", rift.key().as_ref(), user.key().as_ref()],
+        bump = reward_vesting.bump
+    )]
+    pub reward_vesting: Account<'info, RewardVesting>,
+
+    #[account(
+        mut,
+        constraint = vesting_escrow.key() == reward_vesting.escrow @ ErrorCode::InvalidVault
+    )]
+    pub vesting_escrow: Account<'info, TokenAccount>,
+
+    /// User's RIFTS token account
+    #[account(
+        mut,
+        constraint = user_rifts_tokens.mint == vesting_escrow.mint @ ErrorCode::InvalidMint,
+        constraint = user_rifts_tokens.owner == user.key() @ ErrorCode::UnauthorizedTokenAccount
+    )]
+    pub user_rifts_tokens: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct UnstakeLPTokens<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+    
+    #[account(mut)]
+    pub rift: Account<'info, Rift>,
+    
+    #[account(
+        mut,
+        seeds = [b"staker", rift.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub staker_account: Account<'info, StakerAccount>,
+
+    /// Protocol config naming the canonical governance
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// Governance state; frozen/paused time is excluded from reward accrual
+    #[account(
+        constraint = governance.key() == protocol_config.governance @ ErrorCode::InvalidGovernance
+    )]
+    pub governance: Account<'info, governance::Governance>,
+    
+    /// Unchecked while lp_token_mint is unset so stakes in rifts bound before it existed can exit
+    #[account(
+        mut,
+        constraint = rift.lp_token_mint.map_or(true, |mint| mint == user_lp_tokens.mint) @ ErrorCode::InvalidLpMint
+    )]
+    pub user_lp_tokens: Account<'info, TokenAccount>,
+    
+    #[account(mut)]
+    pub staking_vault: Account<'info, TokenAccount>,
+    
+    /// Staking vault authority
+    /// CHECK: PDA for staking vault authority
+    #[account(
+        seeds = [b"staking_vault", rift.key().as_ref()],
+        bump
+    )]
+    pub staking_vault_authority: UncheckedAccount<'info>,
+    
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct MigrateStakerRewards<'info> {
+    pub user: Signer<'info>,
+
+    #[account(mut)]
+    pub rift: Account<'info, Rift>,
+
+    #[account(
+        mut,
+        seeds = [b"staker", rift.key().as_ref(), user.key().as_ref()],
+        bump
+    )]
+    pub staker_account: Account<'info, StakerAccount>,
+
+    /// Protocol config naming the canonical governance
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// Governance state; frozen/paused time is excluded from reward accrual
+    #[account(
+        constraint = governance.key() == protocol_config.governance @ ErrorCode::InvalidGovernance
+    )]
+    pub governance: Account<'info, governance::Governance>,
+}
+
+#[derive(Accounts)]
+pub struct JupiterSwapForBuyback<'info> {
+    #[account(mut)]
+    pub user: Signer<'info>,
+
+    #[account(mut)]
+    pub rift: Account<'info, Rift>,
+
+    /// CHECK: Vault authority PDA
+    #[account(
+        seeds = [b"vault_auth", rift.key().as_ref()],
+        bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// **SECURITY FIX #51**: Source token account for balance verification
+    /// This is the account that pays for the swap
+    #[account(mut)]
+    pub source_token_account: Account<'info, TokenAccount>,
+
+    /// **SECURITY FIX #51**: Destination token account for balance verification
+    /// This is the account that receives the swapped tokens
+    #[account(mut)]
+    pub destination_token_account: Account<'info, TokenAccount>,
+
+    /// CHECK: Jupiter program - validated in instruction against governance config
+    /// **SECURITY FIX**: Removed hardcoded constraint to allow governance configuration
+    #[account(
+        constraint = jupiter_program.executable @ ErrorCode::InvalidProgramId
+    )]
+    pub jupiter_program: UncheckedAccount<'info>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct ExecuteGovernanceProposal<'info> {
+    #[account(mut)]
+    pub executor: Signer<'info>,
+
+    #[account(mut)]
+    pub rift: Account<'info, Rift>,
+
+    /// **SECURITY FIX #43**: Governance proposal with PDA binding to prevent spoofing
+    /// Proposal must be derived from governance account to ensure it belongs to the correct governance
+    #[account(
+        seeds = [b"proposal", governance.key().as_ref(), &proposal.id.to_le_bytes()],
+        bump,
+        seeds::program = governance::ID
+    )]
+    pub proposal: Account<'info, governance::Proposal>,
+
+    /// Protocol config naming the canonical governance
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// Governance state account
+    #[account(
+        constraint = governance.key() == protocol_config.governance @ ErrorCode::InvalidGovernance
+    )]
+    pub governance: Account<'info, governance::Governance>,
+
+    /// Last parameter-change proposal this governance applied to the rift
+    #[account(
+        init_if_needed,
+        payer = executor,
+        space = AppliedProposalRecord::LEN,
+        seeds = [b"applied_proposal", rift.key().as_ref(), governance.key().as_ref(), PROPOSAL_EFFECT_PARAMETERS],
+        bump
+    )]
+    pub applied_proposal: Account<'info, AppliedProposalRecord>,
+
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct ApplyPendingRiftChanges<'info> {
+    #[account(mut)]
+    pub rift: Account<'info, Rift>,
+}
+
+#[derive(Accounts)]
+pub struct EmergencyPause<'info> {
+    /// Governance authority, or the rift's pause_authority
+    #[account(mut)]
+    pub governance_authority: Signer<'info>,
+    
+    #[account(mut)]
+    pub rift: Account<'info, Rift>,
+
+    /// Protocol config naming the canonical governance
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    
+    /// Governance state for authorization (not needed when signed by rift.pause_authority)
+    #[account(
+        constraint = governance.key() == protocol_config.governance @ ErrorCode::InvalidGovernance
+    )]
+    pub governance: Option<Account<'info, governance::Governance>>,
+}
+
+#[derive(Accounts)]
+pub struct EmergencyUnpause<'info> {
+    /// Governance authority, or the rift's pause_authority lifting its own pause
+    #[account(mut)]
+    pub governance_authority: Signer<'info>,
+    
+    #[account(mut)]
+    pub rift: Account<'info, Rift>,
+
+    /// Protocol config naming the canonical governance
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+    
+    /// Governance state for authorization (not needed when the pause_authority lifts its own pause)
+    #[account(
+        constraint = governance.key() == protocol_config.governance @ ErrorCode::InvalidGovernance
+    )]
+    pub governance: Option<Account<'info, governance::Governance>>,
+}
+
+#[derive(Accounts)]
+pub struct CloseRift<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        close = creator,
+        has_one = creator @ ErrorCode::UnauthorizedClose
+    )]
+    pub rift: Account<'info, Rift>,
+}
+
+#[derive(Accounts)]
+pub struct AdminCloseRift<'info> {
+    #[account(mut)]
+    pub program_authority: Signer<'info>,
+
+    /// Protocol config holding the current admin
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(
+        mut,
+        close = program_authority
+    )]
+    pub rift: Account<'info, Rift>,
+}
+
+#[derive(Accounts)]
+pub struct AdminProposeClose<'info> {
+    pub program_authority: Signer<'info>,
+
+    /// Protocol config holding the current admin
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    #[account(mut)]
+    pub rift: Account<'info, Rift>,
+}
+
+#[derive(Accounts)]
+pub struct CancelAdminClose<'info> {
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = rift.creator == creator.key() @ ErrorCode::Unauthorized
+    )]
+    pub rift: Account<'info, Rift>,
+}
+
+#[derive(Accounts)]
+pub struct AdminRescueTokens<'info> {
+    pub program_authority: Signer<'info>,
+
+    /// Protocol config holding the current admin
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    pub rift: Account<'info, Rift>,
+
+    /// CHECK: Vault authority PDA
+    #[account(
+        seeds = [b"vault_auth", rift.key().as_ref()],
+        bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// Token account holding the stray tokens; never the rift's underlying or its RIFT
+    #[account(
+        mut,
+        constraint = stray_token_account.owner == vault_authority.key() @ ErrorCode::UnauthorizedTokenAccount,
+        constraint = stray_token_account.mint != rift.underlying_mint @ ErrorCode::CannotRescueUnderlying,
+        constraint = stray_token_account.mint != rift.rift_mint @ ErrorCode::CannotRescueRiftTokens
+    )]
+    pub stray_token_account: Account<'info, TokenAccount>,
+
+    #[account(
+        mut,
+        constraint = recipient_token_account.mint == stray_token_account.mint @ ErrorCode::InvalidMint
+    )]
+    pub recipient_token_account: Account<'info, TokenAccount>,
+
+    pub token_program: Program<'info, Token>,
+}
+
+#[derive(Accounts)]
+pub struct CleanupStuckAccounts<'info> {
+    /// The creator who originally tried to create the rift
+    /// **SECURITY FIX**: Require creator signature to prevent griefing
+    pub creator: Signer<'info>,
+    
+    /// The underlying mint that was used in the failed rift creation
+    pub underlying_mint: Account<'info, Mint>,
+    
+    /// The stuck rift mint account that needs to be cleaned up
+    #[account(
+        mut,
+        close = creator,
+        seeds = [b"rift_mint", expected_rift.key().as_ref()],
+        bump
+    )]
+    pub stuck_rift_mint: Account<'info, Mint>,
+    
+    /// The expected rift account location (should be empty/non-existent)
+    /// CHECK: We verify this account is empty to ensure it's truly stuck
+    #[account(
+        seeds = [b"rift", underlying_mint.key().as_ref(), creator.key().as_ref()],
+        constraint = underlying_mint.key() != Pubkey::default() && creator.key() != Pubkey::default() @ ErrorCode::InvalidSeedComponent,
+        bump
+    )]
+    pub expected_rift: UncheckedAccount<'info>,
+    
+    /// The account that will pay for the transaction (can be anyone)
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct UpdateJupiterProgramId<'info> {
+    /// **GOVERNANCE AUTHORITY**: Only governance can update Jupiter program ID
+    #[account(mut)]
+    pub governance_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = rift.creator == governance_authority.key() @ ErrorCode::UnauthorizedAdmin
+    )]
+    pub rift: Account<'info, Rift>,
+}
+
+/// **SECURITY FIX #50**: Account struct for setting oracle addresses
+#[derive(Accounts)]
+pub struct SetOracleAccounts<'info> {
+    #[account(mut)]
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = rift.creator == creator.key() @ ErrorCode::Unauthorized
+    )]
+    pub rift: Account<'info, Rift>,
+}
+
+/// Read-only account struct for fee routing queries
+#[derive(Accounts)]
+pub struct GetFeeRouting<'info> {
+    pub rift: Account<'info, Rift>,
+}
+
+/// Read-only account struct for breakeven estimates
+#[derive(Accounts)]
+pub struct GetBreakevenEstimate<'info> {
+    pub rift: Account<'info, Rift>,
+}
+
+#[derive(Accounts)]
+pub struct SetZeroFeePolicy<'info> {
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = rift.creator == creator.key() @ ErrorCode::Unauthorized
+    )]
+    pub rift: Account<'info, Rift>,
+}
+
+#[derive(Accounts)]
+pub struct SetTreasury<'info> {
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = rift.creator == creator.key() @ ErrorCode::Unauthorized
+    )]
+    pub rift: Account<'info, Rift>,
+}
+
+#[derive(Accounts)]
+pub struct SetPauseAuthority<'info> {
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = rift.creator == creator.key() @ ErrorCode::Unauthorized
+    )]
+    pub rift: Account<'info, Rift>,
+}
+
+#[derive(Accounts)]
+pub struct SetRiftName<'info> {
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = rift.creator == creator.key() @ ErrorCode::Unauthorized
+    )]
+    pub rift: Account<'info, Rift>,
+}
+
+#[derive(Accounts)]
+pub struct SetBurnMode<'info> {
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = rift.creator == creator.key() @ ErrorCode::Unauthorized
+    )]
+    pub rift: Account<'info, Rift>,
+}
+
+#[derive(Accounts)]
+pub struct SetProtocolFeeMode<'info> {
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = rift.creator == creator.key() @ ErrorCode::Unauthorized
+    )]
+    pub rift: Account<'info, Rift>,
+}
+
+#[derive(Accounts)]
+pub struct SetMaxWrapPctOfTvl<'info> {
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = rift.creator == creator.key() @ ErrorCode::Unauthorized
+    )]
+    pub rift: Account<'info, Rift>,
+}
+
+#[derive(Accounts)]
+pub struct SetDepositCap<'info> {
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = rift.creator == creator.key() @ ErrorCode::Unauthorized
+    )]
+    pub rift: Account<'info, Rift>,
+}
+
+#[derive(Accounts)]
+pub struct SetWhitelistRoot<'info> {
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = rift.creator == creator.key() @ ErrorCode::Unauthorized
+    )]
+    pub rift: Account<'info, Rift>,
+}
+
+#[derive(Accounts)]
+pub struct SetMinClaimableRewards<'info> {
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = rift.creator == creator.key() @ ErrorCode::Unauthorized
     )]
-    pub user_rift_tokens: Account<'info, TokenAccount>,
+    pub rift: Account<'info, Rift>,
+}
+
+#[derive(Accounts)]
+pub struct SetStakerFeeDiscount<'info> {
+    pub creator: Signer<'info>,
 
-    /// **SECURITY FIX #49**: Validate vault matches rift state
     #[account(
         mut,
-        constraint = vault.key() == rift.vault @ ErrorCode::InvalidVault,
-        constraint = vault.mint == rift.underlying_mint @ ErrorCode::InvalidMint,
-        seeds = [b"vault", rift.key().as_ref()],
-        bump
+        constraint = rift.creator == creator.key() @ ErrorCode::Unauthorized
     )]
-    pub vault: Account<'info, TokenAccount>,
+    pub rift: Account<'info, Rift>,
+}
+
+/// Read-only account struct for invariant monitoring
+#[derive(Accounts)]
+pub struct CheckInvariants<'info> {
+    pub rift: Account<'info, Rift>,
 
-    /// **SECURITY FIX #49**: Validate rift mint matches rift state
     #[account(
-        mut,
         constraint = rift_mint.key() == rift.rift_mint @ ErrorCode::InvalidMint
     )]
     pub rift_mint: Account<'info, Mint>,
+}
 
-    /// CHECK: PDA
+/// Read-only account struct for backing ratio high-water queries
+#[derive(Accounts)]
+pub struct GetBackingRatioHighWater<'info> {
+    pub rift: Account<'info, Rift>,
+}
+
+/// Read-only account struct for configuration validation
+#[derive(Accounts)]
+pub struct ValidateRiftConfig<'info> {
+    pub rift: Account<'info, Rift>,
+}
+
+#[derive(Accounts)]
+pub struct SetRewardRate<'info> {
+    pub governance_authority: Signer<'info>,
+
+    #[account(mut)]
+    pub rift: Account<'info, Rift>,
+
+    /// Protocol config naming the canonical governance
     #[account(
-        seeds = [b"rift_mint_auth", rift.key().as_ref()],
-        bump
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump
     )]
-    pub rift_mint_authority: UncheckedAccount<'info>,
+    pub protocol_config: Account<'info, ProtocolConfig>,
 
-    /// **SECURITY FIX #49**: Add underlying mint for validation
-    pub underlying_mint: Account<'info, Mint>,
+    /// Governance state for authorization
+    #[account(
+        constraint = governance.key() == protocol_config.governance @ ErrorCode::InvalidGovernance
+    )]
+    pub governance: Account<'info, governance::Governance>,
+}
 
-    pub token_program: Program<'info, Token>,
+#[derive(Accounts)]
+pub struct SetRewardVestPeriod<'info> {
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = rift.creator == creator.key() @ ErrorCode::Unauthorized
+    )]
+    pub rift: Account<'info, Rift>,
 }
 
+/// Read-only account struct for fee split previews
 #[derive(Accounts)]
-pub struct SetPoolAddress<'info> {
-    #[account(mut)]
-    pub user: Signer<'info>,
+pub struct PreviewFeeSplit<'info> {
+    pub rift: Account<'info, Rift>,
+}
 
-    #[account(mut)]
+/// Read-only account struct for oracle history queries
+#[derive(Accounts)]
+pub struct GetOracleHistory<'info> {
     pub rift: Account<'info, Rift>,
 }
 
+/// Read-only account struct for countdown queries
 #[derive(Accounts)]
-pub struct WrapAndAddLiquidity<'info> {
-    #[account(mut)]
-    pub user: Signer<'info>,
+pub struct GetCountdowns<'info> {
+    pub rift: Account<'info, Rift>,
+}
 
-    #[account(mut)]
+/// Read-only account struct for oracle health queries
+#[derive(Accounts)]
+pub struct OracleHealthCheck<'info> {
     pub rift: Account<'info, Rift>,
+}
 
-    #[account(mut)]
-    pub user_underlying: Account<'info, TokenAccount>,
+#[derive(Accounts)]
+pub struct SetMaxOracleStaleness<'info> {
+    pub governance_authority: Signer<'info>,
 
     #[account(mut)]
-    pub user_rift_tokens: Account<'info, TokenAccount>,
+    pub rift: Account<'info, Rift>,
 
-    #[account(mut)]
-    pub rift_mint: Account<'info, Mint>,
+    /// Protocol config naming the canonical governance
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
 
-    /// CHECK: PDA
+    /// Governance state for authorization
     #[account(
-        seeds = [b"rift_mint_auth", rift.key().as_ref()],
-        bump
+        constraint = governance.key() == protocol_config.governance @ ErrorCode::InvalidGovernance
     )]
-    pub rift_mint_authority: UncheckedAccount<'info>,
+    pub governance: Account<'info, governance::Governance>,
+}
 
-    pub underlying_mint: Account<'info, Mint>,
+#[derive(Accounts)]
+pub struct SetConfidenceWeighting<'info> {
+    pub creator: Signer<'info>,
 
-    /// CHECK: Validated against rift.liquidity_pool
-    #[account(mut)]
-    pub pool: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        constraint = rift.creator == creator.key() @ ErrorCode::Unauthorized
+    )]
+    pub rift: Account<'info, Rift>,
+}
 
-    /// **PER-USER POSITION**: User's own position NFT mint (created by user)
+#[derive(Accounts)]
+pub struct FundRewardBudget<'info> {
     #[account(mut)]
-    pub user_position_nft_mint: Signer<'info>,
+    pub authority: Signer<'info>,
 
-    /// **PER-USER POSITION**: User's position account (derived from their NFT)
-    /// CHECK: Meteora position PDA derived from user's NFT mint
     #[account(mut)]
-    pub user_position: UncheckedAccount<'info>,
+    pub rift: Account<'info, Rift>,
 
-    /// **PER-USER POSITION**: User's position NFT token account
-    /// CHECK: Meteora position NFT account owned by user
-    #[account(mut)]
-    pub user_position_nft_account: UncheckedAccount<'info>,
+    /// Protocol config naming the canonical governance
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
 
-    /// CHECK: Meteora pool authority
-    pub pool_authority: UncheckedAccount<'info>,
+    /// Governance state for authorization (creator may also fund)
+    #[account(
+        constraint = governance.key() == protocol_config.governance @ ErrorCode::InvalidGovernance
+    )]
+    pub governance: Account<'info, governance::Governance>,
 
-    /// CHECK: Meteora token vaults
-    #[account(mut)]
-    pub token_a_vault: UncheckedAccount<'info>,
+    /// RIFTS mint the budget is funded in (never the rift's own wrapped mint)
+    #[account(
+        constraint = rifts_mint.key() == governance.rifts_mint @ ErrorCode::InvalidMint,
+        constraint = rifts_mint.key() != rift.rift_mint @ ErrorCode::InvalidMint
+    )]
+    pub rifts_mint: Account<'info, Mint>,
 
-    #[account(mut)]
-    pub token_b_vault: UncheckedAccount<'info>,
+    /// Funder's RIFTS account the deposit is drawn from
+    #[account(
+        mut,
+        constraint = funder_rifts_tokens.mint == rifts_mint.key() @ ErrorCode::InvalidMint
+    )]
+    pub funder_rifts_tokens: Account<'info, TokenAccount>,
 
-    /// CHECK: Meteora event authority
-    pub event_authority: UncheckedAccount<'info>,
+    /// Program-owned vault backing rifts_reward_budget; staking and keeper rewards are paid from it
+    #[account(
+        init_if_needed,
+        payer = authority,
+        token::mint = rifts_mint,
+        token::authority = rifts_mint_authority,
+        seeds = [b"reward_budget_vault", rift.key().as_ref()],
+        bump
+    )]
+    pub reward_budget_vault: Account<'info, TokenAccount>,
 
-    /// **SECURITY FIX #51**: Validate Meteora program ID against hardcoded constant
-    /// This prevents attackers from passing fake Meteora programs to steal funds
+    /// CHECK: PDA owning the reward budget vault
     #[account(
-        constraint = meteora_program.key() == METEORA_DAMM_V2_PROGRAM_ID @ ErrorCode::InvalidProgramId,
-        constraint = meteora_program.executable @ ErrorCode::InvalidProgramId
+        seeds = [b"rift_mint_auth", rift.key().as_ref()],
+        bump
     )]
-    pub meteora_program: UncheckedAccount<'info>,
+    pub rifts_mint_authority: UncheckedAccount<'info>,
 
     pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
 }
 
 #[derive(Accounts)]
-pub struct RemoveLiquidityAndUnwrap<'info> {
-    #[account(mut)]
-    pub user: Signer<'info>,
+pub struct SetMaxLiquidityRemoval<'info> {
+    pub creator: Signer<'info>,
 
-    #[account(mut)]
+    #[account(
+        mut,
+        constraint = rift.creator == creator.key() @ ErrorCode::Unauthorized
+    )]
     pub rift: Account<'info, Rift>,
+}
 
+#[derive(Accounts)]
+pub struct ManageMeteoraConfigs<'info> {
     #[account(mut)]
-    pub user_underlying: Account<'info, TokenAccount>,
-
-    #[account(mut)]
-    pub user_rift_tokens: Account<'info, TokenAccount>,
-
-    #[account(mut)]
-    pub rift_mint: Account<'info, Mint>,
-
-    pub underlying_mint: Account<'info, Mint>,
+    pub governance_authority: Signer<'info>,
 
-    /// CHECK: Validated against rift.liquidity_pool
-    #[account(mut)]
-    pub pool: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = governance_authority,
+        space = MeteoraConfigAllowlist::LEN,
+        seeds = [b"meteora_configs"],
+        bump
+    )]
+    pub meteora_config_allowlist: Account<'info, MeteoraConfigAllowlist>,
 
-    /// **PER-USER POSITION**: User's own position NFT mint
-    /// CHECK: User's position NFT mint that they created when adding liquidity
-    pub user_position_nft_mint: UncheckedAccount<'info>,
+    /// Protocol config naming the canonical governance
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
 
-    /// **PER-USER POSITION**: User's position account (derived from their NFT)
-    /// CHECK: Meteora position PDA derived from user's NFT mint
-    #[account(mut)]
-    pub user_position: UncheckedAccount<'info>,
+    /// Governance state for authorization
+    #[account(
+        constraint = governance.key() == protocol_config.governance @ ErrorCode::InvalidGovernance
+    )]
+    pub governance: Account<'info, governance::Governance>,
 
-    /// **PER-USER POSITION**: User's position NFT token account
-    /// CHECK: Meteora position NFT account owned by user
-    #[account(mut)]
-    pub user_position_nft_account: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
 
-    /// CHECK: Meteora pool authority
-    pub pool_authority: UncheckedAccount<'info>,
+#[derive(Accounts)]
+pub struct SetMeteoraConfig<'info> {
+    pub authority: Signer<'info>,
 
-    /// CHECK: Meteora token vaults
     #[account(mut)]
-    pub token_a_vault: UncheckedAccount<'info>,
+    pub rift: Account<'info, Rift>,
 
-    #[account(mut)]
-    pub token_b_vault: UncheckedAccount<'info>,
+    /// Protocol config naming the canonical governance
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
 
-    /// CHECK: Meteora event authority
-    pub event_authority: UncheckedAccount<'info>,
+    /// Governance state for authorization (creator may also set)
+    #[account(
+        constraint = governance.key() == protocol_config.governance @ ErrorCode::InvalidGovernance
+    )]
+    pub governance: Account<'info, governance::Governance>,
 
-    /// **SECURITY FIX #51**: Validate Meteora program ID against hardcoded constant
-    /// This prevents attackers from passing fake Meteora programs to steal funds
+    /// Governance-approved Meteora configs
     #[account(
-        constraint = meteora_program.key() == METEORA_DAMM_V2_PROGRAM_ID @ ErrorCode::InvalidProgramId,
-        constraint = meteora_program.executable @ ErrorCode::InvalidProgramId
+        seeds = [b"meteora_configs"],
+        bump = meteora_config_allowlist.bump
     )]
-    pub meteora_program: UncheckedAccount<'info>,
+    pub meteora_config_allowlist: Account<'info, MeteoraConfigAllowlist>,
 
-    pub token_program: Program<'info, Token>,
+    /// CHECK: Meteora config to use; ownership and allowlist checked in the handler
+    pub meteora_config: Option<UncheckedAccount<'info>>,
 }
 
 #[derive(Accounts)]
-pub struct CreateMeteoraPool<'info> {
-    /// User creating the pool (maps to creator in Meteora)
-    #[account(mut)]
-    pub user: Signer<'info>,
-
+pub struct SyncPoolMetadata<'info> {
     #[account(mut)]
     pub rift: Account<'info, Rift>,
 
-    /// Position NFT mint (required by Meteora)
-    #[account(mut)]
-    /// CHECK: This will be initialized by Meteora program
-    pub position_nft_mint: UncheckedAccount<'info>,
+    /// CHECK: Validated against rift.liquidity_pool and Meteora program ownership
+    pub pool: UncheckedAccount<'info>,
+}
 
-    /// Position NFT account (required by Meteora)
-    #[account(mut)]
-    /// CHECK: This will be initialized by Meteora program
-    pub position_nft_account: UncheckedAccount<'info>,
+#[derive(Accounts)]
+pub struct VerifyPoolState<'info> {
+    pub rift: Account<'info, Rift>,
 
-    /// Payer for the transaction (same as user in our case)
+    /// CHECK: Validated against rift.liquidity_pool and Meteora program ownership
+    pub pool: UncheckedAccount<'info>,
+}
+
+#[derive(Accounts)]
+pub struct InitializeProtocolConfig<'info> {
     #[account(mut)]
     pub payer: Signer<'info>,
 
-    /// Meteora config account (maps to config in Meteora)
-    /// CHECK: This should be the Meteora config account
-    pub config: UncheckedAccount<'info>,
+    #[account(
+        init,
+        payer = payer,
+        space = ProtocolConfig::LEN,
+        seeds = [b"protocol_config"],
+        bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
 
-    /// Pool authority (PDA)
-    /// CHECK: This will be a PDA derived by Meteora
-    pub pool_authority: UncheckedAccount<'info>,
+    /// Canonical governance account recorded in the config; must be the one created by the initial admin
+    #[account(
+        seeds = [b"governance", payer.key().as_ref()],
+        bump,
+        seeds::program = governance::ID
+    )]
+    pub governance: Account<'info, governance::Governance>,
+
+    pub system_program: Program<'info, System>,
+}
 
-    /// The official Meteora pool account to be created
+#[derive(Accounts)]
+pub struct SetRiftFactoryGate<'info> {
     #[account(mut)]
-    /// CHECK: This will be initialized by Meteora program
-    pub pool: UncheckedAccount<'info>,
+    pub program_authority: Signer<'info>,
 
-    /// Position account for liquidity position
-    #[account(mut)]
-    /// CHECK: This will be initialized by Meteora program
-    pub position: UncheckedAccount<'info>,
+    /// Protocol config holding the current admin
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
 
-    /// Token A mint (underlying token)
-    pub token_a_mint: Account<'info, Mint>,
+    #[account(
+        init_if_needed,
+        payer = program_authority,
+        space = RiftFactoryConfig::LEN,
+        seeds = [b"rift_factory_config"],
+        bump
+    )]
+    pub factory_config: Account<'info, RiftFactoryConfig>,
 
-    /// Token B mint (rift token)
-    #[account(mut)]
-    pub token_b_mint: Account<'info, Mint>,
+    pub system_program: Program<'info, System>,
+}
 
-    /// Token A vault (underlying token vault)
-    #[account(mut)]
-    /// CHECK: This will be initialized by Meteora program
-    pub token_a_vault: UncheckedAccount<'info>,
+#[derive(Accounts)]
+pub struct ExecuteProtocolAdminChange<'info> {
+    pub executor: Signer<'info>,
 
-    /// Token B vault (rift token vault)
-    #[account(mut)]
-    /// CHECK: This will be initialized by Meteora program
-    pub token_b_vault: UncheckedAccount<'info>,
+    #[account(
+        mut,
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
 
-    /// Payer's token A account
+    /// Governance proposal bound to the governance account by PDA
+    #[account(
+        seeds = [b"proposal", governance.key().as_ref(), &proposal.id.to_le_bytes()],
+        bump,
+        seeds::program = governance::ID
+    )]
+    pub proposal: Account<'info, governance::Proposal>,
+
+    /// Governance state account; must be the canonical governance recorded in protocol_config
+    #[account(
+        constraint = governance.key() == protocol_config.governance @ ErrorCode::InvalidGovernance
+    )]
+    pub governance: Account<'info, governance::Governance>,
+}
+
+#[derive(Accounts)]
+pub struct SetBackingRatio<'info> {
     #[account(mut)]
-    pub payer_token_a: Account<'info, TokenAccount>,
+    pub executor: Signer<'info>,
 
-    /// Payer's token B account (rift tokens)
     #[account(mut)]
-    pub payer_token_b: Account<'info, TokenAccount>,
+    pub rift: Account<'info, Rift>,
 
-    /// CHECK: PDA for rift mint authority
+    /// Governance proposal bound to the governance account by PDA
     #[account(
-        seeds = [b"rift_mint_auth", rift.key().as_ref()],
-        bump
+        seeds = [b"proposal", governance.key().as_ref(), &proposal.id.to_le_bytes()],
+        bump,
+        seeds::program = governance::ID
     )]
-    pub rift_mint_authority: UncheckedAccount<'info>,
-
-    /// Token program for token A
-    pub token_a_program: Program<'info, Token>,
-
-    /// Token program for token B
-    pub token_b_program: Program<'info, Token>,
+    pub proposal: Account<'info, governance::Proposal>,
 
-    /// Token 2022 program - Meteora expects the actual TOKEN_2022_PROGRAM_ID
-    /// CHECK: We don't validate this to allow Meteora to use TOKEN_2022_PROGRAM_ID
-    pub token_2022_program: UncheckedAccount<'info>,
+    /// Protocol config naming the canonical governance
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
 
-    /// The official Meteora DAMM v2 program
-    /// CHECK: This is the official Meteora program ID
+    /// Governance state account
     #[account(
-        constraint = meteora_program.key() == METEORA_DAMM_V2_PROGRAM_ID @ ErrorCode::InvalidProgramId,
-        constraint = meteora_program.executable @ ErrorCode::InvalidProgramId
+        constraint = governance.key() == protocol_config.governance @ ErrorCode::InvalidGovernance
     )]
-    pub meteora_program: UncheckedAccount<'info>,
+    pub governance: Account<'info, governance::Governance>,
 
-    /// Event authority for Meteora events
-    /// CHECK: PDA derived with ["__event_authority"] seeds from Meteora program
-    pub event_authority: UncheckedAccount<'info>,
+    /// Last backing-ratio proposal this governance applied to the rift
+    #[account(
+        init_if_needed,
+        payer = executor,
+        space = AppliedProposalRecord::LEN,
+        seeds = [b"applied_proposal", rift.key().as_ref(), governance.key().as_ref(), PROPOSAL_EFFECT_BACKING_RATIO],
+        bump
+    )]
+    pub applied_proposal: Account<'info, AppliedProposalRecord>,
 
-    pub token_program: Program<'info, Token>,
     pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
 }
 
-
-/// **NEW ARCHITECTURE**: UnwrapTokens now removes liquidity from Meteora instead of vault
 #[derive(Accounts)]
-pub struct UnwrapTokens<'info> {
-    #[account(mut)]
-    pub user: Signer<'info>,
+pub struct SweepExcessUnderlying<'info> {
+    pub authority: Signer<'info>,
 
-    #[account(mut)]
     pub rift: Account<'info, Rift>,
 
-    /// **SECURITY FIX #49**: Validate user's underlying token account
+    /// Vault holding the underlying tokens
     #[account(
         mut,
-        constraint = user_underlying.mint == rift.underlying_mint @ ErrorCode::InvalidMint,
-        constraint = user_underlying.owner == user.key() @ ErrorCode::UnauthorizedTokenAccount
+        constraint = vault.key() == rift.vault @ ErrorCode::InvalidVault,
+        seeds = [b"vault", rift.key().as_ref()],
+        bump
     )]
-    pub user_underlying: Account<'info, TokenAccount>,
+    pub vault: Account<'info, TokenAccount>,
 
-    /// **SECURITY FIX #49**: Validate user's RIFT token account
+    /// CHECK: Vault authority PDA - validated by seeds constraint
     #[account(
-        mut,
-        constraint = user_rift_tokens.mint == rift.rift_mint @ ErrorCode::InvalidMint,
-        constraint = user_rift_tokens.owner == user.key() @ ErrorCode::UnauthorizedTokenAccount
+        seeds = [b"vault_auth", rift.key().as_ref()],
+        bump
     )]
-    pub user_rift_tokens: Account<'info, TokenAccount>,
+    pub vault_authority: UncheckedAccount<'info>,
 
-    /// **METEORA INTEGRATION**: Meteora pool account
-    #[account(mut)]
-    /// CHECK: Validated against rift.liquidity_pool
-    pub pool: UncheckedAccount<'info>,
+    /// RIFT mint; its live supply is backing the sweep must leave in the vault
+    #[account(
+        constraint = rift_mint.key() == rift.rift_mint @ ErrorCode::InvalidMint
+    )]
+    pub rift_mint: Account<'info, Mint>,
 
-    /// **PER-USER POSITION**: User's own position NFT mint
-    pub user_position_nft_mint: UncheckedAccount<'info>,
+    /// The rift's configured treasury account receiving the excess
+    #[account(
+        mut,
+        constraint = rift.treasury == Some(treasury.key()) @ ErrorCode::InvalidTreasury,
+        constraint = treasury.mint == rift.underlying_mint @ ErrorCode::InvalidMint
+    )]
+    pub treasury: Account<'info, TokenAccount>,
 
-    /// **PER-USER POSITION**: User's position account (derived from their NFT)
-    #[account(mut)]
-    pub user_position: UncheckedAccount<'info>,
+    /// Protocol config naming the canonical governance
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
 
-    /// **PER-USER POSITION**: User's position NFT token account
-    #[account(mut)]
-    pub user_position_nft_account: UncheckedAccount<'info>,
+    /// Governance state for authorization (creator may also sweep)
+    #[account(
+        constraint = governance.key() == protocol_config.governance @ ErrorCode::InvalidGovernance
+    )]
+    pub governance: Account<'info, governance::Governance>,
 
-    /// **METEORA INTEGRATION**: Pool authority PDA
-    /// CHECK: Meteora-derived PDA
-    pub pool_authority: UncheckedAccount<'info>,
+    pub token_program: Program<'info, Token>,
+}
 
-    /// **METEORA INTEGRATION**: Pool's token A vault (underlying)
-    #[account(mut)]
-    /// CHECK: Meteora pool vault
-    pub token_a_vault: UncheckedAccount<'info>,
+#[derive(Accounts)]
+pub struct ExecuteProtocolArbitrage<'info> {
+    pub authority: Signer<'info>,
 
-    /// **METEORA INTEGRATION**: Pool's token B vault (RIFT)
     #[account(mut)]
-    /// CHECK: Meteora pool vault
-    pub token_b_vault: UncheckedAccount<'info>,
+    pub rift: Box<Account<'info, Rift>>,
+
+    /// Protocol config naming the canonical governance
+    #[account(
+        seeds = [b"protocol_config"],
+        bump = protocol_config.bump
+    )]
+    pub protocol_config: Account<'info, ProtocolConfig>,
+
+    /// Governance state for authorization (creator may also execute)
+    #[account(
+        constraint = governance.key() == protocol_config.governance @ ErrorCode::InvalidGovernance
+    )]
+    pub governance: Account<'info, governance::Governance>,
 
-    /// **SECURITY FIX #49**: Validate rift mint matches rift state
     #[account(
         mut,
         constraint = rift_mint.key() == rift.rift_mint @ ErrorCode::InvalidMint
     )]
     pub rift_mint: Account<'info, Mint>,
 
-    /// **SECURITY FIX #49**: Validate underlying mint matches rift state
     #[account(
         constraint = underlying_mint.key() == rift.underlying_mint @ ErrorCode::InvalidMint
     )]
     pub underlying_mint: Account<'info, Mint>,
 
-    /// RIFT mint authority PDA
-    /// CHECK: PDA
+    /// Vault holding the underlying tokens, funds the trade
+    #[account(
+        mut,
+        constraint = vault.key() == rift.vault @ ErrorCode::InvalidVault,
+        seeds = [b"vault", rift.key().as_ref()],
+        bump
+    )]
+    pub vault: Box<Account<'info, TokenAccount>>,
+
+    /// Fee vault receiving the captured spread
+    #[account(
+        mut,
+        constraint = fee_vault.key() == rift.fee_vault @ ErrorCode::InvalidVault
+    )]
+    pub fee_vault: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Vault authority PDA - validated by seeds constraint
+    #[account(
+        seeds = [b"vault_auth", rift.key().as_ref()],
+        bump
+    )]
+    pub vault_authority: UncheckedAccount<'info>,
+
+    /// Protocol-held RIFT account used as the swap's RIFT leg
+    #[account(
+        mut,
+        token::mint = rift_mint,
+        token::authority = vault_authority
+    )]
+    pub arbitrage_rift_account: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: PDA for RIFT mint authority
     #[account(
         seeds = [b"rift_mint_auth", rift.key().as_ref()],
         bump
     )]
     pub rift_mint_authority: UncheckedAccount<'info>,
 
-    /// **METEORA INTEGRATION**: Meteora program
-    /// CHECK: Validated against METEORA_DAMM_V2_PROGRAM_ID
+    /// CHECK: Validated against rift.liquidity_pool and Meteora program ownership
+    #[account(
+        mut,
+        constraint = rift.liquidity_pool == Some(pool.key()) @ ErrorCode::InvalidPoolAccount
+    )]
+    pub pool: UncheckedAccount<'info>,
+
+    /// CHECK: Meteora pool authority
+    #[account(
+        constraint = pool_authority.key() == meteora_pool_authority() @ ErrorCode::InvalidPoolAccount
+    )]
+    pub pool_authority: UncheckedAccount<'info>,
+
+    /// Meteora token vaults, validated against the pool state
+    #[account(mut)]
+    pub token_a_vault: Box<Account<'info, TokenAccount>>,
+
+    #[account(mut)]
+    pub token_b_vault: Box<Account<'info, TokenAccount>>,
+
+    /// CHECK: Meteora event authority
+    pub event_authority: UncheckedAccount<'info>,
+
     #[account(
         constraint = meteora_program.key() == METEORA_DAMM_V2_PROGRAM_ID @ ErrorCode::InvalidProgramId,
         constraint = meteora_program.executable @ ErrorCode::InvalidProgramId
     )]
     pub meteora_program: UncheckedAccount<'info>,
 
-    /// **METEORA INTEGRATION**: Event authority for Meteora events
-    /// CHECK: PDA derived with ["__event_authority"] seeds from Meteora program
-    pub event_authority: UncheckedAccount<'info>,
-
     pub token_program: Program<'info, Token>,
 }
 
-
 #[derive(Accounts)]
-pub struct AdminFixVaultConflict<'info> {
-    #[account(mut)]
-    pub program_authority: Signer<'info>,
+pub struct ClaimMeteoraFees<'info> {
+    pub claimer: Signer<'info>,
 
     #[account(mut)]
-    pub rift: Account<'info, Rift>,
+    pub rift: Box<Account<'info, Rift>>,
 
-    /// CHECK: Vault PDA that may have wrong owner
     #[account(
         mut,
-        seeds = [b"vault", rift.key().as_ref()],
-        bump
+        constraint = rift_mint.key() == rift.rift_mint @ ErrorCode::InvalidMint
     )]
-    pub vault: UncheckedAccount<'info>,
+    pub rift_mint: Account<'info, Mint>,
 
-    /// CHECK: Expected vault authority PDA
     #[account(
-        seeds = [b"vault_auth", rift.key().as_ref()],
-        bump
+        constraint = underlying_mint.key() == rift.underlying_mint @ ErrorCode::InvalidMint
     )]
-    pub vault_authority: UncheckedAccount<'info>,
-}
-
-#[derive(Accounts)]
-pub struct InitializePool<'info> {
-    #[account(mut)]
-    pub user: Signer<'info>,
+    pub underlying_mint: Account<'info, Mint>,
 
-    #[account(mut)]
-    pub rift: Account<'info, Rift>,
+    /// Vault holding the underlying tokens, releases backing for redeemed RIFT fees
+    #[account(
+        mut,
+        constraint = vault.key() == rift.vault @ ErrorCode::InvalidVault,
+        seeds = [b"vault", rift.key().as_ref()],
+        bump
+    )]
+    pub vault: Box<Account<'info, TokenAccount>>,
 
-    #[account(mut)]
-    pub rift_mint: Account<'info, Mint>,
+    /// Fee vault receiving the claimed fees
+    #[account(
+        mut,
+        constraint = fee_vault.key() == rift.fee_vault @ ErrorCode::InvalidVault
+    )]
+    pub fee_vault: Box<Account<'info, TokenAccount>>,
 
-    /// CHECK: PDA
+    /// CHECK: Vault authority PDA - burns redeemed RIFT and releases their backing - validated by seeds constraint
     #[account(
-        seeds = [b"rift_mint_auth", rift.key().as_ref()],
+        seeds = [b"vault_auth", rift.key().as_ref()],
         bump
     )]
-    pub rift_mint_authority: UncheckedAccount<'info>,
+    pub vault_authority: UncheckedAccount<'info>,
 
-    /// Pool underlying token account
+    /// CHECK: Protocol position owner PDA - holds the position NFT, out of admin_rescue_tokens' reach
     #[account(
-        init_if_needed,
-        payer = user,
-        token::mint = underlying_mint,
-        token::authority = pool_authority,
-        seeds = [b"pool_underlying", rift.key().as_ref()],
+        seeds = [b"protocol_position", rift.key().as_ref()],
         bump
     )]
-    pub pool_underlying: Account<'info, TokenAccount>,
+    pub position_owner: UncheckedAccount<'info>,
 
-    /// Pool rift token account
+    /// Protocol-held RIFT account receiving the RIFT side of the fees before the burn
     #[account(
-        init_if_needed,
-        payer = user,
+        mut,
         token::mint = rift_mint,
-        token::authority = pool_authority,
-        seeds = [b"pool_rift", rift.key().as_ref()],
-        bump
+        token::authority = vault_authority
     )]
-    pub pool_rift: Account<'info, TokenAccount>,
+    pub protocol_rift_account: Box<Account<'info, TokenAccount>>,
 
-    /// CHECK: Pool authority PDA
+    /// CHECK: Validated against rift.liquidity_pool and Meteora program ownership
     #[account(
-        seeds = [b"pool_auth", rift.key().as_ref()],
-        bump
+        mut,
+        constraint = rift.liquidity_pool == Some(pool.key()) @ ErrorCode::InvalidPoolAccount
+    )]
+    pub pool: UncheckedAccount<'info>,
+
+    /// CHECK: Meteora pool authority
+    #[account(
+        constraint = pool_authority.key() == meteora_pool_authority() @ ErrorCode::InvalidPoolAccount
     )]
     pub pool_authority: UncheckedAccount<'info>,
 
-    pub underlying_mint: Account<'info, Mint>,
-    pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
-}
+    /// CHECK: Protocol position NFT mint
+    pub position_nft_mint: UncheckedAccount<'info>,
 
-/// **SECURITY FIX #50**: Account struct for updating Pyth oracle
-#[derive(Accounts)]
-pub struct UpdatePythOracle<'info> {
+    /// CHECK: Meteora position PDA for position_nft_mint, checked against the pool in the handler
     #[account(mut)]
-    pub rift: Account<'info, Rift>,
+    pub position: UncheckedAccount<'info>,
 
-    /// **SECURITY FIX #50**: Authority authorized to update oracle prices (creator or governance)
-    pub oracle_authority: Signer<'info>,
+    /// CHECK: Must be held by the position owner PDA and hold position_nft_mint
+    #[account(
+        constraint = position_nft_held_by(
+            &position_nft_account,
+            &position_nft_mint.key(),
+            &position_owner.key()
+        ) @ ErrorCode::UnauthorizedPosition
+    )]
+    pub position_nft_account: UncheckedAccount<'info>,
 
-    /// **SECURITY FIX #50**: Pyth price account - validated against rift.pyth_price_account
-    /// CHECK: Validated in instruction handler against stored pubkey and Pyth program ownership
-    pub pyth_price_account: UncheckedAccount<'info>,
-}
+    /// CHECK: Meteora token vaults, validated against the pool state
+    #[account(mut)]
+    pub token_a_vault: UncheckedAccount<'info>,
 
-/// **SECURITY FIX #50**: Account struct for updating Switchboard oracle
-#[derive(Accounts)]
-pub struct UpdateSwitchboardOracle<'info> {
     #[account(mut)]
-    pub rift: Account<'info, Rift>,
+    pub token_b_vault: UncheckedAccount<'info>,
 
-    /// **SECURITY FIX #50**: Authority authorized to update oracle prices (creator or governance)
-    pub oracle_authority: Signer<'info>,
+    /// CHECK: Meteora event authority
+    pub event_authority: UncheckedAccount<'info>,
 
-    /// **SECURITY FIX #50**: Switchboard aggregator feed - validated against rift.switchboard_feed_account
-    /// CHECK: Validated in instruction handler against stored pubkey and Switchboard program ownership
-    pub switchboard_feed: UncheckedAccount<'info>,
-}
+    #[account(
+        constraint = meteora_program.key() == METEORA_DAMM_V2_PROGRAM_ID @ ErrorCode::InvalidProgramId,
+        constraint = meteora_program.executable @ ErrorCode::InvalidProgramId
+    )]
+    pub meteora_program: UncheckedAccount<'info>,
 
-#[derive(Accounts)]
-pub struct TriggerRebalance<'info> {
-    #[account(mut)]
-    pub user: Signer<'info>,
-    
-    #[account(mut)]
-    pub rift: Account<'info, Rift>,
+    pub token_program: Program<'info, Token>,
 }
 
-
-/// Optimized fee distribution context - essential accounts only
 #[derive(Accounts)]
-pub struct ProcessFeeDistribution<'info> {
-    #[account(mut)]
-    pub fee_payer: Signer<'info>,
+pub struct CompoundProtocolLiquidity<'info> {
+    pub compounder: Signer<'info>,
 
     #[account(mut)]
-    pub rift: Account<'info, Rift>,
+    pub rift: Box<Account<'info, Rift>>,
 
-    /// Vault holding the underlying tokens
     #[account(
-        mut,
-        seeds = [b"vault", rift.key().as_ref()],
-        bump
+        constraint = rift_mint.key() == rift.rift_mint @ ErrorCode::InvalidMint
     )]
-    pub vault: Account<'info, TokenAccount>,
+    pub rift_mint: Account<'info, Mint>,
 
-    /// CHECK: Vault authority PDA - validated by seeds constraint
     #[account(
-        seeds = [b"vault_auth", rift.key().as_ref()],
-        bump
+        constraint = underlying_mint.key() == rift.underlying_mint @ ErrorCode::InvalidMint
     )]
-    pub vault_authority: UncheckedAccount<'info>,
-
-    /// Treasury account for fee collection
-    #[account(mut)]
-    pub treasury: Account<'info, TokenAccount>,
-
-    /// Fee collector vault (optional - only if fee_collector_amount > 0)
-    #[account(mut)]
-    pub fee_collector_vault: Option<Account<'info, TokenAccount>>,
-
-    /// Partner vault (optional - only if partner fees configured)
-    /// **SECURITY FIX**: Validate partner vault belongs to configured partner
-    pub partner_vault: Option<Account<'info, TokenAccount>>,
-
-    pub token_program: Program<'info, Token>,
-}
+    pub underlying_mint: Account<'info, Mint>,
 
-#[derive(Accounts)]
-pub struct StakeLPTokens<'info> {
-    #[account(mut)]
-    pub user: Signer<'info>,
-    
-    #[account(mut)]
-    pub rift: Account<'info, Rift>,
-    
+    /// CHECK: Protocol position owner PDA - holds the position NFT and funds the re-add - validated by seeds
     #[account(
-        init_if_needed,
-        payer = user,
-        space = 8 + std::mem::size_of::<StakerAccount>(),
-        seeds = [b"staker", rift.key().as_ref(), user.key().as_ref()],
+        seeds = [b"protocol_position", rift.key().as_ref()],
         bump
     )]
-    pub staker_account: Account<'info, StakerAccount>,
-    
-    #[account(mut)]
-    pub user_lp_tokens: Account<'info, TokenAccount>,
-    
-    #[account(mut)]
-    pub staking_vault: Account<'info, TokenAccount>,
-    
-    pub token_program: Program<'info, Token>,
-    pub system_program: Program<'info, System>,
-    pub rent: Sysvar<'info, Rent>,
-}
+    pub position_owner: UncheckedAccount<'info>,
 
-#[derive(Accounts)]
-pub struct ClaimStakingRewards<'info> {
-    #[account(mut)]
-    pub user: Signer<'info>,
-    
-    #[account(mut)]
-    pub rift: Account<'info, Rift>,
-    
+    /// Protocol-held underlying account receiving fees and funding the re-add
     #[account(
         mut,
-        seeds = [b"staker", rift.key().as_ref(), user.key().as_ref()],
-        bump
+        token::mint = underlying_mint,
+        token::authority = position_owner
     )]
-    pub staker_account: Account<'info, StakerAccount>,
-    
-    /// RIFTS token mint for rewards
-    #[account(mut)]
-    pub rifts_mint: Account<'info, Mint>,
-    
-    /// User's RIFTS token account
-    #[account(mut)]
-    pub user_rifts_tokens: Account<'info, TokenAccount>,
-    
-    /// RIFTS mint authority
-    /// CHECK: PDA for RIFTS mint authority
+    pub protocol_underlying_account: Box<Account<'info, TokenAccount>>,
+
+    /// Protocol-held RIFT account receiving fees and funding the re-add
     #[account(
-        seeds = [b"rift_mint_auth", rift.key().as_ref()],
-        bump
+        mut,
+        token::mint = rift_mint,
+        token::authority = position_owner
     )]
-    pub rifts_mint_authority: UncheckedAccount<'info>,
-    
-    pub token_program: Program<'info, Token>,
-}
+    pub protocol_rift_account: Box<Account<'info, TokenAccount>>,
 
-#[derive(Accounts)]
-pub struct UnstakeLPTokens<'info> {
-    #[account(mut)]
-    pub user: Signer<'info>,
-    
-    #[account(mut)]
-    pub rift: Account<'info, Rift>,
-    
+    /// CHECK: Validated against rift.liquidity_pool and Meteora program ownership
     #[account(
         mut,
-        seeds = [b"staker", rift.key().as_ref(), user.key().as_ref()],
-        bump
+        constraint = rift.liquidity_pool == Some(pool.key()) @ ErrorCode::InvalidPoolAccount
     )]
-    pub staker_account: Account<'info, StakerAccount>,
-    
-    #[account(mut)]
-    pub user_lp_tokens: Account<'info, TokenAccount>,
-    
-    #[account(mut)]
-    pub staking_vault: Account<'info, TokenAccount>,
-    
-    /// Staking vault authority
-    /// CHECK: PDA for staking vault authority
+    pub pool: UncheckedAccount<'info>,
+
+    /// CHECK: Meteora pool authority, also checked against rift.pool_authority in the handler
     #[account(
-        seeds = [b"staking_vault", rift.key().as_ref()],
-        bump
+        constraint = pool_authority.key() == meteora_pool_authority() @ ErrorCode::InvalidPoolAccount
     )]
-    pub staking_vault_authority: UncheckedAccount<'info>,
-    
-    pub token_program: Program<'info, Token>,
-}
+    pub pool_authority: UncheckedAccount<'info>,
 
-#[derive(Accounts)]
-pub struct JupiterSwapForBuyback<'info> {
-    #[account(mut)]
-    pub user: Signer<'info>,
+    /// CHECK: Protocol position NFT mint
+    pub position_nft_mint: UncheckedAccount<'info>,
 
+    /// CHECK: Meteora position PDA for position_nft_mint, checked against the pool in the handler
     #[account(mut)]
-    pub rift: Account<'info, Rift>,
+    pub position: UncheckedAccount<'info>,
 
-    /// CHECK: Vault authority PDA
+    /// CHECK: Must be held by the position owner PDA and hold position_nft_mint
     #[account(
-        seeds = [b"vault_auth", rift.key().as_ref()],
-        bump
+        constraint = position_nft_held_by(
+            &position_nft_account,
+            &position_nft_mint.key(),
+            &position_owner.key()
+        ) @ ErrorCode::UnauthorizedPosition
     )]
-    pub vault_authority: UncheckedAccount<'info>,
+    pub position_nft_account: UncheckedAccount<'info>,
 
-    /// **SECURITY FIX #51**: Source token account for balance verification
-    /// This is the account that pays for the swap
+    /// CHECK: Meteora token vaults, validated against the pool state
     #[account(mut)]
-    pub source_token_account: Account<'info, TokenAccount>,
+    pub token_a_vault: UncheckedAccount<'info>,
 
-    /// **SECURITY FIX #51**: Destination token account for balance verification
-    /// This is the account that receives the swapped tokens
     #[account(mut)]
-    pub destination_token_account: Account<'info, TokenAccount>,
+    pub token_b_vault: UncheckedAccount<'info>,
+
+    /// CHECK: Meteora event authority
+    pub event_authority: UncheckedAccount<'info>,
 
-    /// CHECK: Jupiter program - validated in instruction against governance config
-    /// **SECURITY FIX**: Removed hardcoded constraint to allow governance configuration
     #[account(
-        constraint = jupiter_program.executable @ ErrorCode::InvalidProgramId
+        constraint = meteora_program.key() == METEORA_DAMM_V2_PROGRAM_ID @ ErrorCode::InvalidProgramId,
+        constraint = meteora_program.executable @ ErrorCode::InvalidProgramId
     )]
-    pub jupiter_program: UncheckedAccount<'info>,
+    pub meteora_program: UncheckedAccount<'info>,
 
     pub token_program: Program<'info, Token>,
 }
 
 #[derive(Accounts)]
-pub struct ExecuteGovernanceProposal<'info> {
-    #[account(mut)]
-    pub executor: Signer<'info>,
-
-    #[account(mut)]
-    pub rift: Account<'info, Rift>,
+pub struct SetWrapCooldown<'info> {
+    pub creator: Signer<'info>,
 
-    /// **SECURITY FIX #43**: Governance proposal with PDA binding to prevent spoofing
-    /// Proposal must be derived from governance account to ensure it belongs to the correct governance
     #[account(
-        seeds = [b"proposal", governance.key().as_ref(), &proposal.id.to_le_bytes()],
-        bump,
-        seeds::program = governance::ID
+        mut,
+        constraint = rift.creator == creator.key() @ ErrorCode::Unauthorized
     )]
-    pub proposal: Account<'info, governance::Proposal>,
-
-    /// Governance state account
-    pub governance: Account<'info, governance::Governance>,
-}
-
-#[derive(Accounts)]
-pub struct EmergencyPause<'info> {
-    #[account(mut)]
-    pub governance_authority: Signer<'info>,
-    
-    #[account(mut)]
     pub rift: Account<'info, Rift>,
-    
-    /// Governance state for authorization
-    pub governance: Account<'info, governance::Governance>,
 }
 
 #[derive(Accounts)]
-pub struct EmergencyUnpause<'info> {
-    #[account(mut)]
-    pub governance_authority: Signer<'info>,
-    
-    #[account(mut)]
+pub struct SetMinWrapAmount<'info> {
+    pub creator: Signer<'info>,
+
+    #[account(
+        mut,
+        constraint = rift.creator == creator.key() @ ErrorCode::Unauthorized
+    )]
     pub rift: Account<'info, Rift>,
-    
-    /// Governance state for authorization
-    pub governance: Account<'info, governance::Governance>,
+
+    #[account(
+        constraint = underlying_mint.key() == rift.underlying_mint @ ErrorCode::InvalidMint
+    )]
+    pub underlying_mint: Account<'info, Mint>,
 }
 
 #[derive(Accounts)]
-pub struct CloseRift<'info> {
-    #[account(mut)]
+pub struct SetMinStakeDuration<'info> {
     pub creator: Signer<'info>,
 
     #[account(
         mut,
-        close = creator,
-        has_one = creator @ ErrorCode::UnauthorizedClose
+        constraint = rift.creator == creator.key() @ ErrorCode::Unauthorized
     )]
     pub rift: Account<'info, Rift>,
 }
 
 #[derive(Accounts)]
-pub struct AdminCloseRift<'info> {
-    #[account(mut)]
-    pub program_authority: Signer<'info>,
+pub struct SetUndercollateralizationConfig<'info> {
+    pub creator: Signer<'info>,
 
     #[account(
         mut,
-        close = program_authority
+        constraint = rift.creator == creator.key() @ ErrorCode::Unauthorized
     )]
     pub rift: Account<'info, Rift>,
 }
 
 #[derive(Accounts)]
-pub struct CleanupStuckAccounts<'info> {
-    /// The creator who originally tried to create the rift
-    /// **SECURITY FIX**: Require creator signature to prevent griefing
+pub struct SetKeeperReward<'info> {
     pub creator: Signer<'info>,
-    
-    /// The underlying mint that was used in the failed rift creation
-    pub underlying_mint: Account<'info, Mint>,
-    
-    /// The stuck rift mint account that needs to be cleaned up
+
     #[account(
         mut,
-        close = creator,
-        seeds = [b"rift_mint", expected_rift.key().as_ref()],
-        bump
+        constraint = rift.creator == creator.key() @ ErrorCode::Unauthorized
     )]
-    pub stuck_rift_mint: Account<'info, Mint>,
-    
-    /// The expected rift account location (should be empty/non-existent)
-    /// CHECK: We verify this account is empty to ensure it's truly stuck
+    pub rift: Account<'info, Rift>,
+}
+
+#[derive(Accounts)]
+pub struct SetOracleMinSources<'info> {
+    pub creator: Signer<'info>,
+
     #[account(
-        seeds = [b"rift", underlying_mint.key().as_ref(), creator.key().as_ref()],
-        constraint = underlying_mint.key() != Pubkey::default() && creator.key() != Pubkey::default() @ ErrorCode::InvalidSeedComponent,
-        bump
+        mut,
+        constraint = rift.creator == creator.key() @ ErrorCode::Unauthorized
     )]
-    pub expected_rift: UncheckedAccount<'info>,
-    
-    /// The account that will pay for the transaction (can be anyone)
-    #[account(mut)]
-    pub payer: Signer<'info>,
-    
-    pub system_program: Program<'info, System>,
+    pub rift: Account<'info, Rift>,
 }
 
 #[derive(Accounts)]
-pub struct UpdateJupiterProgramId<'info> {
-    /// **GOVERNANCE AUTHORITY**: Only governance can update Jupiter program ID
-    #[account(mut)]
-    pub governance_authority: Signer<'info>,
+pub struct SetMaxConfidenceBps<'info> {
+    pub creator: Signer<'info>,
 
     #[account(
         mut,
-        constraint = rift.creator == governance_authority.key() @ ErrorCode::UnauthorizedAdmin
+        constraint = rift.creator == creator.key() @ ErrorCode::Unauthorized
     )]
     pub rift: Account<'info, Rift>,
 }
 
-/// **SECURITY FIX #50**: Account struct for setting oracle addresses
 #[derive(Accounts)]
-pub struct SetOracleAccounts<'info> {
-    #[account(mut)]
+pub struct SetBootstrapCap<'info> {
     pub creator: Signer<'info>,
 
     #[account(
@@ -3171,6 +8049,134 @@ pub struct Rift {
     
     // Governance Integration
     pub last_governance_update: i64,   // Timestamp of last governance parameter update
+
+    // Sliding 24h volume window
+    pub volume_window_start: i64,      // Last time total_volume_24h was decayed
+
+    // Zero-fee wrap handling
+    pub zero_fee_policy: ZeroFeePolicy, // What to do when the wrap fee rounds to zero
+
+    // Governance-controlled treasury split
+    pub treasury_fee_bps: u16,         // Share of post burn/partner fees sent to treasury (default 500 = 5%)
+
+    // LP staking reward formula
+    pub reward_formula_version: u8,    // Reward formula version stakers must be on to accrue
+
+    // Launch guardrail
+    pub max_total_underlying: u64,     // Deposit cap on total_underlying_wrapped (0 = unlimited)
+
+    // Private phase allowlist
+    pub whitelist_merkle_root: Option<[u8; 32]>, // Merkle root of allowed wrappers (None = open)
+
+    // Staking reward claims
+    pub min_claimable_rewards: u64,    // Claims below this stay pending (0 = no minimum)
+
+    // LP staker loyalty discount
+    pub staker_fee_discount_bps: u16,  // Discount on wrap/unwrap fee for qualifying stakers (10000 = free)
+    pub staker_discount_min_stake: u64, // Minimum staked LP to qualify for the discount
+
+    // LP staking reward rate
+    pub reward_rate_per_hour: u64,     // RIFTS per staked LP per hour, scaled by REWARD_RATE_PRECISION
+
+    // Staking reward vesting
+    pub reward_vest_period: i64,       // Linear vesting period for claimed rewards in seconds (0 = no vesting)
+
+    // Oracle staleness
+    pub max_oracle_staleness: i64,     // Max oracle price age in seconds (60-3600, default 300)
+
+    // Oracle aggregation
+    pub use_confidence_weighting: bool, // Rebalance on the confidence-weighted price instead of the mean
+
+    // LP staking reward budget
+    pub rifts_reward_budget: u64,      // RIFTS deposited in reward_budget_vault and not yet paid out
+
+    // Emergency pause details
+    pub pause_reason: Option<PauseReason>, // Why the rift was paused (None when not paused)
+    pub pause_auto_unpause_at: i64,    // Pause self-clears for wrap/unwrap after this time (0 = manual only)
+
+    // Pool exit guardrail
+    pub max_liquidity_removal_per_tx: u128, // Max Meteora liquidity_delta removed per unwrap (0 = uncapped)
+
+    // Meteora fee tier
+    pub pool_config: Option<Pubkey>,   // Allowlisted Meteora config the bound pool was created with
+
+    // Live Meteora pool snapshot (sync_pool_metadata)
+    pub pool_sqrt_price: u128,         // Pool sqrt price (Q64.64) at last sync
+    pub pool_liquidity: u128,          // Pool liquidity at last sync
+    pub last_pool_sync: i64,           // When pool metadata was last synced (0 = never)
+
+    // Anti-MEV unwrap cooldown
+    pub wrap_cooldown_seconds: i64,    // Min time between a user's wrap and their next unwrap (0 = disabled)
+
+    // Backing history
+    pub backing_ratio_high_water: u64, // Highest backing_ratio ever set by a rebalance (or the initial ratio)
+
+    // Staking lock
+    pub min_stake_duration: i64,       // Seconds LP must stay staked before unstaking (0 = no lock)
+
+    // Undercollateralization alarm
+    pub undercollateralization_floor: u64,    // backing_ratio below this emits UndercollateralizationWarning
+    pub pause_on_undercollateralization: bool, // Auto-pause the rift when the floor is breached
+
+    // Keeper incentive
+    pub keeper_reward: u64,            // RIFTS paid from rifts_reward_budget per trigger_rebalance (0 = disabled)
+    pub last_keeper_reward_time: i64,  // Last keeper payout; limited to one per oracle_update_interval
+
+    // RIFTS buyback split
+    pub rifts_lp_share_bps: u16,       // Bought-back RIFTS sent to LP stakers (default 9000 = 90%)
+    pub rifts_burn_share_bps: u16,     // Bought-back RIFTS burned (default 1000 = 10%)
+
+    // Fee timelock
+    pub pending_fee_changes: Option<PendingFeeChanges>, // Fee increases awaiting apply_pending_rift_changes
+
+    // Oracle quorum
+    pub oracle_min_sources: u8,        // Distinct fresh oracle feeds required to rebalance (1 = no quorum)
+
+    // Fee-free bootstrap liquidity
+    pub bootstrap_cap: u64,            // Max RIFT the creator may bootstrap_mint (0 = disabled)
+    pub bootstrap_minted: u64,         // RIFT minted through bootstrap_mint so far
+    pub bootstrap_complete: bool,      // Set by the first public wrap; bootstrap_mint is then disabled
+
+    // Dust protection
+    pub min_wrap_amount: u64,          // Smallest accepted wrap (default DEFAULT_MIN_WRAP_AMOUNT)
+
+    // Segregated wrap fees
+    pub fee_vault: Pubkey,             // Underlying fee vault PDA (default until initialize_fee_vault)
+    pub accrued_underlying_fees: u64,  // Wrap fees held in fee_vault awaiting distribution
+
+    // Oracle data quality
+    pub max_confidence_bps: u16,       // Reject oracle updates with confidence above this share of price
+
+    // Admin close notice
+    pub close_proposed_at: i64,        // When the admin proposed closing this rift (0 = no pending close)
+
+    // Pool creation config
+    pub meteora_config_key: Option<Pubkey>, // Meteora config for create_meteora_pool (None = DEFAULT_METEORA_CONFIG)
+
+    // Burn destination
+    pub burn_mode: BurnMode,           // How unwrap paths dispose of redeemed RIFT
+    pub incinerator: Option<Pubkey>,   // Incinerator owner for SendToIncinerator (None = Solana incinerator)
+    pub total_incinerated: u64,        // RIFT sent to the incinerator; still counted in mint supply
+
+    // Delegated oracle pushing
+    pub oracle_pushers: [Pubkey; MAX_ORACLE_PUSHERS], // Keepers allowed to push oracle prices (default = empty slot)
+
+    // Gradual growth
+    pub max_wrap_pct_of_tvl_bps: u16,  // Largest single wrap as a share of total_underlying_wrapped (0 = disabled)
+    pub wrap_tvl_cap_threshold: u64,   // total_underlying_wrapped below which the share cap is not enforced
+
+    // LP staking
+    pub lp_token_mint: Option<Pubkey>, // LP token accepted by stake/unstake_lp_tokens (["lp_mint", rift] PDA, set by initialize_pool)
+
+    // Standalone pause control
+    pub pause_authority: Option<Pubkey>, // May emergency_pause without a governance account, and unpause its own pauses
+    pub paused_by: Option<Pubkey>,       // Signer of the active emergency_pause (None when unpaused or auto-paused)
+
+    // Treasury destination
+    pub treasury: Option<Pubkey>, // Only token account process_fee_distribution may pay the treasury cut to
+
+    // Protocol position fees
+    pub protocol_fee_mode: ProtocolFeeMode, // Which of claim_meteora_fees / compound_protocol_liquidity may run
 }
 
 impl Rift {
@@ -3196,7 +8202,441 @@ pub struct StakerAccount {
     pub total_staked: u64,                // Total LP tokens ever staked
     pub total_rewards_claimed: u64,       // Total RIFTS rewards claimed
     pub last_reward_update: i64,          // Last reward calculation timestamp
-    pub stake_start_time: i64,            // When staking started
+    pub stake_start_time: i64,            // When the most recent stake was made
+    pub reward_formula_version: u8,       // Reward formula version rewards are accrued under
+    pub frozen_seconds_checkpoint: i64,   // Governance frozen seconds at last_reward_update
+}
+
+impl StakerAccount {
+    /// Rewards accrued since last_reward_update under the staker's formula version
+    /// Rewards = staked_amount * whole_hours * reward_rate_per_hour / 1e11
+    /// Time governance spent frozen or paused since the last update is not rewarded
+    pub fn accrued_rewards(
+        &self,
+        current_time: i64,
+        reward_rate_per_hour: u64,
+        frozen_seconds: i64,
+    ) -> Result<u64> {
+        let frozen_elapsed = frozen_seconds
+            .checked_sub(self.frozen_seconds_checkpoint)
+            .ok_or(ErrorCode::MathOverflow)?
+            .max(0);
+        let time_elapsed_i64 = current_time
+            .checked_sub(self.last_reward_update)
+            .ok_or(ErrorCode::MathOverflow)?
+            .saturating_sub(frozen_elapsed)
+            .max(0);
+        let time_elapsed = u64::try_from(time_elapsed_i64)
+            .map_err(|_| ErrorCode::MathOverflow)?;
+
+        if self.staked_amount == 0 || time_elapsed == 0 {
+            return Ok(0);
+        }
+
+        let time_hours = time_elapsed
+            .checked_div(3600)
+            .ok_or(ErrorCode::MathOverflow)?;
+        let rewards = (self.staked_amount as u128)
+            .checked_mul(time_hours as u128)
+            .ok_or(ErrorCode::MathOverflow)?
+            .checked_mul(reward_rate_per_hour as u128)
+            .ok_or(ErrorCode::MathOverflow)?
+            .checked_div(REWARD_RATE_PRECISION)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        u64::try_from(rewards).map_err(|_| ErrorCode::MathOverflow.into())
+    }
+
+    /// Move accrued rewards into pending_rewards and restart the accrual clock
+    pub fn accrue_rewards(
+        &mut self,
+        current_time: i64,
+        reward_rate_per_hour: u64,
+        frozen_seconds: i64,
+    ) -> Result<u64> {
+        let new_rewards = self.accrued_rewards(current_time, reward_rate_per_hour, frozen_seconds)?;
+
+        self.pending_rewards = self.pending_rewards
+            .checked_add(new_rewards)
+            .ok_or(ErrorCode::MathOverflow)?;
+        self.last_reward_update = current_time;
+        self.frozen_seconds_checkpoint = frozen_seconds;
+
+        Ok(new_rewards)
+    }
+}
+
+/// Per-user escrow of claimed staking rewards released linearly over reward_vest_period
+#[account]
+pub struct RewardVesting {
+    pub user: Pubkey,                     // Reward recipient
+    pub rift: Pubkey,                     // Associated rift
+    pub escrow: Pubkey,                   // Token account holding unreleased rewards
+    pub carried_amount: u64,              // Vested from earlier schedules, not yet claimed
+    pub locked_amount: u64,               // Amount in the current linear schedule
+    pub released_from_locked: u64,        // Portion of locked_amount already claimed
+    pub vest_start: i64,                  // Current schedule start
+    pub vest_end: i64,                    // Current schedule end
+    pub total_claimed: u64,               // Total rewards released to the user
+    pub bump: u8,
+}
+
+impl RewardVesting {
+    /// Amount of locked_amount vested at current_time
+    pub fn vested_from_locked(&self, current_time: i64) -> Result<u64> {
+        if current_time >= self.vest_end {
+            return Ok(self.locked_amount);
+        }
+        if current_time <= self.vest_start {
+            return Ok(0);
+        }
+
+        let elapsed = current_time
+            .checked_sub(self.vest_start)
+            .ok_or(ErrorCode::MathOverflow)?;
+        let duration = self.vest_end
+            .checked_sub(self.vest_start)
+            .ok_or(ErrorCode::MathOverflow)?;
+        let vested = (self.locked_amount as u128)
+            .checked_mul(elapsed as u128)
+            .ok_or(ErrorCode::MathOverflow)?
+            .checked_div(duration as u128)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        u64::try_from(vested).map_err(|_| ErrorCode::MathOverflow.into())
+    }
+
+    /// Amount the user can withdraw at current_time
+    pub fn claimable(&self, current_time: i64) -> Result<u64> {
+        self.vested_from_locked(current_time)?
+            .checked_sub(self.released_from_locked)
+            .and_then(|v| v.checked_add(self.carried_amount))
+            .ok_or(ErrorCode::MathOverflow.into())
+    }
+
+    /// Add newly claimed rewards, restarting the schedule for everything still unvested
+    pub fn add_rewards(&mut self, amount: u64, current_time: i64, vest_period: i64) -> Result<()> {
+        let vested = self.vested_from_locked(current_time)?;
+
+        // Already-vested rewards stay claimable
+        self.carried_amount = self.claimable(current_time)?;
+        self.locked_amount = self.locked_amount
+            .checked_sub(vested)
+            .and_then(|v| v.checked_add(amount))
+            .ok_or(ErrorCode::MathOverflow)?;
+        self.released_from_locked = 0;
+        self.vest_start = current_time;
+        self.vest_end = current_time
+            .checked_add(vest_period)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        Ok(())
+    }
+
+    /// Mark everything vested at current_time as released and return the amount
+    pub fn claim(&mut self, current_time: i64) -> Result<u64> {
+        let claimable = self.claimable(current_time)?;
+
+        self.released_from_locked = self.vested_from_locked(current_time)?;
+        self.carried_amount = 0;
+        self.total_claimed = self.total_claimed
+            .checked_add(claimable)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        Ok(claimable)
+    }
+}
+
+/// Per-user wrap timestamp backing the anti-MEV unwrap cooldown
+#[account]
+pub struct WrapRecord {
+    pub user: Pubkey,
+    pub rift: Pubkey,
+    pub last_wrap_time: i64,
+    pub bump: u8,
+}
+
+impl WrapRecord {
+    pub const LEN: usize = 8 + 32 + 32 + 8 + 1;
+
+    pub fn record_wrap(&mut self, user: Pubkey, rift: Pubkey, current_time: i64, bump: u8) {
+        self.user = user;
+        self.rift = rift;
+        self.last_wrap_time = current_time;
+        self.bump = bump;
+    }
+
+    /// Last wrap time from a (possibly uninitialized) wrap record PDA; 0 if the user never wrapped
+    pub fn last_wrap_time_of(info: &AccountInfo) -> Result<i64> {
+        if info.owner != &crate::ID || info.data_is_empty() {
+            return Ok(0);
+        }
+        let record = WrapRecord::try_deserialize(&mut &info.data.borrow()[..])?;
+        Ok(record.last_wrap_time)
+    }
+}
+
+/// Highest proposal id a governance has applied to a rift for one effect
+/// Seeded by (rift, governance, effect) so each governance and effect has its own id sequence
+#[account]
+pub struct AppliedProposalRecord {
+    pub last_proposal_id: Option<u64>, // None until the first (governance ids start at 0)
+    pub bump: u8,
+}
+
+impl AppliedProposalRecord {
+    pub const LEN: usize = 8 + 9 + 1;
+
+    /// Record proposal_id as applied, rejecting replays and out-of-order ids
+    pub fn record(&mut self, proposal_id: u64, bump: u8) -> Result<()> {
+        require!(
+            self.last_proposal_id.map_or(true, |last| proposal_id > last),
+            ErrorCode::ProposalAlreadyApplied
+        );
+        self.last_proposal_id = Some(proposal_id);
+        self.bump = bump;
+        Ok(())
+    }
+}
+
+/// Protocol-wide settings; the admin is rotated only through governance proposals
+#[account]
+pub struct ProtocolConfig {
+    pub protocol_admin: Pubkey, // Authority for admin_* emergency instructions
+    pub governance: Pubkey,     // Canonical governance account whose proposals may rotate protocol_admin
+    pub bump: u8,
+}
+
+impl ProtocolConfig {
+    pub const LEN: usize = 8 + 32 + 32 + 1;
+}
+
+/// Global rift creation gate; absent or unset means creation is permissionless
+#[account]
+pub struct RiftFactoryConfig {
+    pub required_collection_mint: Option<Pubkey>, // Verified collection creators must hold an NFT of
+    pub bump: u8,
+}
+
+impl RiftFactoryConfig {
+    pub const LEN: usize = 8 + 33 + 1;
+}
+
+/// Index of every created rift; entries live in RiftRegistryPage PDAs of RIFTS_PER_REGISTRY_PAGE
+#[account]
+pub struct RiftRegistry {
+    pub rift_count: u64, // Total rifts registered; page index = rift_count / RIFTS_PER_REGISTRY_PAGE
+    pub bump: u8,
+}
+
+impl RiftRegistry {
+    pub const LEN: usize = 8 + 8 + 1;
+
+    /// Page the next rift is appended to
+    pub fn current_page(&self) -> u64 {
+        self.rift_count / RIFTS_PER_REGISTRY_PAGE as u64
+    }
+
+    /// Append `rift` to `page`, which must be the PDA for current_page()
+    pub fn register(
+        &mut self,
+        page: &mut RiftRegistryPage,
+        rift: Pubkey,
+        registry_bump: u8,
+        page_bump: u8,
+    ) -> Result<()> {
+        require!(page.rifts.len() < RIFTS_PER_REGISTRY_PAGE, ErrorCode::RegistryPageFull);
+
+        if page.rifts.is_empty() {
+            page.page_index = self.current_page();
+            page.bump = page_bump;
+        }
+        page.rifts.push(rift);
+
+        self.bump = registry_bump;
+        self.rift_count = self.rift_count
+            .checked_add(1)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        Ok(())
+    }
+}
+
+/// One page of the rift registry, seeds [b"rift_registry_page", page_index]
+#[account]
+pub struct RiftRegistryPage {
+    pub page_index: u64,
+    pub rifts: Vec<Pubkey>, // Up to RIFTS_PER_REGISTRY_PAGE, in creation order
+    pub bump: u8,
+}
+
+impl RiftRegistryPage {
+    pub const LEN: usize = 8 + 8 + 4 + 32 * RIFTS_PER_REGISTRY_PAGE + 1;
+}
+
+/// Governance-maintained set of Meteora config keys (fee tiers) rifts may create pools with
+#[account]
+pub struct MeteoraConfigAllowlist {
+    pub configs: Vec<Pubkey>, // Up to MAX_METEORA_CONFIGS
+    pub initialized: bool,    // Set on first add_meteora_config, which seeds DEFAULT_METEORA_CONFIG
+    pub bump: u8,
+}
+
+impl MeteoraConfigAllowlist {
+    pub const LEN: usize = 8 + 4 + 32 * MAX_METEORA_CONFIGS + 1 + 1;
+
+    pub fn is_allowed(&self, config: &Pubkey) -> bool {
+        self.configs.contains(config)
+    }
+}
+
+/// Token amounts process_fee_distribution routes for a given fee, returned by preview_fee_split
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct FeeSplit {
+    pub burn_amount: u64,
+    pub partner_amount: u64,              // 0 when no partner_wallet is set
+    pub treasury_amount: u64,
+    pub fee_collector_amount: u64,
+}
+
+/// Fee destinations for a rift, each share expressed in bps of the collected wrap fee
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct FeeRouting {
+    pub rift: Pubkey,
+    pub wrap_fee_bps: u16,                // Fee charged on wrap/unwrap (bps of amount)
+    pub burn_bps: u16,                    // Burned
+    pub partner_bps: u16,                 // Sent to partner_wallet
+    pub partner_wallet: Option<Pubkey>,
+    pub treasury_bps: u16,                // Sent to treasury
+    pub fee_collector_bps: u16,           // Sent to fee collector for RIFTS buyback
+    pub lp_staker_bps: u16,               // Portion of fee_collector_bps paid to LP stakers
+    pub rifts_burn_bps: u16,              // Portion of fee_collector_bps burned as RIFTS
+}
+
+/// Addresses derived by create_rift and create_rift_with_vanity_pda, returned via return data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct CreatedRiftAddresses {
+    pub rift: Pubkey,
+    pub rift_mint: Pubkey,
+    pub vault: Pubkey,
+    pub rift_mint_authority: Pubkey,
+}
+
+/// Oracle and rebalance timers for a rift, returned via return data (countdowns clamp at 0)
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct Countdowns {
+    pub oracle_countdown: i64,
+    pub rebalance_countdown: i64,
+    pub last_oracle_update: i64,
+    pub last_rebalance: i64,
+    pub rebalance_count: u32,
+}
+
+/// Oracle freshness snapshot for a rift, returned via return data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct OracleHealth {
+    pub rift: Pubkey,
+    pub seconds_since_update: i64,        // Seconds since last_oracle_update
+    pub oracle_countdown: i64,            // get_oracle_countdown value
+    pub newest_sample_fresh: bool,        // Newest buffered sample within max_oracle_staleness
+    pub average_price: u64,               // Average of buffered samples (0 if none)
+    pub sample_count: u8,                 // Populated samples in oracle_prices
+}
+
+/// Oracle price buffer resolved from the ring, returned via return data
+/// Borsh layout: price_index (u8), then 10 PriceData {price u64, confidence u64, timestamp i64, source Option<OracleType>}
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct OracleHistory {
+    pub price_index: u8,                  // Raw ring write index (next slot to overwrite)
+    pub samples: [PriceData; 10],         // Oldest first; unfilled slots have timestamp 0
+}
+
+/// Result of check_invariants, returned via return data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct InvariantStatus {
+    pub rift: Pubkey,
+    pub rift_mint_supply: u64,
+    pub total_rift_minted: u64,
+    /// Mint supply <= total_rift_minted + total_incinerated. Not equality: holders can
+    /// SPL-burn their own RIFT, which shrinks supply without touching rift accounting
+    pub supply_within_accounting: bool,
+}
+
+/// Result of get_breakeven_estimate, returned via return data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct BreakevenEstimate {
+    pub amount: u64,
+    pub wrap_fee: u64,
+    pub unwrap_fee: u64,
+    pub total_fees: u64,
+    pub rewards_per_hour: u64,              // RIFTS accrued per hour on the wrapped amount
+    pub seconds_to_breakeven: Option<u64>,  // None if rewards never exceed fees (zero rate)
+}
+
+/// Result of verify_pool_state, returned via return data
+#[derive(AnchorSerialize, AnchorDeserialize, Clone)]
+pub struct PoolStateReport {
+    pub authority_matches: bool,
+    pub token_a_vault_matches: bool,
+    pub token_b_vault_matches: bool,
+    pub live_authority: Pubkey,
+    pub live_token_a_vault: Pubkey,
+    pub live_token_b_vault: Pubkey,
+}
+
+impl PoolStateReport {
+    pub fn is_consistent(&self) -> bool {
+        self.authority_matches && self.token_a_vault_matches && self.token_b_vault_matches
+    }
+}
+
+/// Governance fee increases buffered on the rift until `effective_at`
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, Debug, Default)]
+pub struct PendingFeeChanges {
+    pub burn_fee_bps: Option<u16>,
+    pub partner_fee_bps: Option<u16>,
+    pub treasury_fee_bps: Option<u16>,
+    pub effective_at: i64,
+}
+
+impl PendingFeeChanges {
+    pub fn is_empty(&self) -> bool {
+        self.burn_fee_bps.is_none() && self.partner_fee_bps.is_none() && self.treasury_fee_bps.is_none()
+    }
+}
+
+/// Reason recorded by emergency_pause
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum PauseReason {
+    OracleFailure,
+    PoolCompromise,
+    Exploit,
+    Maintenance,
+    Undercollateralized,
+}
+
+/// How unwrapped RIFT leaves circulation
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum BurnMode {
+    #[default]
+    RealBurn,         // Burn via the token program, reducing mint supply
+    SendToIncinerator, // Transfer to the incinerator, keeping mint supply fixed
+}
+
+/// What happens to trading fees on the protocol-owned Meteora position
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum ProtocolFeeMode {
+    #[default]
+    Claim,    // claim_meteora_fees sweeps them into the fee vault
+    Compound, // compound_protocol_liquidity re-adds them as liquidity
+}
+
+/// Behavior when `amount * WRAP_FEE_BPS / 10000` rounds to zero
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq, Debug, Default)]
+pub enum ZeroFeePolicy {
+    #[default]
+    ChargeMinimum, // Charge a minimum fee of 1 base unit
+    Reject,        // Reject the wrap with FeeTooSmall
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
@@ -3205,10 +8645,121 @@ pub struct PriceData {
     pub price: u64,
     pub confidence: u64,
     pub timestamp: i64,
+    pub source: Option<OracleType>, // Feed that produced the sample (None for seeded samples)
+}
+
+/// Rift fields before oracle_prices, unchanged since the first release; read only by migrate_rift
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyRiftHead {
+    pub name: [u8; 32],
+    pub creator: Pubkey,
+    pub underlying_mint: Pubkey,
+    pub rift_mint: Pubkey,
+    pub vault: Pubkey,
+    pub burn_fee_bps: u16,
+    pub partner_fee_bps: u16,
+    pub partner_wallet: Option<Pubkey>,
+    pub total_underlying_wrapped: u64,
+    pub total_rift_minted: u64,
+    pub total_burned: u64,
+    pub backing_ratio: u64,
+    pub last_rebalance: i64,
+    pub created_at: i64,
+}
+
+/// PriceData before `source` was recorded
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy)]
+pub struct LegacyPriceData {
+    pub price: u64,
+    pub confidence: u64,
+    pub timestamp: i64,
+}
+
+impl From<LegacyPriceData> for PriceData {
+    fn from(legacy: LegacyPriceData) -> Self {
+        Self {
+            price: legacy.price,
+            confidence: legacy.confidence,
+            timestamp: legacy.timestamp,
+            source: None,
+        }
+    }
+}
+
+/// Rift fields from price_index through last_governance_update, unchanged since the first release
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyRiftTail {
+    pub price_index: u8,
+    pub oracle_update_interval: i64,
+    pub max_rebalance_interval: i64,
+    pub arbitrage_threshold_bps: u16,
+    pub last_oracle_update: i64,
+    pub total_volume_24h: u64,
+    pub price_deviation: u64,
+    pub arbitrage_opportunity_bps: u16,
+    pub jupiter_program_id: Option<Pubkey>,
+    pub rebalance_count: u32,
+    pub total_fees_collected: u64,
+    pub rifts_tokens_distributed: u64,
+    pub rifts_tokens_burned: u64,
+    pub liquidity_pool: Option<Pubkey>,
+    pub lp_token_supply: u64,
+    pub pool_trading_fee_bps: u16,
+    pub total_liquidity_underlying: u64,
+    pub total_liquidity_rift: u64,
+    pub active_bin_id: i32,
+    pub bin_step: u16,
+    pub pool_authority: Option<Pubkey>,
+    pub pool_token_a_vault: Option<Pubkey>,
+    pub pool_token_b_vault: Option<Pubkey>,
+    pub pool_underlying: Option<Pubkey>,
+    pub pool_rift: Option<Pubkey>,
+    pub pyth_price_account: Option<Pubkey>,
+    pub switchboard_feed_account: Option<Pubkey>,
+    pub total_lp_staked: u64,
+    pub pending_rewards: u64,
+    pub last_reward_distribution: i64,
+    pub reentrancy_guard: bool,
+    pub is_paused: bool,
+    pub pause_timestamp: i64,
+    pub pending_fee_distribution: u64,
+    pub last_governance_update: i64,
+}
+
+/// Decode a pre-migration Rift account and rebuild it in the current layout
+pub fn migrate_legacy_rift(data: &[u8], current_time: i64) -> Result<Box<Rift>> {
+    require!(
+        data.len() >= 8 && data[..8] == *Rift::DISCRIMINATOR,
+        ErrorCode::InvalidLegacyRift
+    );
+    let mut legacy: &[u8] = &data[8..];
+    let head = LegacyRiftHead::deserialize(&mut legacy)
+        .map_err(|_| ErrorCode::InvalidLegacyRift)?;
+    let prices = <[LegacyPriceData; 10]>::deserialize(&mut legacy)
+        .map_err(|_| ErrorCode::InvalidLegacyRift)?;
+    let tail = LegacyRiftTail::deserialize(&mut legacy)
+        .map_err(|_| ErrorCode::InvalidLegacyRift)?;
+
+    // Legacy fields re-encoded in the current layout; appended fields start zeroed
+    let mut bytes = Rift::DISCRIMINATOR.to_vec();
+    head.serialize(&mut bytes)?;
+    prices.map(PriceData::from).serialize(&mut bytes)?;
+    tail.serialize(&mut bytes)?;
+    bytes.resize(8 + std::mem::size_of::<Rift>(), 0);
+
+    let mut rift = Box::new(Rift::try_deserialize(&mut &bytes[..])?);
+    rift.init_extended_fields(current_time);
+    Ok(rift)
 }
 
 impl Rift {
-    pub fn add_price_data(&mut self, price: u64, confidence: u64, timestamp: i64) -> Result<()> {
+    pub fn add_price_data(
+        &mut self,
+        price: u64,
+        confidence: u64,
+        timestamp: i64,
+        source: OracleType,
+    ) -> Result<()> {
         // **CRITICAL SECURITY FIX**: Validate timestamp bounds to prevent manipulation
         let current_time = Clock::get()?.unix_timestamp;
 
@@ -3218,9 +8769,9 @@ impl Rift {
             ErrorCode::InvalidTimestamp
         );
 
-        // Reject timestamps older than 5 minutes (300 seconds)
+        // Reject timestamps older than the rift's staleness window
         require!(
-            timestamp >= current_time - 300,
+            timestamp >= current_time - self.max_oracle_staleness,
             ErrorCode::InvalidTimestamp
         );
 
@@ -3228,12 +8779,24 @@ impl Rift {
             price,
             confidence,
             timestamp,
+            source: Some(source),
         };
         self.price_index = (self.price_index + 1) % 10;
         self.last_oracle_update = timestamp;
         Ok(())
     }
     
+    /// Distinct feed types with a sample inside the max_oracle_staleness window
+    pub fn fresh_oracle_sources(&self, current_time: i64) -> u8 {
+        let fresh_after = current_time.saturating_sub(self.max_oracle_staleness);
+        let fresh = |source: OracleType| {
+            self.oracle_prices
+                .iter()
+                .any(|sample| sample.source == Some(source) && sample.timestamp >= fresh_after)
+        };
+        u8::from(fresh(OracleType::Pyth)) + u8::from(fresh(OracleType::Switchboard))
+    }
+
     pub fn should_trigger_rebalance(&self, current_time: i64) -> Result<bool> {
         // **CRITICAL SECURITY FIX**: Validate current_time to prevent timestamp manipulation
         let actual_current_time = Clock::get()?.unix_timestamp;
@@ -3281,7 +8844,7 @@ impl Rift {
         Ok(current_time - self.last_oracle_update > self.oracle_update_interval)
     }
     
-    pub fn trigger_automatic_rebalance(&mut self, current_time: i64) -> Result<()> {
+    pub fn trigger_automatic_rebalance(&mut self, rift_key: Pubkey, current_time: i64) -> Result<()> {
         // **CRITICAL SECURITY FIX**: Validate current_time to prevent timestamp manipulation
         let actual_current_time = Clock::get()?.unix_timestamp;
         require!(
@@ -3289,7 +8852,21 @@ impl Rift {
             ErrorCode::InvalidTimestamp
         );
 
-        let avg_price = self.get_average_oracle_price()?;
+        // Skip rather than rebalance on thin data when the quorum isn't met
+        if self.oracle_min_sources > 1 {
+            let fresh_sources = self.fresh_oracle_sources(current_time);
+            if fresh_sources < self.oracle_min_sources {
+                msg!("Rebalance skipped: {} fresh oracle sources, {} required",
+                     fresh_sources, self.oracle_min_sources);
+                return Ok(());
+            }
+        }
+
+        let avg_price = if self.use_confidence_weighting {
+            self.get_confidence_weighted_price(current_time)?
+        } else {
+            self.get_average_oracle_price()?
+        };
         
         // **CRITICAL FIX**: Validate oracle price before updating backing ratio
         require!(avg_price > 0, ErrorCode::InvalidOraclePrice);
@@ -3302,6 +8879,32 @@ impl Rift {
         } else {
             return Err(ErrorCode::InvalidOraclePrice.into());
         }
+
+        // Track the highest backing ratio ever reached
+        if self.backing_ratio > self.backing_ratio_high_water {
+            self.backing_ratio_high_water = self.backing_ratio;
+        }
+
+        // Alarm when RIFT holders become undercollateralized
+        if self.backing_ratio < self.undercollateralization_floor {
+            let auto_paused = self.pause_on_undercollateralization && !self.is_paused;
+            if auto_paused {
+                self.is_paused = true;
+                self.pause_timestamp = current_time;
+                self.pause_reason = Some(PauseReason::Undercollateralized);
+                self.pause_auto_unpause_at = 0;
+                self.paused_by = None;
+            }
+
+            emit!(UndercollateralizationWarning {
+                rift: rift_key,
+                backing_ratio: self.backing_ratio,
+                oracle_price: avg_price,
+                floor: self.undercollateralization_floor,
+                auto_paused,
+                timestamp: current_time,
+            });
+        }
         
         self.last_rebalance = current_time;
         self.rebalance_count = self.rebalance_count
@@ -3312,8 +8915,38 @@ impl Rift {
         self.arbitrage_opportunity_bps = 0; // Reset after rebalance
         self.price_deviation = 0;
 
-        // **NEW FEATURE**: Reset volume counter after rebalance for volatility farming
-        self.total_volume_24h = 0; // Reset volume tracking
+        // Volume is no longer reset here - total_volume_24h decays over a sliding window
+
+        Ok(())
+    }
+
+    /// Decay total_volume_24h by the elapsed fraction of a 24h window, then add new volume
+    /// Keeps total_volume_24h approximating a sliding 24-hour total instead of a counter
+    pub fn record_volume(&mut self, amount: u64, current_time: i64) -> Result<()> {
+        const VOLUME_WINDOW_SECONDS: i64 = 86400; // 24 hours
+
+        let elapsed = current_time
+            .checked_sub(self.volume_window_start)
+            .ok_or(ErrorCode::MathOverflow)?
+            .max(0);
+
+        if elapsed >= VOLUME_WINDOW_SECONDS {
+            self.total_volume_24h = 0;
+        } else if elapsed > 0 {
+            // Use u128 to avoid overflow on volume * elapsed
+            let decayed = u128::from(self.total_volume_24h)
+                .checked_mul(elapsed as u128)
+                .ok_or(ErrorCode::MathOverflow)?
+                .checked_div(VOLUME_WINDOW_SECONDS as u128)
+                .ok_or(ErrorCode::MathOverflow)?;
+            let decayed = u64::try_from(decayed).map_err(|_| ErrorCode::MathOverflow)?;
+            self.total_volume_24h = self.total_volume_24h.saturating_sub(decayed);
+        }
+
+        self.volume_window_start = current_time;
+        self.total_volume_24h = self.total_volume_24h
+            .checked_add(amount)
+            .ok_or(ErrorCode::MathOverflow)?;
 
         Ok(())
     }
@@ -3339,109 +8972,677 @@ impl Rift {
             // Scale by 1,000,000 (6 decimal places) before division to prevent truncation bias
             const PRECISION_SCALE: u128 = 1_000_000;
 
-            let scaled_total = total_price
-                .checked_mul(PRECISION_SCALE)
-                .ok_or(ErrorCode::MathOverflow)?;
+            let scaled_total = total_price
+                .checked_mul(PRECISION_SCALE)
+                .ok_or(ErrorCode::MathOverflow)?;
+
+            let scaled_avg = scaled_total
+                .checked_div(u128::from(count))
+                .ok_or(ErrorCode::MathOverflow)?;
+
+            // Convert back to u64 with proper precision preservation
+            let avg_price = scaled_avg
+                .checked_div(PRECISION_SCALE)
+                .ok_or(ErrorCode::MathOverflow)?;
+
+            let final_price = u64::try_from(avg_price)
+                .map_err(|_| ErrorCode::MathOverflow)?;
+
+            // **CRITICAL FIX**: Validate average price is reasonable
+            require!(final_price > 0, ErrorCode::InvalidOraclePrice);
+            require!(final_price <= 1_000_000_000_000, ErrorCode::OraclePriceTooLarge);
+
+            Ok(final_price)
+        } else {
+            // **CRITICAL FIX**: Validate fallback backing ratio
+            require!(self.backing_ratio > 0, ErrorCode::InvalidBackingRatio);
+            Ok(self.backing_ratio) // Fallback to current backing ratio
+        }
+    }
+    
+    /// Average of fresh oracle samples weighted by 1 / confidence (6-decimal scale)
+    /// Tight quotes dominate wide ones; falls back to backing_ratio when no fresh samples exist
+    pub fn get_confidence_weighted_price(&self, current_time: i64) -> Result<u64> {
+        // Weight = WEIGHT_SCALE / max(confidence, MIN_CONFIDENCE) in fixed point
+        const WEIGHT_SCALE: u128 = 1_000_000_000_000;
+        const MIN_CONFIDENCE: u64 = 1; // Floor to avoid divide-by-zero
+
+        let mut weighted_total = 0u128;
+        let mut total_weight = 0u128;
+
+        for price_data in &self.oracle_prices {
+            if price_data.timestamp <= 0
+                || current_time.saturating_sub(price_data.timestamp) > self.max_oracle_staleness
+            {
+                continue;
+            }
+
+            let weight = WEIGHT_SCALE
+                .checked_div(u128::from(price_data.confidence.max(MIN_CONFIDENCE)))
+                .ok_or(ErrorCode::MathOverflow)?;
+            weighted_total = weighted_total
+                .checked_add(
+                    u128::from(price_data.price)
+                        .checked_mul(weight)
+                        .ok_or(ErrorCode::MathOverflow)?
+                )
+                .ok_or(ErrorCode::MathOverflow)?;
+            total_weight = total_weight
+                .checked_add(weight)
+                .ok_or(ErrorCode::MathOverflow)?;
+        }
+
+        if total_weight > 0 {
+            let weighted_price = weighted_total
+                .checked_div(total_weight)
+                .ok_or(ErrorCode::MathOverflow)?;
+            let final_price = u64::try_from(weighted_price)
+                .map_err(|_| ErrorCode::MathOverflow)?;
+
+            require!(final_price > 0, ErrorCode::InvalidOraclePrice);
+            require!(final_price <= 1_000_000_000_000, ErrorCode::OraclePriceTooLarge);
+
+            Ok(final_price)
+        } else {
+            require!(self.backing_ratio > 0, ErrorCode::InvalidBackingRatio);
+            Ok(self.backing_ratio) // Fallback to current backing ratio
+        }
+    }
+    
+    /// Store the absolute deviation (bps) between the pool spot price and the oracle average
+    /// `pool_spot_price` is underlying per RIFT in the same 6-decimal scale as oracle prices
+    pub fn compute_arbitrage_bps(&mut self, pool_spot_price: u64) -> Result<u16> {
+        let oracle_price = self.get_average_oracle_price()?;
+        require!(oracle_price > 0, ErrorCode::InvalidOraclePrice);
+
+        let deviation = u128::from(pool_spot_price.abs_diff(oracle_price))
+            .checked_mul(10000)
+            .ok_or(ErrorCode::MathOverflow)?
+            .checked_div(u128::from(oracle_price))
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        // Saturate: anything beyond u16 is far past every threshold anyway
+        let arbitrage_bps = u16::try_from(deviation).unwrap_or(u16::MAX);
+        self.arbitrage_opportunity_bps = arbitrage_bps;
+
+        Ok(arbitrage_bps)
+    }
+
+    /// Deviation in bps between an oracle price and backing_ratio, both at PRICE_DECIMALS
+    pub fn calculate_price_deviation(&self, oracle_price: u64) -> Result<u16> {
+        if self.backing_ratio == 0 {
+            return Ok(0);
+        }
+        
+        let deviation = if oracle_price > self.backing_ratio {
+            oracle_price
+                .checked_sub(self.backing_ratio)
+                .ok_or(ErrorCode::MathOverflow)?
+                .checked_mul(10000)
+                .ok_or(ErrorCode::MathOverflow)?
+                .checked_div(self.backing_ratio)
+                .ok_or(ErrorCode::MathOverflow)?
+        } else {
+            self.backing_ratio
+                .checked_sub(oracle_price)
+                .ok_or(ErrorCode::MathOverflow)?
+                .checked_mul(10000)
+                .ok_or(ErrorCode::MathOverflow)?
+                .checked_div(self.backing_ratio)
+                .ok_or(ErrorCode::MathOverflow)?
+        };
+        
+        Ok(u16::try_from(deviation).map_err(|_| ErrorCode::MathOverflow)?)
+    }
+    
+    /// Minimum swap output implied by the oracle price (underlying per RIFT, 6 decimals)
+    /// less `max_slippage_bps`; only underlying <-> rift mint swaps can be priced
+    pub fn oracle_min_amount_out(
+        &self,
+        source_mint: Pubkey,
+        destination_mint: Pubkey,
+        amount_in: u64,
+        max_slippage_bps: u16,
+    ) -> Result<u64> {
+        require!(max_slippage_bps <= 10000, ErrorCode::InvalidSlippageBps);
+
+        let oracle_price = u128::from(self.get_average_oracle_price()?);
+        require!(oracle_price > 0, ErrorCode::InvalidOraclePrice);
+
+        let amount_in = u128::from(amount_in);
+        let expected_out = if source_mint == self.underlying_mint && destination_mint == self.rift_mint {
+            amount_in
+                .checked_mul(1_000_000)
+                .ok_or(ErrorCode::MathOverflow)?
+                .checked_div(oracle_price)
+                .ok_or(ErrorCode::MathOverflow)?
+        } else if source_mint == self.rift_mint && destination_mint == self.underlying_mint {
+            amount_in
+                .checked_mul(oracle_price)
+                .ok_or(ErrorCode::MathOverflow)?
+                .checked_div(1_000_000)
+                .ok_or(ErrorCode::MathOverflow)?
+        } else {
+            return Err(ErrorCode::UnpricedSwapPair.into());
+        };
+
+        let minimum_out = expected_out
+            .checked_mul(u128::from(10000 - max_slippage_bps))
+            .ok_or(ErrorCode::MathOverflow)?
+            .checked_div(10000)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        Ok(u64::try_from(minimum_out).map_err(|_| ErrorCode::MathOverflow)?)
+    }
+
+    /// Split bought-back RIFTS into (lp_staker_amount, burn_amount) by rifts_lp_share_bps
+    /// Rounding dust goes to the burn side so the two always sum to `amount`
+    pub fn split_rifts_buyback(&self, amount: u64) -> Result<(u64, u64)> {
+        let lp_staker_amount = amount
+            .checked_mul(u64::from(self.rifts_lp_share_bps))
+            .ok_or(ErrorCode::MathOverflow)?
+            .checked_div(10000)
+            .ok_or(ErrorCode::MathOverflow)?;
+        let burn_amount = amount
+            .checked_sub(lp_staker_amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        Ok((lp_staker_amount, burn_amount))
+    }
+
+    pub fn process_rifts_distribution(&mut self, amount: u64) -> Result<()> {
+        let (lp_staker_amount, burn_amount) = self.split_rifts_buyback(amount)?;
+        
+        self.rifts_tokens_distributed = self.rifts_tokens_distributed
+            .checked_add(lp_staker_amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+        self.rifts_tokens_burned = self.rifts_tokens_burned
+            .checked_add(burn_amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+        
+        Ok(())
+    }
+    
+    pub fn get_pending_fees(&self) -> u64 {
+        // **SECURITY FIX**: Get total fees that haven't been distributed yet with proper error handling
+        let total_distributed = match self.rifts_tokens_distributed
+            .checked_add(self.rifts_tokens_burned) {
+            Some(total) => total,
+            None => return 0, // Overflow in distributed calculation - return 0 as safe fallback
+        };
+
+        if self.total_fees_collected > total_distributed {
+            match self.total_fees_collected.checked_sub(total_distributed) {
+                Some(pending) => pending,
+                None => 0, // Underflow should not happen given the check above, but return 0 as safe fallback
+            }
+        } else {
+            0
+        }
+    }
+    
+    /// Reject unwraps within wrap_cooldown_seconds of the user's last wrap
+    pub fn check_wrap_cooldown(&self, last_wrap_time: i64, current_time: i64) -> Result<()> {
+        if self.wrap_cooldown_seconds > 0 && last_wrap_time > 0 {
+            let unlocked_at = last_wrap_time
+                .checked_add(self.wrap_cooldown_seconds)
+                .ok_or(ErrorCode::MathOverflow)?;
+            require!(current_time >= unlocked_at, ErrorCode::WrapCooldownActive);
+        }
+        Ok(())
+    }
+
+    /// Reject pool removals above max_liquidity_removal_per_tx so large exits are split up
+    pub fn check_liquidity_removal(&self, liquidity_delta: u128) -> Result<()> {
+        if self.max_liquidity_removal_per_tx > 0 {
+            require!(
+                liquidity_delta <= self.max_liquidity_removal_per_tx,
+                ErrorCode::RemovalExceedsMax
+            );
+        }
+        Ok(())
+    }
+
+    /// Defaults for every field appended after last_governance_update
+    /// Shared by both create paths and migrate_rift so new and migrated rifts start out alike
+    pub fn init_extended_fields(&mut self, current_time: i64) {
+        self.volume_window_start = current_time;
+        // Fee-free micro-wraps are charged the minimum fee by default
+        self.zero_fee_policy = ZeroFeePolicy::ChargeMinimum;
+        self.treasury_fee_bps = DEFAULT_TREASURY_FEE_BPS;
+        self.reward_formula_version = REWARD_FORMULA_VERSION;
+        self.max_total_underlying = 0; // Unlimited until the creator sets a cap
+        self.whitelist_merkle_root = None; // Open wrapping by default
+        self.min_claimable_rewards = 0;
+        self.staker_fee_discount_bps = 0;
+        self.staker_discount_min_stake = 0;
+        self.reward_rate_per_hour = DEFAULT_REWARD_RATE_PER_HOUR;
+        self.reward_vest_period = 0; // Rewards paid out directly by default
+        self.max_oracle_staleness = DEFAULT_MAX_ORACLE_STALENESS;
+        self.use_confidence_weighting = false;
+        self.rifts_reward_budget = 0;
+        self.pause_reason = None;
+        self.pause_auto_unpause_at = 0;
+        self.max_liquidity_removal_per_tx = 0;
+        self.pool_config = None;
+        self.pool_sqrt_price = 0;
+        self.pool_liquidity = 0;
+        self.last_pool_sync = 0;
+        self.wrap_cooldown_seconds = 0;
+        self.backing_ratio_high_water = self.backing_ratio;
+        self.min_stake_duration = 0;
+        self.undercollateralization_floor = DEFAULT_UNDERCOLLATERALIZATION_FLOOR;
+        self.pause_on_undercollateralization = false;
+        self.keeper_reward = 0;
+        self.last_keeper_reward_time = 0;
+        self.rifts_lp_share_bps = DEFAULT_RIFTS_LP_SHARE_BPS;
+        self.rifts_burn_share_bps = DEFAULT_RIFTS_BURN_SHARE_BPS;
+        self.pending_fee_changes = None;
+        self.oracle_min_sources = 1;
+        self.bootstrap_cap = 0;
+        self.bootstrap_minted = 0;
+        self.bootstrap_complete = false;
+        self.min_wrap_amount = DEFAULT_MIN_WRAP_AMOUNT;
+        self.fee_vault = Pubkey::default(); // Set by the create paths or initialize_fee_vault
+        self.accrued_underlying_fees = 0;
+        self.max_confidence_bps = DEFAULT_MAX_CONFIDENCE_BPS;
+        self.close_proposed_at = 0;
+        self.meteora_config_key = None;
+        self.burn_mode = BurnMode::RealBurn;
+        self.incinerator = None;
+        self.total_incinerated = 0;
+        self.oracle_pushers = [Pubkey::default(); MAX_ORACLE_PUSHERS];
+        self.max_wrap_pct_of_tvl_bps = 0;
+        self.wrap_tvl_cap_threshold = 0;
+        self.lp_token_mint = None;
+        self.pause_authority = None;
+        self.paused_by = None;
+        self.treasury = None;
+        self.protocol_fee_mode = ProtocolFeeMode::Claim;
+    }
+
+    /// Whether the rift is paused at `current_time`, treating an expired auto-unpause as unpaused
+    pub fn is_paused_at(&self, current_time: i64) -> bool {
+        if !self.is_paused {
+            return false;
+        }
+        self.pause_auto_unpause_at == 0 || current_time <= self.pause_auto_unpause_at
+    }
+
+    /// Ensure wrapping `amount` keeps total_underlying_wrapped within max_total_underlying
+    pub fn check_deposit_cap(&self, amount: u64) -> Result<()> {
+        if self.max_total_underlying == 0 {
+            return Ok(());
+        }
+
+        let new_total = self.total_underlying_wrapped
+            .checked_add(amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+        require!(
+            new_total <= self.max_total_underlying,
+            ErrorCode::DepositCapExceeded
+        );
+
+        Ok(())
+    }
+
+    /// Ensure a single wrap of `amount` stays within max_wrap_pct_of_tvl_bps of current backing
+    /// Skipped while disabled, on an empty rift, or until total_underlying_wrapped
+    /// reaches wrap_tvl_cap_threshold
+    pub fn check_wrap_size(&self, amount: u64) -> Result<()> {
+        if self.max_wrap_pct_of_tvl_bps == 0
+            || self.total_underlying_wrapped == 0
+            || self.total_underlying_wrapped < self.wrap_tvl_cap_threshold
+        {
+            return Ok(());
+        }
+
+        let max_wrap = u128::from(self.total_underlying_wrapped)
+            .checked_mul(u128::from(self.max_wrap_pct_of_tvl_bps))
+            .ok_or(ErrorCode::MathOverflow)?
+            / 10000;
+        require!(
+            u128::from(amount) <= max_wrap,
+            ErrorCode::WrapTooLargeRelativeToTvl
+        );
+
+        Ok(())
+    }
+
+    /// Reduce a wrap/unwrap fee by staker_fee_discount_bps if the staker qualifies
+    pub fn apply_staker_discount(&self, fee: u64, staker: Option<&StakerAccount>) -> Result<u64> {
+        let staker = match staker {
+            Some(staker) => staker,
+            None => return Ok(fee),
+        };
+
+        if self.staker_fee_discount_bps == 0
+            || staker.staked_amount == 0
+            || staker.staked_amount < self.staker_discount_min_stake
+        {
+            return Ok(fee);
+        }
+
+        // Discount is capped at 100% so the net fee never goes negative
+        let discount_bps = self.staker_fee_discount_bps.min(10000);
+        let discount = u64::try_from(
+            (fee as u128)
+                .checked_mul(discount_bps as u128)
+                .ok_or(ErrorCode::MathOverflow)?
+                .checked_div(10000)
+                .ok_or(ErrorCode::MathOverflow)?
+        ).map_err(|_| ErrorCode::MathOverflow)?;
+
+        Ok(fee.saturating_sub(discount))
+    }
+
+    /// Reload the rift mint after mint/burn CPIs and require its supply is within total_rift_minted
+    /// Supply may fall short when holders SPL-burn their own RIFT, so only the upper bound holds
+    pub fn assert_supply_invariant(&self, rift_mint: &mut Account<Mint>) -> Result<()> {
+        rift_mint.reload()?;
+        require!(
+            rift_mint.supply <= self.expected_mint_supply()?,
+            ErrorCode::SupplyDesync
+        );
+        Ok(())
+    }
+
+    /// Mint supply implied by accounting: outstanding RIFT plus RIFT parked in the incinerator
+    pub fn expected_mint_supply(&self) -> Result<u64> {
+        Ok(self.total_rift_minted
+            .checked_add(self.total_incinerated)
+            .ok_or(ErrorCode::MathOverflow)?)
+    }
+
+    /// The creator or any non-empty oracle_pushers slot may push oracle prices
+    pub fn is_oracle_authority(&self, key: &Pubkey) -> bool {
+        *key == self.creator
+            || (*key != Pubkey::default() && self.oracle_pushers.contains(key))
+    }
+
+    /// Owner whose token account receives RIFT under BurnMode::SendToIncinerator
+    pub fn incinerator_owner(&self) -> Pubkey {
+        self.incinerator
+            .unwrap_or(anchor_lang::solana_program::incinerator::ID)
+    }
+
+    /// Require a pool's initial price to be within max_deviation_bps of the oracle average
+    pub fn validate_initial_price(&self, init_price: u64, max_deviation_bps: u16) -> Result<()> {
+        require!(init_price > 0, ErrorCode::InvalidOraclePrice);
+        require!(
+            max_deviation_bps <= MAX_INITIAL_PRICE_DEVIATION_BPS,
+            ErrorCode::InvalidPriceDeviationBps
+        );
 
-            let scaled_avg = scaled_total
-                .checked_div(u128::from(count))
-                .ok_or(ErrorCode::MathOverflow)?;
+        let oracle_price = self.get_average_oracle_price()?;
+        let difference = init_price.abs_diff(oracle_price);
+        let deviation_bps = (difference as u128)
+            .checked_mul(10000)
+            .ok_or(ErrorCode::MathOverflow)?
+            .checked_div(oracle_price as u128)
+            .ok_or(ErrorCode::MathOverflow)?;
 
-            // Convert back to u64 with proper precision preservation
-            let avg_price = scaled_avg
-                .checked_div(PRECISION_SCALE)
-                .ok_or(ErrorCode::MathOverflow)?;
+        msg!("Initial price {} vs oracle {} ({} bps, max {})",
+             init_price, oracle_price, deviation_bps, max_deviation_bps);
 
-            let final_price = u64::try_from(avg_price)
-                .map_err(|_| ErrorCode::MathOverflow)?;
+        require!(
+            deviation_bps <= max_deviation_bps as u128,
+            ErrorCode::InitialPriceDeviation
+        );
 
-            // **CRITICAL FIX**: Validate average price is reasonable
-            require!(final_price > 0, ErrorCode::InvalidOraclePrice);
-            require!(final_price <= 1_000_000_000_000, ErrorCode::OraclePriceTooLarge);
+        Ok(())
+    }
 
-            Ok(final_price)
-        } else {
-            // **CRITICAL FIX**: Validate fallback backing ratio
-            require!(self.backing_ratio > 0, ErrorCode::InvalidBackingRatio);
-            Ok(self.backing_ratio) // Fallback to current backing ratio
+    /// Calculate the wrap fee, enforcing zero_fee_policy when the fee rounds to zero
+    pub fn calculate_wrap_fee(&self, amount: u64) -> Result<u64> {
+        let wrap_fee = amount
+            .checked_mul(u64::from(WRAP_FEE_BPS))
+            .ok_or(ErrorCode::MathOverflow)?
+            .checked_div(10000)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        if wrap_fee == 0 && amount > 0 {
+            return match self.zero_fee_policy {
+                ZeroFeePolicy::ChargeMinimum => Ok(1),
+                ZeroFeePolicy::Reject => Err(ErrorCode::FeeTooSmall.into()),
+            };
         }
+
+        Ok(wrap_fee)
     }
-    
-    pub fn calculate_price_deviation(&self, oracle_price: u64) -> Result<u16> {
-        if self.backing_ratio == 0 {
-            return Ok(0);
-        }
-        
-        let deviation = if oracle_price > self.backing_ratio {
-            oracle_price
-                .checked_sub(self.backing_ratio)
-                .ok_or(ErrorCode::MathOverflow)?
-                .checked_mul(10000)
+
+    /// Payback period for wrapping `amount`, staking the proceeds and unwrapping later
+    /// Treats staked LP as the post-fee wrapped amount and RIFTS rewards 1:1 with fees
+    pub fn get_breakeven_estimate(&self, amount: u64) -> Result<BreakevenEstimate> {
+        let wrap_fee = self.calculate_wrap_fee(amount)?;
+        let staked_amount = amount.checked_sub(wrap_fee).ok_or(ErrorCode::MathOverflow)?;
+        let unwrap_fee = u64::try_from(
+            (staked_amount as u128)
+                .checked_mul(WRAP_FEE_BPS as u128)
                 .ok_or(ErrorCode::MathOverflow)?
-                .checked_div(self.backing_ratio)
+                .checked_div(10000)
                 .ok_or(ErrorCode::MathOverflow)?
+        ).map_err(|_| ErrorCode::MathOverflow)?;
+        let total_fees = wrap_fee.checked_add(unwrap_fee).ok_or(ErrorCode::MathOverflow)?;
+
+        // Per-hour accrual, matching StakerAccount::accrued_rewards
+        let rate_numerator = (staked_amount as u128)
+            .checked_mul(self.reward_rate_per_hour as u128)
+            .ok_or(ErrorCode::MathOverflow)?;
+        let rewards_per_hour = u64::try_from(rate_numerator / REWARD_RATE_PRECISION)
+            .map_err(|_| ErrorCode::MathOverflow)?;
+
+        // Rewards accrue in whole hours: smallest h with staked * h * rate / PRECISION > total_fees
+        let seconds_to_breakeven = if rate_numerator == 0 {
+            None
         } else {
-            self.backing_ratio
-                .checked_sub(oracle_price)
-                .ok_or(ErrorCode::MathOverflow)?
-                .checked_mul(10000)
-                .ok_or(ErrorCode::MathOverflow)?
-                .checked_div(self.backing_ratio)
-                .ok_or(ErrorCode::MathOverflow)?
+            let target = (total_fees as u128 + 1)
+                .checked_mul(REWARD_RATE_PRECISION)
+                .ok_or(ErrorCode::MathOverflow)?;
+            let hours = target.div_ceil(rate_numerator);
+            hours
+                .checked_mul(3600)
+                .and_then(|seconds| u64::try_from(seconds).ok())
         };
-        
-        Ok(u16::try_from(deviation).map_err(|_| ErrorCode::MathOverflow)?)
+
+        Ok(BreakevenEstimate {
+            amount,
+            wrap_fee,
+            unwrap_fee,
+            total_fees,
+            rewards_per_hour,
+            seconds_to_breakeven,
+        })
     }
-    
-    pub fn process_rifts_distribution(&mut self, amount: u64) -> Result<()> {
-        // 90% to LP stakers, 10% burned with checked arithmetic
-        let lp_staker_amount = amount
-            .checked_mul(90)
+
+    /// Split a fee across burn, partner, treasury and fee collector
+    /// Without a partner_wallet the partner share folds into the remainder
+    pub fn calculate_fee_split(&self, fee_amount: u64) -> Result<FeeSplit> {
+        let burn_bps = u64::from(self.burn_fee_bps);
+        let partner_bps = if self.partner_wallet.is_some() {
+            u64::from(self.partner_fee_bps)
+        } else {
+            0
+        };
+
+        let burn_amount = fee_amount
+            .checked_mul(burn_bps)
             .ok_or(ErrorCode::MathOverflow)?
-            .checked_div(100)
+            .checked_div(10000)
             .ok_or(ErrorCode::MathOverflow)?;
-        let burn_amount = amount
-            .checked_mul(10)
+
+        let partner_amount = fee_amount
+            .checked_mul(partner_bps)
             .ok_or(ErrorCode::MathOverflow)?
-            .checked_div(100)
+            .checked_div(10000)
             .ok_or(ErrorCode::MathOverflow)?;
-        
-        self.rifts_tokens_distributed = self.rifts_tokens_distributed
-            .checked_add(lp_staker_amount)
+
+        let burn_plus_partner = burn_amount
+            .checked_add(partner_amount)
             .ok_or(ErrorCode::MathOverflow)?;
-        self.rifts_tokens_burned = self.rifts_tokens_burned
-            .checked_add(burn_amount)
+
+        let remaining = fee_amount
+            .checked_sub(burn_plus_partner)
             .ok_or(ErrorCode::MathOverflow)?;
-        
-        Ok(())
+
+        // treasury_fee_bps to treasury, the rest to fee collector
+        let treasury_amount = remaining
+            .checked_mul(self.treasury_fee_bps as u64)
+            .ok_or(ErrorCode::MathOverflow)?
+            .checked_div(10000)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        let fee_collector_amount = remaining
+            .checked_sub(treasury_amount)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        Ok(FeeSplit {
+            burn_amount,
+            partner_amount,
+            treasury_amount,
+            fee_collector_amount,
+        })
     }
-    
-    pub fn get_pending_fees(&self) -> u64 {
-        // **SECURITY FIX**: Get total fees that haven't been distributed yet with proper error handling
-        let total_distributed = match self.rifts_tokens_distributed
-            .checked_add(self.rifts_tokens_burned) {
-            Some(total) => total,
-            None => return 0, // Overflow in distributed calculation - return 0 as safe fallback
-        };
 
-        if self.total_fees_collected > total_distributed {
-            match self.total_fees_collected.checked_sub(total_distributed) {
-                Some(pending) => pending,
-                None => 0, // Underflow should not happen given the check above, but return 0 as safe fallback
-            }
+    /// Compute fee routing shares using the same split as process_fee_distribution
+    pub fn get_fee_routing(&self, rift_key: Pubkey) -> Result<FeeRouting> {
+        let burn_bps = self.burn_fee_bps;
+        // Partner share folds into the remainder when no partner is set
+        let partner_bps = if self.partner_wallet.is_some() { self.partner_fee_bps } else { 0 };
+
+        let remaining_bps = 10000u16
+            .checked_sub(burn_bps)
+            .and_then(|r| r.checked_sub(partner_bps))
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        // treasury_fee_bps of the remainder to treasury, the rest to fee collector
+        let treasury_bps = (remaining_bps as u32)
+            .checked_mul(self.treasury_fee_bps as u32)
+            .ok_or(ErrorCode::MathOverflow)?
+            .checked_div(10000)
+            .ok_or(ErrorCode::MathOverflow)? as u16;
+        let fee_collector_bps = remaining_bps
+            .checked_sub(treasury_bps)
+            .ok_or(ErrorCode::MathOverflow)?;
+
+        // Bought-back RIFTS split between LP stakers and burn
+        let (lp_staker_bps, rifts_burn_bps) = self.split_rifts_buyback(u64::from(fee_collector_bps))?;
+        let lp_staker_bps = lp_staker_bps as u16;
+        let rifts_burn_bps = rifts_burn_bps as u16;
+
+        Ok(FeeRouting {
+            rift: rift_key,
+            wrap_fee_bps: WRAP_FEE_BPS,
+            burn_bps,
+            partner_bps,
+            partner_wallet: self.partner_wallet,
+            treasury_bps,
+            fee_collector_bps,
+            lp_staker_bps,
+            rifts_burn_bps,
+        })
+    }
+
+    /// Bitmask of CONFIG_VIOLATION_* flags for any broken configuration invariant
+    pub fn config_violations(&self) -> u32 {
+        let mut violations = 0u32;
+
+        // Fee bounds match create_rift and governance limits
+        if self.burn_fee_bps > 4500
+            || self.partner_fee_bps > 500
+            || self.treasury_fee_bps >= MAX_TREASURY_FEE_BPS
+            || validate_combined_fees(self.burn_fee_bps, self.partner_fee_bps, self.treasury_fee_bps).is_err()
+            || self.staker_fee_discount_bps > 10000
+            || self.pool_trading_fee_bps > 10000
+            || u32::from(self.rifts_lp_share_bps) + u32::from(self.rifts_burn_share_bps) != 10000
+        {
+            violations |= CONFIG_VIOLATION_FEE_BOUNDS;
+        }
+
+        // Oracle parameter ranges match create_rift bounds
+        if !(300..=86400).contains(&self.oracle_update_interval)
+            || !(3600..=604800).contains(&self.max_rebalance_interval)
+            || validate_arbitrage_threshold(self.arbitrage_threshold_bps).is_err()
+            || !(MIN_MAX_ORACLE_STALENESS..=MAX_MAX_ORACLE_STALENESS).contains(&self.max_oracle_staleness)
+        {
+            violations |= CONFIG_VIOLATION_ORACLE_PARAMS;
+        }
+
+        // Backing ratio is 6-decimal; a never-rebalanced rift must still sit at 1.0x
+        // (catches the basis-point 10000 written by the legacy creation path)
+        if self.backing_ratio == 0
+            || self.backing_ratio > 1_000_000_000_000
+            || (self.rebalance_count == 0 && self.backing_ratio != 1_000_000)
+        {
+            violations |= CONFIG_VIOLATION_BACKING_RATIO;
+        }
+
+        // A bound Meteora pool must have its pool, authority and both vaults set
+        let meteora_fields = [
+            self.pool_authority,
+            self.pool_token_a_vault,
+            self.pool_token_b_vault,
+        ];
+        let any_bound = meteora_fields.iter().any(|field| field.is_some());
+        let all_bound = meteora_fields.iter().all(|field| field.is_some());
+        if any_bound && (!all_bound || self.liquidity_pool.is_none()) {
+            violations |= CONFIG_VIOLATION_POOL_STATE;
+        }
+
+        violations
+    }
+
+    /// Oracle samples in chronological order, starting from the oldest ring slot
+    pub fn get_oracle_history(&self) -> OracleHistory {
+        let len = self.oracle_prices.len();
+        // price_index points at the next slot to write, which holds the oldest sample
+        let samples = std::array::from_fn(|i| {
+            self.oracle_prices[(self.price_index as usize + i) % len]
+        });
+
+        OracleHistory {
+            price_index: self.price_index,
+            samples,
+        }
+    }
+
+    /// Summarize oracle freshness without erroring on an empty price buffer
+    pub fn get_oracle_health(&self, rift_key: Pubkey, current_time: i64) -> OracleHealth {
+        let sample_count = self.oracle_prices
+            .iter()
+            .filter(|p| p.timestamp > 0)
+            .count() as u8;
+
+        // price_index points at the next slot, so the newest sample is one behind it
+        let newest_index = (self.price_index as usize + self.oracle_prices.len() - 1) % self.oracle_prices.len();
+        let newest = &self.oracle_prices[newest_index];
+        let newest_sample_fresh = newest.timestamp > 0
+            && current_time.saturating_sub(newest.timestamp) <= self.max_oracle_staleness;
+
+        let average_price = if sample_count > 0 {
+            self.get_average_oracle_price().unwrap_or(0)
         } else {
             0
+        };
+
+        OracleHealth {
+            rift: rift_key,
+            seconds_since_update: current_time.saturating_sub(self.last_oracle_update),
+            oracle_countdown: self.get_oracle_countdown(current_time),
+            newest_sample_fresh,
+            average_price,
+            sample_count,
         }
     }
-    
+
     pub fn get_oracle_countdown(&self, current_time: i64) -> i64 {
-        let next_oracle_time = self.last_oracle_update + self.oracle_update_interval;
-        (next_oracle_time - current_time).max(0)
+        let next_oracle_time = self.last_oracle_update.saturating_add(self.oracle_update_interval);
+        next_oracle_time.saturating_sub(current_time).max(0)
     }
     
     pub fn get_rebalance_countdown(&self, current_time: i64) -> i64 {
-        let next_rebalance_time = self.last_rebalance + self.max_rebalance_interval;
-        (next_rebalance_time - current_time).max(0)
+        let next_rebalance_time = self.last_rebalance.saturating_add(self.max_rebalance_interval);
+        next_rebalance_time.saturating_sub(current_time).max(0)
     }
 
     /// Process fee distribution immediately (called automatically on wrap/unwrap)
@@ -3461,25 +9662,18 @@ impl Rift {
             .checked_sub(burn_amount.checked_add(partner_amount).ok_or(ErrorCode::MathOverflow)?)
             .ok_or(ErrorCode::MathOverflow)?;
         
-        // 5% to treasury, 95% to buy RIFTS tokens
+        // treasury_fee_bps to treasury, the rest to buy RIFTS tokens
         let treasury_amount = remaining_fees
-            .checked_mul(5)
+            .checked_mul(self.treasury_fee_bps as u64)
             .ok_or(ErrorCode::MathOverflow)?
-            .checked_div(100)
+            .checked_div(10000)
             .ok_or(ErrorCode::MathOverflow)?;
         let rifts_buy_amount = remaining_fees
             .checked_sub(treasury_amount)
             .ok_or(ErrorCode::MathOverflow)?;
         
         // Process RIFTS token buyback and distribution
-        let lp_staker_amount = rifts_buy_amount
-            .checked_mul(90)
-            .ok_or(ErrorCode::MathOverflow)?
-            .checked_div(100)
-            .ok_or(ErrorCode::MathOverflow)?;
-        let rifts_burn_amount = rifts_buy_amount
-            .checked_sub(lp_staker_amount)
-            .ok_or(ErrorCode::MathOverflow)?;
+        let (lp_staker_amount, rifts_burn_amount) = self.split_rifts_buyback(rifts_buy_amount)?;
         
         // Update tracking with checked arithmetic
         self.rifts_tokens_distributed = self.rifts_tokens_distributed
@@ -3502,6 +9696,582 @@ impl Rift {
     }
 }
 
+/// Fields read directly from a Meteora DAMM v2 pool account
+pub struct MeteoraPoolState {
+    pub token_a_mint: Pubkey,
+    pub token_b_mint: Pubkey,
+    pub token_a_vault: Pubkey,
+    pub token_b_vault: Pubkey,
+    pub liquidity: u128,
+    pub sqrt_price: u128, // Q64.64
+}
+
+/// Read the live state of a Meteora DAMM v2 pool account
+pub fn read_meteora_pool_state(pool: &AccountInfo) -> Result<MeteoraPoolState> {
+    require!(
+        pool.owner == &METEORA_DAMM_V2_PROGRAM_ID,
+        ErrorCode::InvalidPoolAccount
+    );
+
+    let pool_data = pool.data.borrow();
+    require!(pool_data.len() >= 472, ErrorCode::InvalidPoolAccount);
+
+    // DAMM v2 Pool layout (after 8-byte discriminator):
+    // 8-168: pool_fees
+    // 168-200: token_a_mint
+    // 200-232: token_b_mint
+    // 232-360: token_a_vault, token_b_vault, whitelisted_vault, partner
+    // 360-376: liquidity (u128)
+    // ... padding, protocol/partner fees, sqrt_min_price, sqrt_max_price
+    // 456-472: sqrt_price (u128, Q64.64)
+    let token_a_mint = Pubkey::new_from_array(
+        pool_data[168..200].try_into().map_err(|_| ErrorCode::InvalidPoolAccount)?
+    );
+    let token_b_mint = Pubkey::new_from_array(
+        pool_data[200..232].try_into().map_err(|_| ErrorCode::InvalidPoolAccount)?
+    );
+    let token_a_vault = Pubkey::new_from_array(
+        pool_data[232..264].try_into().map_err(|_| ErrorCode::InvalidPoolAccount)?
+    );
+    let token_b_vault = Pubkey::new_from_array(
+        pool_data[264..296].try_into().map_err(|_| ErrorCode::InvalidPoolAccount)?
+    );
+    let liquidity = u128::from_le_bytes(
+        pool_data[360..376].try_into().map_err(|_| ErrorCode::InvalidPoolAccount)?
+    );
+    let sqrt_price = u128::from_le_bytes(
+        pool_data[456..472].try_into().map_err(|_| ErrorCode::InvalidPoolAccount)?
+    );
+    require!(
+        (MIN_SQRT_PRICE..=MAX_SQRT_PRICE).contains(&sqrt_price),
+        ErrorCode::InvalidPoolAccount
+    );
+
+    Ok(MeteoraPoolState {
+        token_a_mint,
+        token_b_mint,
+        token_a_vault,
+        token_b_vault,
+        liquidity,
+        sqrt_price,
+    })
+}
+
+/// Vault balance sweep_excess_underlying may move out: everything above the backing owed,
+/// taken as the larger of the accounted wrapped amount and the outstanding RIFT supply
+pub fn sweepable_excess(vault_balance: u64, total_underlying_wrapped: u64, rift_supply: u64) -> u64 {
+    vault_balance.saturating_sub(total_underlying_wrapped.max(rift_supply))
+}
+
+/// Meteora DAMM v2 pool PDA: ["pool", config, larger_mint, smaller_mint]
+pub fn meteora_pool_pda(config: &Pubkey, mint_a: &Pubkey, mint_b: &Pubkey) -> Pubkey {
+    let (first_mint, second_mint) = if mint_a.to_bytes() > mint_b.to_bytes() {
+        (mint_a, mint_b)
+    } else {
+        (mint_b, mint_a)
+    };
+
+    Pubkey::find_program_address(
+        &[b"pool", config.as_ref(), first_mint.as_ref(), second_mint.as_ref()],
+        &METEORA_DAMM_V2_PROGRAM_ID,
+    ).0
+}
+
+/// ["incinerator", rift] PDA; the program never signs for it, so RIFT sent there is unrecoverable
+pub fn rift_incinerator_pda(rift: &Pubkey) -> Pubkey {
+    Pubkey::find_program_address(&[b"incinerator", rift.as_ref()], &crate::ID).0
+}
+
+/// Dispose of redeemed RIFT per rift.burn_mode: SPL burn, or transfer to the incinerator owner
+pub fn dispose_redeemed_rift<'info>(
+    rift: &mut Rift,
+    token_program: &Program<'info, Token>,
+    rift_mint: &Account<'info, Mint>,
+    from: &Account<'info, TokenAccount>,
+    authority: &Signer<'info>,
+    incinerator_rift_tokens: Option<&Account<'info, TokenAccount>>,
+    amount: u64,
+) -> Result<()> {
+    match rift.burn_mode {
+        BurnMode::RealBurn => {
+            let burn_ctx = CpiContext::new(
+                token_program.to_account_info(),
+                anchor_spl::token::Burn {
+                    mint: rift_mint.to_account_info(),
+                    from: from.to_account_info(),
+                    authority: authority.to_account_info(),
+                },
+            );
+            anchor_spl::token::burn(burn_ctx, amount)?;
+
+            msg!("✅ Burned {} RIFT tokens", amount);
+        }
+        BurnMode::SendToIncinerator => {
+            let incinerator = incinerator_rift_tokens.ok_or(ErrorCode::InvalidIncinerator)?;
+            require!(
+                incinerator.owner == rift.incinerator_owner(),
+                ErrorCode::InvalidIncinerator
+            );
+
+            let transfer_ctx = CpiContext::new(
+                token_program.to_account_info(),
+                Transfer {
+                    from: from.to_account_info(),
+                    to: incinerator.to_account_info(),
+                    authority: authority.to_account_info(),
+                },
+            );
+            token::transfer(transfer_ctx, amount)?;
+
+            rift.total_incinerated = rift.total_incinerated
+                .checked_add(amount)
+                .ok_or(ErrorCode::MathOverflow)?;
+
+            msg!("✅ Sent {} RIFT tokens to incinerator {}", amount, incinerator.key());
+        }
+    }
+    Ok(())
+}
+
+/// True when `nft_account` is a token account owned by `user` holding the `nft_mint` position NFT
+/// Reads the base SPL layout shared by Token and Token-2022: mint (0..32), owner (32..64), amount (64..72)
+pub fn position_nft_held_by(nft_account: &AccountInfo, nft_mint: &Pubkey, user: &Pubkey) -> bool {
+    if nft_account.owner != &anchor_spl::token::ID && nft_account.owner != &anchor_spl::token_2022::ID {
+        return false;
+    }
+    let Ok(data) = nft_account.try_borrow_data() else {
+        return false;
+    };
+    if data.len() < 72 {
+        return false;
+    }
+
+    let amount = u64::from_le_bytes(data[64..72].try_into().unwrap_or_default());
+    data[0..32] == nft_mint.to_bytes() && data[32..64] == user.to_bytes() && amount == 1
+}
+
+/// Check that `position` is the Meteora position PDA for `nft_mint` and belongs to `pool`
+pub fn check_pool_position(position: &AccountInfo, nft_mint: &Pubkey, pool: &Pubkey) -> Result<()> {
+    let (expected_position, _) = Pubkey::find_program_address(
+        &[b"position", nft_mint.as_ref()],
+        &METEORA_DAMM_V2_PROGRAM_ID,
+    );
+    require!(
+        position.key() == expected_position && position.owner == &METEORA_DAMM_V2_PROGRAM_ID,
+        ErrorCode::InvalidPosition
+    );
+    let position_data = position.try_borrow_data()?;
+    require!(
+        position_data.len() >= 40 && position_data[8..40] == pool.to_bytes(),
+        ErrorCode::InvalidPosition
+    );
+    Ok(())
+}
+
+/// True when a token account with `owner` and `mint` is the rift's staking vault for `lp_mint`
+/// The owner must be the ["staking_vault", rift] PDA that signs unstake_lp_tokens transfers
+pub fn is_staking_vault(owner: &Pubkey, mint: &Pubkey, rift: &Pubkey, lp_mint: &Pubkey) -> bool {
+    let (vault_authority, _) = Pubkey::find_program_address(
+        &[b"staking_vault", rift.as_ref()],
+        &crate::ID,
+    );
+    *owner == vault_authority && mint == lp_mint
+}
+
+/// Enforce the RiftFactoryConfig collection gate for `creator`, if one is configured
+pub fn check_collection_gate(
+    factory_config: &AccountInfo,
+    creator: &Pubkey,
+    nft_account: Option<&AccountInfo>,
+    nft_metadata: Option<&AccountInfo>,
+) -> Result<()> {
+    if factory_config.owner != &crate::ID || factory_config.data_is_empty() {
+        return Ok(());
+    }
+    let config = RiftFactoryConfig::try_deserialize(&mut &factory_config.try_borrow_data()?[..])?;
+    let Some(required_collection) = config.required_collection_mint else {
+        return Ok(());
+    };
+
+    let (Some(nft_account), Some(nft_metadata)) = (nft_account, nft_metadata) else {
+        return Err(ErrorCode::CollectionGateNotSatisfied.into());
+    };
+    require!(
+        nft_metadata.owner == &TOKEN_METADATA_PROGRAM_ID,
+        ErrorCode::CollectionGateNotSatisfied
+    );
+    let (nft_mint, collection) = {
+        let data = nft_metadata.try_borrow_data()?;
+        metadata_verified_collection(&data).ok_or(ErrorCode::CollectionGateNotSatisfied)?
+    };
+    let (expected_metadata, _) = Pubkey::find_program_address(
+        &[b"metadata", TOKEN_METADATA_PROGRAM_ID.as_ref(), nft_mint.as_ref()],
+        &TOKEN_METADATA_PROGRAM_ID,
+    );
+    require!(
+        nft_metadata.key() == expected_metadata
+            && collection == required_collection
+            && position_nft_held_by(nft_account, &nft_mint, creator),
+        ErrorCode::CollectionGateNotSatisfied
+    );
+
+    Ok(())
+}
+
+/// Parse a Metaplex Metadata account into (mint, collection) if its collection is verified
+fn metadata_verified_collection(data: &[u8]) -> Option<(Pubkey, Pubkey)> {
+    fn take<'a>(data: &'a [u8], pos: &mut usize, len: usize) -> Option<&'a [u8]> {
+        let bytes = data.get(*pos..pos.checked_add(len)?)?;
+        *pos += len;
+        Some(bytes)
+    }
+    fn skip_string(data: &[u8], pos: &mut usize) -> Option<()> {
+        let len = u32::from_le_bytes(take(data, pos, 4)?.try_into().ok()?);
+        take(data, pos, len as usize).map(|_| ())
+    }
+    fn skip_option(data: &[u8], pos: &mut usize, len: usize) -> Option<()> {
+        if take(data, pos, 1)?[0] == 1 {
+            take(data, pos, len)?;
+        }
+        Some(())
+    }
+
+    // key, update_authority
+    let mut pos = 1 + 32;
+    let mint = Pubkey::try_from(take(data, &mut pos, 32)?).ok()?;
+    // name, symbol, uri, seller_fee_basis_points
+    skip_string(data, &mut pos)?;
+    skip_string(data, &mut pos)?;
+    skip_string(data, &mut pos)?;
+    take(data, &mut pos, 2)?;
+    // creators: Option<Vec<Creator { address, verified, share }>>
+    if take(data, &mut pos, 1)?[0] == 1 {
+        let count = u32::from_le_bytes(take(data, &mut pos, 4)?.try_into().ok()?);
+        take(data, &mut pos, (count as usize).checked_mul(34)?)?;
+    }
+    // primary_sale_happened, is_mutable, edition_nonce, token_standard
+    take(data, &mut pos, 2)?;
+    skip_option(data, &mut pos, 1)?;
+    skip_option(data, &mut pos, 1)?;
+    // collection: Option<Collection { verified, key }>
+    if take(data, &mut pos, 1)?[0] != 1 {
+        return None;
+    }
+    let verified = take(data, &mut pos, 1)?[0] == 1;
+    let collection = Pubkey::try_from(take(data, &mut pos, 32)?).ok()?;
+
+    verified.then_some((mint, collection))
+}
+
+/// DAMM v2 pools share a single program-wide authority PDA
+pub fn meteora_pool_authority() -> Pubkey {
+    Pubkey::find_program_address(&[b"pool_authority"], &METEORA_DAMM_V2_PROGRAM_ID).0
+}
+
+/// Read the Meteora DAMM v2 pool spot price as underlying per RIFT (6 decimals)
+/// Assumes the rift mint and underlying mint share decimals, as they do for rifts
+pub fn parse_meteora_pool_price(pool: &AccountInfo, rift_mint: &Pubkey) -> Result<u64> {
+    let MeteoraPoolState { token_a_mint, sqrt_price, .. } = read_meteora_pool_state(pool)?;
+
+    // price (token B per token A) = sqrt_price^2 / 2^128; drop 32 fractional bits first so it fits
+    let sqrt_price_q32 = sqrt_price >> 32;
+    let price_q64 = sqrt_price_q32
+        .checked_mul(sqrt_price_q32)
+        .ok_or(ErrorCode::MathOverflow)?;
+    let b_per_a = (price_q64 >> 32)
+        .checked_mul(1_000_000)
+        .ok_or(ErrorCode::MathOverflow)?
+        >> 32;
+
+    // Orient to underlying per RIFT
+    let price = if token_a_mint == *rift_mint {
+        b_per_a
+    } else {
+        require!(b_per_a > 0, ErrorCode::InvalidPoolAccount);
+        1_000_000_000_000u128
+            .checked_div(b_per_a)
+            .ok_or(ErrorCode::MathOverflow)?
+    };
+
+    u64::try_from(price).map_err(|_| ErrorCode::MathOverflow.into())
+}
+
+/// **SECURITY FIX #50**: Parse and validate a Pyth price account
+/// Checks ownership, staleness, confidence and exponent
+/// Returns (price normalized to 6 decimals, confidence, publish_time, confidence in bps of price)
+pub fn parse_pyth_price(
+    pyth_price_account: &AccountInfo,
+    current_time: i64,
+    max_age_seconds: i64,
+    max_confidence_bps: u16,
+) -> Result<(u64, u64, i64, u64)> {
+    // **SECURITY FIX #50**: Validate Pyth account ownership
+    let pyth_program_id = Pubkey::from_str_const("FsJ3A3u2vn5cTVofAjvy6y5kwABJAqYWpe4975bi2epH");
+    require!(
+        pyth_price_account.owner == &pyth_program_id,
+        ErrorCode::InvalidOracleOwner
+    );
+
+    // Parse Pyth price data
+    let pyth_price_data = &pyth_price_account.data.borrow();
+    require!(pyth_price_data.len() >= 240, ErrorCode::InvalidOracleData);
+
+    // Pyth price account layout:
+    // 0-4: magic (0xa1b2c3d4)
+    // 4-8: version
+    // 8-12: type
+    // 12-16: size
+    // 16-48: product account
+    // 48-80: next price account
+    // 80-88: aggregate price (i64)
+    // 88-96: confidence (u64)
+    // 96-100: status (u32)
+    // 100-104: corp_act (u32)
+    // 104-112: publish_time (i64)
+    // 112-120: prev_publish_time (i64)
+    // 120-128: prev_price (i64)
+    // 128-136: prev_conf (u64)
+    // ... more fields
+
+    let price_i64 = i64::from_le_bytes(
+        pyth_price_data[80..88].try_into().map_err(|_| ErrorCode::InvalidOracleData)?
+    );
+    let confidence_u64 = u64::from_le_bytes(
+        pyth_price_data[88..96].try_into().map_err(|_| ErrorCode::InvalidOracleData)?
+    );
+    let publish_time_i64 = i64::from_le_bytes(
+        pyth_price_data[104..112].try_into().map_err(|_| ErrorCode::InvalidOracleData)?
+    );
+    let exponent_i32 = i32::from_le_bytes(
+        pyth_price_data[20..24].try_into().map_err(|_| ErrorCode::InvalidOracleData)?
+    );
+
+    // Convert price to u64 (handle negative prices as error)
+    require!(price_i64 > 0, ErrorCode::InvalidOraclePrice);
+    let price = price_i64 as u64;
+    let confidence = confidence_u64;
+
+    // **SECURITY FIX #50**: Validate staleness against the caller's window
+    require!(
+        current_time - publish_time_i64 <= max_age_seconds,
+        ErrorCode::OraclePriceStale
+    );
+
+    msg!("Pyth price age: {} seconds", current_time - publish_time_i64);
+
+    // **SECURITY FIX #50**: Validate confidence against the configured ceiling
+    let confidence_bps = confidence_bps_of(price, confidence)?;
+    require!(
+        confidence_bps <= u64::from(max_confidence_bps),
+        ErrorCode::OracleConfidenceTooLow
+    );
+
+    msg!("Pyth confidence: {} ({} bps, max allowed: {} bps)", confidence, confidence_bps, max_confidence_bps);
+
+    // **SECURITY FIX #50**: Validate exponent/decimals (-8 to -6 typical for USD pairs)
+    require!(
+        (MIN_ORACLE_EXPONENT..=MAX_ORACLE_EXPONENT).contains(&exponent_i32),
+        ErrorCode::InvalidOracleExponent
+    );
+
+    msg!("Pyth exponent: {}", exponent_i32);
+
+    // Normalize price to PRICE_DECIMALS (standard for rift system)
+    let normalized_price = normalize_price(price, exponent_i32)?;
+
+    Ok((normalized_price, confidence, publish_time_i64, confidence_bps))
+}
+
+/// **SECURITY FIX #50**: Parse and validate a Switchboard aggregator account
+/// Checks ownership, staleness, scale and confidence
+/// Returns (price normalized to 6 decimals, confidence, round_open_timestamp, confidence in bps of price)
+pub fn parse_switchboard_price(
+    switchboard_feed: &AccountInfo,
+    current_time: i64,
+    max_age_seconds: i64,
+    max_confidence_bps: u16,
+) -> Result<(u64, u64, i64, u64)> {
+    // **SECURITY FIX #50**: Validate Switchboard account ownership
+    let switchboard_program_id = Pubkey::from_str_const("SW1TCH7qEPTdLsDHRgPuMQjbQxKdH2aBStViMFnt64f");
+    require!(
+        switchboard_feed.owner == &switchboard_program_id,
+        ErrorCode::InvalidOracleOwner
+    );
+
+    // Parse Switchboard aggregator data
+    let switchboard_data = &switchboard_feed.data.borrow();
+    require!(switchboard_data.len() >= 512, ErrorCode::InvalidOracleData);
+
+    // Switchboard V2 AggregatorAccountData layout (simplified):
+    // The actual layout is complex, but the key fields we need are:
+    // - latest_confirmed_round.result (SwitchboardDecimal at specific offset)
+    // - latest_confirmed_round.round_open_timestamp (i64)
+    // - min_oracle_results (u32)
+    // - oracle_request_batch_size (u32)
+
+    // For security, we parse the essential fields for validation
+    // Offset 200: latest_confirmed_round.result.mantissa (i128)
+    // Offset 216: latest_confirmed_round.result.scale (u32)
+    // Offset 232: latest_confirmed_round.round_open_timestamp (i64)
+
+    let mantissa_bytes: [u8; 16] = switchboard_data[200..216].try_into()
+        .map_err(|_| ErrorCode::InvalidOracleData)?;
+    let mantissa = i128::from_le_bytes(mantissa_bytes);
+
+    let scale = u32::from_le_bytes(
+        switchboard_data[216..220].try_into().map_err(|_| ErrorCode::InvalidOracleData)?
+    );
+
+    let round_open_timestamp = i64::from_le_bytes(
+        switchboard_data[232..240].try_into().map_err(|_| ErrorCode::InvalidOracleData)?
+    );
+
+    // Convert mantissa to u64 price
+    require!(mantissa > 0, ErrorCode::InvalidOraclePrice);
+
+    // Calculate price with scale: price = mantissa / 10^scale
+    let price = if scale > 0 {
+        let divisor = 10u128.pow(scale);
+        let price_u128 = (mantissa as u128).checked_div(divisor)
+            .ok_or(ErrorCode::MathOverflow)?;
+        u64::try_from(price_u128).map_err(|_| ErrorCode::MathOverflow)?
+    } else {
+        u64::try_from(mantissa).map_err(|_| ErrorCode::MathOverflow)?
+    };
+
+    // **SECURITY FIX #50**: Validate staleness against the caller's window
+    require!(
+        current_time - round_open_timestamp <= max_age_seconds,
+        ErrorCode::OraclePriceStale
+    );
+
+    msg!("Switchboard price age: {} seconds", current_time - round_open_timestamp);
+
+    // **SECURITY FIX #50**: Validate scale/decimals (0-18 typical)
+    require!(
+        scale <= 18,
+        ErrorCode::InvalidOracleExponent
+    );
+
+    msg!("Switchboard scale: {}", scale);
+
+    // For Switchboard, we use a default confidence of 1% of price
+    // In production, you could parse the std_deviation field for actual confidence
+    let confidence = price.checked_mul(1).ok_or(ErrorCode::MathOverflow)?
+        .checked_div(100).ok_or(ErrorCode::MathOverflow)?;
+
+    // **SECURITY FIX #50**: Validate confidence against the configured ceiling
+    let confidence_bps = confidence_bps_of(price, confidence)?;
+    require!(
+        confidence_bps <= u64::from(max_confidence_bps),
+        ErrorCode::OracleConfidenceTooLow
+    );
+
+    // Normalize price to PRICE_DECIMALS (a scale of n decimals is exponent -n)
+    let normalized_price = normalize_price(price, -(scale as i32))?;
+
+    Ok((normalized_price, confidence, round_open_timestamp, confidence_bps))
+}
+
+/// Oracle confidence as a share of price in bps, rounded up; both in the feed's raw units
+pub fn confidence_bps_of(price: u64, confidence: u64) -> Result<u64> {
+    require!(price > 0, ErrorCode::InvalidOraclePrice);
+    let bps = u128::from(confidence)
+        .checked_mul(10000)
+        .ok_or(ErrorCode::MathOverflow)?
+        .div_ceil(u128::from(price));
+    Ok(u64::try_from(bps).unwrap_or(u64::MAX))
+}
+
+/// Rescale a raw oracle price with exponent `from_exponent`
+/// (MIN_ORACLE_EXPONENT..=MAX_ORACLE_EXPONENT) to PRICE_DECIMALS
+/// Scaling down truncates; scaling up fails with MathOverflow instead of wrapping
+pub fn normalize_price(raw: u64, from_exponent: i32) -> Result<u64> {
+    require!(
+        (MIN_ORACLE_EXPONENT..=MAX_ORACLE_EXPONENT).contains(&from_exponent),
+        ErrorCode::InvalidOracleExponent
+    );
+
+    // Positive exponents scale up by as much as 10^(MAX_ORACLE_EXPONENT + PRICE_DECIMALS),
+    // so the arithmetic runs in u128 and only the final value must fit u64
+    let target_exponent = -(PRICE_DECIMALS as i32);
+    let normalized = if from_exponent < target_exponent {
+        // e.g. -8 → -6: divide by 100
+        let scale_factor = 10u128.pow((target_exponent - from_exponent) as u32);
+        u128::from(raw) / scale_factor
+    } else {
+        // e.g. -4 → -6: multiply by 100; +2 → -6: multiply by 10^8
+        let scale_factor = 10u128.pow((from_exponent - target_exponent) as u32);
+        u128::from(raw).checked_mul(scale_factor).ok_or(ErrorCode::MathOverflow)?
+    };
+
+    u64::try_from(normalized).map_err(|_| ErrorCode::MathOverflow.into())
+}
+
+/// Maximum Merkle proof depth accepted for whitelist checks (supports 2^32 leaves)
+pub const MAX_WHITELIST_PROOF_LEN: usize = 32;
+
+/// Integer square root using Newton's method (floor of sqrt(n))
+pub fn integer_sqrt(n: u128) -> u128 {
+    if n == 0 {
+        return 0;
+    }
+    let mut x = n;
+    let mut y = x / 2 + x % 2;
+    while y < x {
+        x = y;
+        y = (x + n / x) / 2;
+    }
+    x
+}
+
+/// Check that burn + partner + treasury fees stay within MAX_COMBINED_FEE_BPS
+pub fn validate_combined_fees(burn_fee_bps: u16, partner_fee_bps: u16, treasury_fee_bps: u16) -> Result<()> {
+    require!(
+        u32::from(burn_fee_bps) + u32::from(partner_fee_bps) + u32::from(treasury_fee_bps)
+            <= MAX_COMBINED_FEE_BPS,
+        ErrorCode::CombinedFeeTooHigh
+    );
+    Ok(())
+}
+
+/// Check an arbitrage threshold against MIN_ARB_BPS..=MAX_ARB_BPS
+pub fn validate_arbitrage_threshold(threshold_bps: u16) -> Result<()> {
+    require!(
+        (MIN_ARB_BPS..=MAX_ARB_BPS).contains(&threshold_bps),
+        ErrorCode::InvalidRebalanceThreshold
+    );
+    Ok(())
+}
+
+/// Verify `user` is in the whitelist Merkle tree, or pass when no root is set
+/// Leaves are sha256(pubkey), parents are sha256 of the sorted child pair
+pub fn verify_whitelist(
+    merkle_root: Option<[u8; 32]>,
+    user: &Pubkey,
+    proof: &[[u8; 32]],
+) -> Result<()> {
+    let root = match merkle_root {
+        Some(root) => root,
+        None => return Ok(()),
+    };
+
+    require!(proof.len() <= MAX_WHITELIST_PROOF_LEN, ErrorCode::InvalidWhitelistProof);
+
+    let mut computed = hashv(&[user.as_ref()]).to_bytes();
+    for node in proof {
+        computed = if computed <= *node {
+            hashv(&[&computed, node]).to_bytes()
+        } else {
+            hashv(&[node, &computed]).to_bytes()
+        };
+    }
+
+    require!(computed == root, ErrorCode::NotWhitelisted);
+
+    Ok(())
+}
+
 #[event]
 pub struct RiftCreated {
     pub rift: Pubkey,
@@ -3524,6 +10294,39 @@ pub struct RiftAdminClosed {
     pub admin: Pubkey,
 }
 
+#[event]
+pub struct RiftRenamed {
+    pub rift: Pubkey,
+    pub old_name: [u8; 32],
+    pub new_name: [u8; 32],
+    pub renamed_by: Pubkey,
+}
+
+#[event]
+pub struct RiftCloseProposed {
+    pub rift: Pubkey,
+    pub creator: Pubkey,
+    pub admin: Pubkey,
+    pub proposed_at: i64,
+    pub executable_at: i64,
+}
+
+#[event]
+pub struct RiftCloseCancelled {
+    pub rift: Pubkey,
+    pub creator: Pubkey,
+    pub proposed_at: i64,
+}
+
+#[event]
+pub struct TokensRescued {
+    pub rift: Pubkey,
+    pub mint: Pubkey,
+    pub amount: u64,
+    pub recipient: Pubkey,
+    pub admin: Pubkey,
+}
+
 #[event]
 pub struct StuckAccountCleaned {
     pub creator: Pubkey,
@@ -3553,6 +10356,15 @@ pub struct MeteoraPoolCreated {
     pub bin_step: u16,
 }
 
+#[event]
+pub struct BootstrapMinted {
+    pub rift: Pubkey,
+    pub creator: Pubkey,
+    pub amount: u64,
+    pub bootstrap_minted: u64,
+    pub bootstrap_cap: u64,
+}
+
 #[event]
 pub struct TokensWrapped {
     pub rift: Pubkey,
@@ -3562,12 +10374,49 @@ pub struct TokensWrapped {
     pub rift_tokens_minted: u64,
 }
 
+#[event]
+pub struct TokensWrappedFor {
+    pub rift: Pubkey,
+    pub payer: Pubkey,
+    pub beneficiary: Pubkey,
+    pub amount_in: u64,
+    pub fee_paid: u64,
+    pub rift_tokens_minted: u64,
+}
+
+#[event]
+pub struct RiftFeeChangesScheduled {
+    pub rift: Pubkey,
+    pub burn_fee_bps: Option<u16>,
+    pub partner_fee_bps: Option<u16>,
+    pub treasury_fee_bps: Option<u16>,
+    pub effective_at: i64,
+}
+
+#[event]
+pub struct RiftFeeChangesApplied {
+    pub rift: Pubkey,
+    pub burn_fee_bps: u16,
+    pub partner_fee_bps: u16,
+    pub treasury_fee_bps: u16,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PoolUnbound {
+    pub rift: Pubkey,
+    pub pool: Pubkey,
+    pub creator: Pubkey,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct PoolInitialized {
     pub rift: Pubkey,
     pub pool_underlying: Pubkey,
     pub pool_rift: Pubkey,
     pub initial_rift_amount: u64,
+    pub initial_underlying_amount: u64,
     pub trading_fee_bps: u16,
     pub bin_step: u16,
 }
@@ -3615,11 +10464,54 @@ pub struct StakingRewardsClaimed {
     pub total_claimed: u64,
 }
 
+#[event]
+pub struct RewardsCompounded {
+    pub rift: Pubkey,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub total_staked: u64,
+}
+
+#[event]
+pub struct PendingRewardsFlushed {
+    pub rift: Pubkey,
+    pub staking_pool: Pubkey,
+    pub amount: u64,
+}
+
+#[event]
+pub struct StakerRewardsMigrated {
+    pub rift: Pubkey,
+    pub user: Pubkey,
+    pub old_version: u8,
+    pub new_version: u8,
+    pub settled_rewards: u64,
+    pub pending_rewards: u64,
+}
+
+#[event]
+pub struct VestedRewardsClaimed {
+    pub rift: Pubkey,
+    pub user: Pubkey,
+    pub amount: u64,
+    pub total_claimed: u64,
+}
+
+#[event]
+pub struct JupiterSwapFailed {
+    pub rift: Pubkey,
+    pub amount_in: u64,
+    pub expected_out: u64, // Larger of minimum_amount_out and the oracle floor
+    pub actual_out: u64,
+    pub timestamp: i64,
+}
+
 #[event]
 pub struct JupiterSwapExecuted {
     pub rift: Pubkey,
     pub amount_in: u64,
     pub minimum_amount_out: u64,
+    pub oracle_minimum_out: u64,
     pub timestamp: i64,
 }
 
@@ -3636,6 +10528,8 @@ pub struct RiftPaused {
     pub rift: Pubkey,
     pub authority: Pubkey,
     pub timestamp: i64,
+    pub reason: PauseReason,
+    pub auto_unpause_at: i64,
 }
 
 #[event]
@@ -3819,10 +10713,164 @@ pub enum ErrorCode {
     OracleConfidenceTooLow,
     #[msg("Invalid oracle exponent - exponent outside acceptable range")]
     InvalidOracleExponent,
+    #[msg("Invalid treasury fee (must be below 20%)")]
+    InvalidTreasuryFee,
+    #[msg("Staker account must be migrated to the current reward formula version")]
+    StakerMigrationRequired,
+    #[msg("Staker account is already on the current reward formula version")]
+    StakerAlreadyMigrated,
+    #[msg("Deposit would exceed the rift's maximum total underlying")]
+    DepositCapExceeded,
+    #[msg("Whitelist proof too long")]
+    InvalidWhitelistProof,
+    #[msg("User is not on the rift whitelist")]
+    NotWhitelisted,
+    #[msg("Claimable rewards below the rift's minimum - keep accruing")]
+    RewardsBelowMinimum,
+    #[msg("Invalid fee discount (max 100%)")]
+    InvalidFeeDiscount,
+    #[msg("RIFT mint supply exceeds total_rift_minted")]
+    SupplyDesync,
+    #[msg("Pool mints must be exactly the rift mint and the underlying mint")]
+    InvalidPoolMints,
+    #[msg("Invalid reward rate (max ~100% APY)")]
+    InvalidRewardRate,
+    #[msg("Invalid reward vest period (0 to 365 days)")]
+    InvalidVestPeriod,
+    #[msg("Reward vesting account and escrow are required while vesting is enabled")]
+    VestingAccountRequired,
+    #[msg("Too many rifts in one batch")]
+    TooManyRifts,
+    #[msg("Invalid rift account")]
+    InvalidRiftAccount,
+    #[msg("Initial pool price deviates too far from the oracle average")]
+    InitialPriceDeviation,
+    #[msg("Invalid price deviation (max 5%)")]
+    InvalidPriceDeviationBps,
+    #[msg("Invalid max oracle staleness (60 to 3600 seconds)")]
+    InvalidOracleStaleness,
+    #[msg("Staking reward budget exhausted")]
+    InsufficientRewardBudget,
+    #[msg("Pause expiry must be in the future (or 0 for manual unpause)")]
+    InvalidPauseExpiry,
+    #[msg("Liquidity removal exceeds the per-transaction maximum")]
+    RemovalExceedsMax,
+    #[msg("Meteora config is not on the governance allowlist")]
+    MeteoraConfigNotAllowed,
+    #[msg("Meteora config allowlist is full")]
+    TooManyMeteoraConfigs,
+    #[msg("The rift's underlying tokens cannot be rescued")]
+    CannotRescueUnderlying,
+    #[msg("Vault holds no underlying beyond total_underlying_wrapped")]
+    NoExcessToSweep,
+    #[msg("Unwrap blocked: wrap cooldown still active")]
+    WrapCooldownActive,
+    #[msg("Invalid wrap cooldown (0 to 300 seconds)")]
+    InvalidWrapCooldown,
+    #[msg("Governance has no pending oracle updates")]
+    NoPendingOracleUpdates,
+    #[msg("Staking pool does not accept deposits from this rift")]
+    InvalidStakingPool,
+    #[msg("Reward vault does not match the staking pool")]
+    InvalidRewardVault,
+    #[msg("Minimum stake duration has not elapsed")]
+    StakeDurationNotMet,
+    #[msg("Invalid minimum stake duration")]
+    InvalidStakeDuration,
+    #[msg("Reward token is not the staked token and cannot be compounded")]
+    RewardTokenNotStakeable,
+    #[msg("Rewards vest on this rift and cannot be compounded")]
+    CannotCompoundVestedRewards,
+    #[msg("Liquidity amount deviates too far from on-chain computation")]
+    LiquidityOutOfTolerance,
+    #[msg("Undercollateralization floor must be between 1 and 1_000_000")]
+    InvalidUndercollateralizationFloor,
+    #[msg("Staker account does not match the rift and user")]
+    InvalidStakerAccount,
+    #[msg("RIFTS LP and burn shares must sum to 10000 bps")]
+    InvalidRiftsShareSplit,
+    #[msg("Position NFT account is not owned by the user or holds a different position")]
+    UnauthorizedPosition,
+    #[msg("Slippage must be at most 10000 bps")]
+    InvalidSlippageBps,
+    #[msg("Oracle price cannot value this swap pair")]
+    UnpricedSwapPair,
+    #[msg("Pool still holds rift liquidity")]
+    PoolHasLiquidity,
+    #[msg("No pending rift fee changes")]
+    NoPendingRiftChanges,
+    #[msg("Pending rift fee changes are not yet effective")]
+    RiftChangesNotEffective,
+    #[msg("Oracle quorum must be between 1 and the number of oracle types")]
+    InvalidOracleMinSources,
+    #[msg("Bootstrap minting is closed after the first public wrap")]
+    BootstrapComplete,
+    #[msg("Bootstrap mint would exceed the bootstrap cap")]
+    BootstrapCapExceeded,
+    #[msg("Rift registry page is full")]
+    RegistryPageFull,
+    #[msg("Amount is below the rift's minimum wrap amount")]
+    AmountBelowMinimum,
+    #[msg("Minimum wrap amount is outside the allowed range for this mint")]
+    InvalidMinWrapAmount,
+    #[msg("Proposal has already been applied to this rift")]
+    ProposalAlreadyApplied,
+    #[msg("Fee amount exceeds the wrap fees accrued in the fee vault")]
+    InsufficientAccruedFees,
+    #[msg("Creator does not hold a verified NFT from the required collection")]
+    CollectionGateNotSatisfied,
+    #[msg("Confidence ceiling must be between 1 and 10000 bps")]
+    InvalidMaxConfidence,
+    #[msg("Pool deviation from the oracle is within the arbitrage threshold")]
+    ArbitrageBelowThreshold,
+    #[msg("Arbitrage trade exceeds the allowed share of pool liquidity")]
+    ArbitrageTradeTooLarge,
+    #[msg("Arbitrage did not return more than the input's backing")]
+    ArbitrageNotProfitable,
+    #[msg("An admin close is already pending for this rift")]
+    CloseAlreadyProposed,
+    #[msg("No admin close has been proposed for this rift")]
+    CloseNotProposed,
+    #[msg("Admin close grace period has not elapsed")]
+    CloseGracePending,
+    #[msg("Meteora config does not match the rift's config or is not owned by Meteora")]
+    InvalidMeteoraConfig,
+    #[msg("Position is not the Meteora position for this NFT and pool")]
+    InvalidPosition,
+    #[msg("Incinerator account is missing or not owned by the rift's incinerator")]
+    InvalidIncinerator,
+    #[msg("Burn, partner and treasury fees combined exceed 9500 bps")]
+    CombinedFeeTooHigh,
+    #[msg("Wrap exceeds the allowed share of the rift's current TVL")]
+    WrapTooLargeRelativeToTvl,
+    #[msg("Max wrap share of TVL must be at most 10000 bps")]
+    InvalidWrapTvlCap,
+    #[msg("LP token account does not hold the rift's LP mint")]
+    InvalidLpMint,
+    #[msg("Manual backing ratio change exceeds the per-call limit")]
+    BackingRatioChangeTooLarge,
+    #[msg("Treasury account does not match the rift's configured treasury")]
+    InvalidTreasury,
+    #[msg("No protocol-held fees to compound")]
+    NothingToCompound,
+    #[msg("Governance account is not the protocol's canonical governance")]
+    InvalidGovernance,
+    #[msg("The rift's own RIFT tokens cannot be rescued")]
+    CannotRescueRiftTokens,
+    #[msg("Proposal targets a different rift")]
+    ProposalNotForThisRift,
+    #[msg("Rift account already uses the current layout")]
+    RiftAlreadyMigrated,
+    #[msg("Account is not a pre-migration Rift")]
+    InvalidLegacyRift,
+    #[msg("Rift was paused by governance or automatically; only governance can change it")]
+    PausedByAnotherAuthority,
+    #[msg("Instruction is disabled by the rift's protocol fee mode")]
+    WrongProtocolFeeMode,
 }
 
 /// **SECURITY FIX #50**: Oracle type enum for event emission
-#[derive(AnchorSerialize, AnchorDeserialize, Clone, PartialEq, Eq)]
+#[derive(AnchorSerialize, AnchorDeserialize, Clone, Copy, PartialEq, Eq)]
 pub enum OracleType {
     Pyth,
     Switchboard,
@@ -3835,5 +10883,680 @@ pub struct OraclePriceUpdated {
     pub oracle_type: OracleType,
     pub price: u64,
     pub confidence: u64,
+    pub max_confidence_bps: u16, // Ceiling the update was accepted under
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct DepositCapChanged {
+    pub rift: Pubkey,
+    pub old_cap: u64,
+    pub new_cap: u64,
+    pub changed_by: Pubkey,
+}
+
+#[event]
+pub struct WhitelistRootChanged {
+    pub rift: Pubkey,
+    pub old_root: Option<[u8; 32]>,
+    pub new_root: Option<[u8; 32]>,
+}
+
+#[event]
+pub struct OracleHealthReported {
+    pub rift: Pubkey,
+    pub seconds_since_update: i64,
+    pub oracle_countdown: i64,
+    pub newest_sample_fresh: bool,
+    pub average_price: u64,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RewardBudgetFunded {
+    pub rift: Pubkey,
+    pub amount: u64,
+    pub new_budget: u64,
+    pub funded_by: Pubkey,
+}
+
+#[event]
+pub struct RiftMeteoraConfigUpdated {
+    pub rift: Pubkey,
+    pub old_config: Pubkey,
+    pub new_config: Pubkey,
+    pub updated_by: Pubkey,
+}
+
+#[event]
+pub struct MeteoraConfigAllowlistUpdated {
+    pub config: Pubkey,
+    pub allowed: bool,
+    pub updated_by: Pubkey,
+}
+
+#[event]
+pub struct RiftFactoryGateUpdated {
+    pub required_collection_mint: Option<Pubkey>,
+    pub admin: Pubkey,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct PoolMetadataSynced {
+    pub rift: Pubkey,
+    pub pool: Pubkey,
+    pub sqrt_price: u128,
+    pub liquidity: u128,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct ProtocolAdminChanged {
+    pub old_admin: Pubkey,
+    pub new_admin: Pubkey,
+    pub proposal_id: u64,
+    pub executor: Pubkey,
+}
+
+#[event]
+pub struct BackingRatioManuallySet {
+    pub rift: Pubkey,
+    pub old_backing_ratio: u64,
+    pub new_backing_ratio: u64,
+    pub proposal_id: u64,
+    pub executor: Pubkey,
+}
+
+#[event]
+pub struct ExcessSwept {
+    pub rift: Pubkey,
+    pub amount: u64,
+    pub vault_balance: u64,
+    pub total_underlying_wrapped: u64,
+    pub treasury: Pubkey,
+    pub swept_by: Pubkey,
+}
+
+#[event]
+pub struct MeteoraFeesClaimed {
+    pub rift: Pubkey,
+    pub pool: Pubkey,
+    pub position: Pubkey,
+    pub underlying_fees: u64,
+    pub rift_fees: u64,
+    pub total_fees: u64,
+}
+
+#[event]
+pub struct ProtocolLiquidityCompounded {
+    pub rift: Pubkey,
+    pub pool: Pubkey,
+    pub position: Pubkey,
+    pub underlying_fees: u64,
+    pub rift_fees: u64,
+    pub underlying_added: u64,
+    pub rift_added: u64,
+    pub liquidity_delta: u128,
+}
+
+#[event]
+pub struct ArbitrageCaptured {
+    pub rift: Pubkey,
+    pub sold_rift: bool,
+    pub amount_in: u64,
+    pub amount_out: u64,
+    pub profit: u64,
+    pub pool_price_before: u64,
+    pub pool_price_after: u64,
+    pub oracle_price: u64,
+    pub backing_ratio: u64,
+    pub executor: Pubkey,
+}
+
+#[event]
+pub struct UndercollateralizationWarning {
+    pub rift: Pubkey,
+    pub backing_ratio: u64,
+    pub oracle_price: u64,
+    pub floor: u64,
+    pub auto_paused: bool,
+    pub timestamp: i64,
+}
+
+#[event]
+pub struct RebalanceTriggered {
+    pub rift: Pubkey,
+    pub keeper: Pubkey,
+    pub backing_ratio: u64,
+    pub keeper_reward_paid: u64,
     pub timestamp: i64,
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::solana_program::program_pack::Pack;
+
+    fn leaf(user: &Pubkey) -> [u8; 32] {
+        hashv(&[user.as_ref()]).to_bytes()
+    }
+
+    fn parent(a: [u8; 32], b: [u8; 32]) -> [u8; 32] {
+        if a <= b {
+            hashv(&[&a, &b]).to_bytes()
+        } else {
+            hashv(&[&b, &a]).to_bytes()
+        }
+    }
+
+    // Four-leaf tree: root = H(H(l0, l1), H(l2, l3))
+    fn build_tree(users: &[Pubkey; 4]) -> ([u8; 32], [u8; 32], [u8; 32]) {
+        let leaves: Vec<[u8; 32]> = users.iter().map(leaf).collect();
+        let left = parent(leaves[0], leaves[1]);
+        let right = parent(leaves[2], leaves[3]);
+        (parent(left, right), left, right)
+    }
+
+    #[test]
+    fn whitelist_accepts_valid_proof() {
+        let users = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+        let (root, left, right) = build_tree(&users);
+
+        let proof_0 = vec![leaf(&users[1]), right];
+        assert!(verify_whitelist(Some(root), &users[0], &proof_0).is_ok());
+
+        let proof_3 = vec![leaf(&users[2]), left];
+        assert!(verify_whitelist(Some(root), &users[3], &proof_3).is_ok());
+    }
+
+    #[test]
+    fn whitelist_rejects_invalid_proof() {
+        let users = [Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique(), Pubkey::new_unique()];
+        let (root, _left, right) = build_tree(&users);
+
+        // Outsider reusing a member's proof
+        let outsider = Pubkey::new_unique();
+        let proof_0 = vec![leaf(&users[1]), right];
+        assert_eq!(
+            verify_whitelist(Some(root), &outsider, &proof_0).unwrap_err(),
+            ErrorCode::NotWhitelisted.into()
+        );
+
+        // Member with a truncated proof
+        assert_eq!(
+            verify_whitelist(Some(root), &users[0], &proof_0[..1]).unwrap_err(),
+            ErrorCode::NotWhitelisted.into()
+        );
+
+        // Oversized proof is rejected before hashing
+        let long_proof = vec![[0u8; 32]; MAX_WHITELIST_PROOF_LEN + 1];
+        assert_eq!(
+            verify_whitelist(Some(root), &users[0], &long_proof).unwrap_err(),
+            ErrorCode::InvalidWhitelistProof.into()
+        );
+    }
+
+    #[test]
+    fn whitelist_open_mode_allows_everyone() {
+        let user = Pubkey::new_unique();
+        assert!(verify_whitelist(None, &user, &[]).is_ok());
+        assert!(verify_whitelist(None, &user, &[[7u8; 32]]).is_ok());
+    }
+
+    #[test]
+    fn arbitrage_threshold_valid_at_creation_is_valid_for_governance() {
+        // Creation default and both range endpoints pass the shared governance check
+        for threshold in [DEFAULT_ARB_BPS, MIN_ARB_BPS, MAX_ARB_BPS] {
+            assert!(validate_arbitrage_threshold(threshold).is_ok());
+        }
+        // Values the old governance-only 50..=1000 range rejected
+        assert!(validate_arbitrage_threshold(20).is_ok());
+        assert!(validate_arbitrage_threshold(2500).is_ok());
+    }
+
+    #[test]
+    fn normalize_price_maps_one_to_price_scale_for_every_exponent() {
+        // 1.0 expressed at each supported exponent normalizes to 1.0 at PRICE_DECIMALS
+        for exponent in -18..=0i32 {
+            let one = 10u64.pow(exponent.unsigned_abs());
+            assert_eq!(normalize_price(one, exponent).unwrap(), 10u64.pow(PRICE_DECIMALS));
+        }
+    }
+
+    #[test]
+    fn normalize_price_matches_reference_scaling() {
+        let raw = 1_234_567_890u64;
+        for exponent in -18..=0i32 {
+            let expected = u128::from(raw) * 10u128.pow(PRICE_DECIMALS) / 10u128.pow(exponent.unsigned_abs());
+            assert_eq!(u128::from(normalize_price(raw, exponent).unwrap()), expected, "exponent {}", exponent);
+        }
+    }
+
+    #[test]
+    fn normalize_price_rejects_bad_exponents_and_overflow() {
+        for exponent in [MIN_ORACLE_EXPONENT - 1, MAX_ORACLE_EXPONENT + 1, i32::MIN, i32::MAX] {
+            assert_eq!(
+                normalize_price(1, exponent).unwrap_err(),
+                ErrorCode::InvalidOracleExponent.into()
+            );
+        }
+        assert_eq!(
+            normalize_price(u64::MAX, 0).unwrap_err(),
+            ErrorCode::MathOverflow.into()
+        );
+    }
+
+    #[test]
+    fn normalize_price_scales_up_positive_exponents() {
+        // 12 * 10^2 = 1_200.0 and 3 * 10^4 = 30_000.0
+        assert_eq!(normalize_price(12, 2).unwrap(), 1_200 * 10u64.pow(PRICE_DECIMALS));
+        assert_eq!(normalize_price(3, 4).unwrap(), 30_000 * 10u64.pow(PRICE_DECIMALS));
+
+        // Largest raw values that still fit u64 after scaling, and the first that do not
+        let max_at_2 = u64::MAX / 10u64.pow(PRICE_DECIMALS + 2);
+        let max_at_4 = u64::MAX / 10u64.pow(PRICE_DECIMALS + 4);
+        assert_eq!(normalize_price(max_at_2, 2).unwrap(), max_at_2 * 10u64.pow(PRICE_DECIMALS + 2));
+        assert_eq!(normalize_price(max_at_4, 4).unwrap(), max_at_4 * 10u64.pow(PRICE_DECIMALS + 4));
+        assert_eq!(normalize_price(max_at_2 + 1, 2).unwrap_err(), ErrorCode::MathOverflow.into());
+        assert_eq!(normalize_price(max_at_4 + 1, 4).unwrap_err(), ErrorCode::MathOverflow.into());
+        assert_eq!(normalize_price(u64::MAX, MAX_ORACLE_EXPONENT).unwrap_err(), ErrorCode::MathOverflow.into());
+    }
+
+    #[test]
+    fn arbitrage_threshold_rejects_out_of_range() {
+        for threshold in [0, MIN_ARB_BPS - 1, MAX_ARB_BPS + 1, u16::MAX] {
+            assert_eq!(
+                validate_arbitrage_threshold(threshold).unwrap_err(),
+                ErrorCode::InvalidRebalanceThreshold.into()
+            );
+        }
+    }
+
+    #[test]
+    fn sweep_never_drops_vault_below_backing() {
+        let amounts = [0u64, 1, 999, 1_000, 1_001, 5_000_000, u64::MAX - 1, u64::MAX];
+        for vault_balance in amounts {
+            for total_underlying_wrapped in amounts {
+                for rift_supply in amounts {
+                    let excess = sweepable_excess(vault_balance, total_underlying_wrapped, rift_supply);
+                    let remaining = vault_balance - excess;
+                    let backing = total_underlying_wrapped.max(rift_supply);
+                    assert!(excess <= vault_balance);
+                    assert!(remaining >= backing.min(vault_balance));
+                    if vault_balance > backing {
+                        assert_eq!(remaining, backing);
+                    }
+                }
+            }
+        }
+
+        // A pool-side unwrap lowered total_underlying_wrapped while RIFT is still outstanding
+        assert_eq!(sweepable_excess(1_000, 400, 1_000), 0);
+        assert_eq!(sweepable_excess(1_200, 400, 1_000), 200);
+    }
+
+    #[test]
+    fn applied_proposal_record_rejects_replays() {
+        let mut record = AppliedProposalRecord { last_proposal_id: None, bump: 0 };
+        assert!(record.record(0, 254).is_ok());
+        assert!(record.record(0, 254).is_err());
+        assert!(record.record(3, 254).is_ok());
+        assert!(record.record(2, 254).is_err());
+        assert_eq!(record.last_proposal_id, Some(3));
+
+        // A fresh record (another governance or effect) starts its own sequence
+        let mut other = AppliedProposalRecord { last_proposal_id: None, bump: 0 };
+        assert!(other.record(0, 253).is_ok());
+    }
+
+    #[test]
+    fn wrap_size_cap_skips_empty_rift() {
+        let mut rift: Rift = zeroed(8 + std::mem::size_of::<Rift>());
+        rift.max_wrap_pct_of_tvl_bps = 1_000;
+
+        // First wrap into an empty rift with no threshold configured
+        assert!(rift.check_wrap_size(1_000_000).is_ok());
+
+        rift.total_underlying_wrapped = 10_000;
+        assert!(rift.check_wrap_size(1_000).is_ok());
+        assert!(rift.check_wrap_size(1_001).is_err());
+    }
+
+    fn metadata_bytes(mint: &Pubkey, creators: u32, collection: Option<(bool, Pubkey)>) -> Vec<u8> {
+        let mut data = vec![4u8];
+        data.extend_from_slice(&[0u8; 32]);
+        data.extend_from_slice(mint.as_ref());
+        for field in ["Gate #1", "GATE", "https://example.com/1.json"] {
+            data.extend_from_slice(&(field.len() as u32).to_le_bytes());
+            data.extend_from_slice(field.as_bytes());
+        }
+        data.extend_from_slice(&500u16.to_le_bytes());
+        data.push(1);
+        data.extend_from_slice(&creators.to_le_bytes());
+        data.extend(std::iter::repeat(7u8).take(creators as usize * 34));
+        data.extend_from_slice(&[1, 1]); // primary_sale_happened, is_mutable
+        data.extend_from_slice(&[1, 254]); // edition_nonce
+        data.push(0); // token_standard
+        match collection {
+            Some((verified, key)) => {
+                data.extend_from_slice(&[1, verified as u8]);
+                data.extend_from_slice(key.as_ref());
+            }
+            None => data.push(0),
+        }
+        data
+    }
+
+    #[test]
+    fn metadata_collection_requires_verification() {
+        let mint = Pubkey::new_unique();
+        let collection = Pubkey::new_unique();
+
+        assert_eq!(
+            metadata_verified_collection(&metadata_bytes(&mint, 2, Some((true, collection)))),
+            Some((mint, collection))
+        );
+        assert_eq!(metadata_verified_collection(&metadata_bytes(&mint, 0, Some((false, collection)))), None);
+        assert_eq!(metadata_verified_collection(&metadata_bytes(&mint, 1, None)), None);
+
+        let truncated = metadata_bytes(&mint, 1, Some((true, collection)));
+        assert_eq!(metadata_verified_collection(&truncated[..truncated.len() - 1]), None);
+    }
+
+    #[test]
+    fn staking_vault_rejects_wrong_vault() {
+        let rift = Pubkey::new_unique();
+        let other_rift = Pubkey::new_unique();
+        let lp_mint = Pubkey::new_unique();
+        let (authority, _) = Pubkey::find_program_address(&[b"staking_vault", rift.as_ref()], &crate::ID);
+        let (other_authority, _) = Pubkey::find_program_address(&[b"staking_vault", other_rift.as_ref()], &crate::ID);
+
+        assert!(is_staking_vault(&authority, &lp_mint, &rift, &lp_mint));
+
+        // Attacker-owned account, another rift's vault, or the wrong mint
+        assert!(!is_staking_vault(&Pubkey::new_unique(), &lp_mint, &rift, &lp_mint));
+        assert!(!is_staking_vault(&other_authority, &lp_mint, &rift, &lp_mint));
+        assert!(!is_staking_vault(&authority, &Pubkey::new_unique(), &rift, &lp_mint));
+    }
+
+    // floor(sqrt(n)): r*r <= n < (r+1)*(r+1), where an overflowing (r+1)^2 counts as > n
+    fn assert_floor_sqrt(n: u128) {
+        let r = integer_sqrt(n);
+        assert!(r.checked_mul(r).map_or(false, |sq| sq <= n), "sqrt({}) = {} too large", n, r);
+        assert!((r + 1).checked_mul(r + 1).map_or(true, |sq| sq > n), "sqrt({}) = {} too small", n, r);
+    }
+
+    #[test]
+    fn integer_sqrt_is_floor_sqrt() {
+        assert_eq!(integer_sqrt(0), 0);
+        assert_eq!(integer_sqrt(u128::MAX), u64::MAX as u128);
+
+        for n in 0..10_000u128 {
+            assert_floor_sqrt(n);
+        }
+
+        // Perfect squares and their neighbours, up to the largest square that fits in u128
+        for r in [1u128, 2, 3, 1 << 32, (1 << 32) + 1, u32::MAX as u128, 1 << 63, u64::MAX as u128] {
+            let sq = r * r;
+            assert_eq!(integer_sqrt(sq), r);
+            assert_eq!(integer_sqrt(sq - 1), r - 1);
+            assert_floor_sqrt(sq + 1);
+        }
+
+        // Deterministic pseudo-random sweep across all magnitudes
+        let mut n: u128 = 0x9E37_79B9_7F4A_7C15_F39C_C060_5CED_C834;
+        for _ in 0..10_000 {
+            n = n.wrapping_mul(0x2360_ED05_1FC6_5DA4_4385_DF64_9FCC_F645).wrapping_add(1);
+            assert_floor_sqrt(n);
+            assert_floor_sqrt(n >> (n % 128) as u32);
+        }
+
+        // Product of two u64 amounts, as computed in unwrap_tokens and wrap_and_add_liquidity
+        assert_floor_sqrt(u64::MAX as u128 * u64::MAX as u128);
+    }
+
+    // Minimal runtime for driving an instruction through the entrypoint: CPIs to SPL Token and
+    // lp-staking are executed in-process, PDA signers are derived from the signer seeds
+    struct CpiStubs;
+
+    impl anchor_lang::solana_program::program_stubs::SyscallStubs for CpiStubs {
+        fn sol_invoke_signed(
+            &self,
+            instruction: &anchor_lang::solana_program::instruction::Instruction,
+            account_infos: &[AccountInfo],
+            signers_seeds: &[&[&[u8]]],
+        ) -> anchor_lang::solana_program::entrypoint::ProgramResult {
+            let signers: Vec<Pubkey> = signers_seeds
+                .iter()
+                .flat_map(|seeds| {
+                    [crate::ID, lp_staking::ID]
+                        .iter()
+                        .filter_map(|program| Pubkey::create_program_address(seeds, program).ok())
+                        .collect::<Vec<_>>()
+                })
+                .collect();
+            let accounts: Vec<AccountInfo> = instruction
+                .accounts
+                .iter()
+                .map(|meta| {
+                    let mut info = account_infos
+                        .iter()
+                        .find(|info| *info.key == meta.pubkey)
+                        .expect("CPI account not passed")
+                        .clone();
+                    info.is_signer = info.is_signer || signers.contains(&meta.pubkey);
+                    info
+                })
+                .collect();
+            let accounts: &[AccountInfo] = Box::leak(accounts.into_boxed_slice());
+
+            if instruction.program_id == anchor_spl::token::ID {
+                anchor_spl::token::spl_token::processor::Processor::process(
+                    &instruction.program_id,
+                    accounts,
+                    &instruction.data,
+                )
+            } else if instruction.program_id == lp_staking::ID {
+                lp_staking::entry(&instruction.program_id, accounts, &instruction.data)
+            } else {
+                panic!("unexpected CPI to {}", instruction.program_id)
+            }
+        }
+
+        fn sol_get_clock_sysvar(&self, var_addr: *mut u8) -> u64 {
+            let clock = Clock { unix_timestamp: 1_700_000_000, ..Clock::default() };
+            unsafe { *(var_addr as *mut Clock) = clock };
+            0
+        }
+    }
+
+    struct TestAccount {
+        key: Pubkey,
+        owner: Pubkey,
+        lamports: u64,
+        data: Vec<u8>,
+        executable: bool,
+    }
+
+    impl TestAccount {
+        fn new(key: Pubkey, owner: Pubkey, data: Vec<u8>) -> Self {
+            Self { key, owner, lamports: 1_000_000_000, data, executable: false }
+        }
+
+        fn program(key: Pubkey) -> Self {
+            Self { executable: true, ..Self::new(key, Pubkey::default(), Vec::new()) }
+        }
+
+        fn info(&mut self) -> AccountInfo<'_> {
+            AccountInfo::new(
+                &self.key,
+                false,
+                !self.executable,
+                &mut self.lamports,
+                &mut self.data,
+                &self.owner,
+                self.executable,
+                0,
+            )
+        }
+    }
+
+    // Anchor account bytes for `value`, padded to `space`
+    fn anchor_account<T: AccountSerialize>(value: &T, space: usize) -> Vec<u8> {
+        let mut data = Vec::with_capacity(space);
+        value.try_serialize(&mut data).unwrap();
+        data.resize(space, 0);
+        data
+    }
+
+    // Every field zeroed (None / false / first variant), as a freshly allocated account reads
+    fn zeroed<T: AnchorDeserialize>(space: usize) -> T {
+        T::deserialize(&mut &vec![0u8; space][..]).unwrap()
+    }
+
+    fn token_account(mint: Pubkey, owner: Pubkey, amount: u64) -> Vec<u8> {
+        use anchor_spl::token::spl_token::state::{Account as SplAccount, AccountState};
+        let mut data = vec![0u8; SplAccount::LEN];
+        SplAccount::pack(
+            SplAccount { mint, owner, amount, state: AccountState::Initialized, ..SplAccount::default() },
+            &mut data,
+        )
+        .unwrap();
+        data
+    }
+
+    fn token_amount(data: &[u8]) -> u64 {
+        anchor_spl::token::spl_token::state::Account::unpack(data).unwrap().amount
+    }
+
+    #[test]
+    fn flush_pending_rewards_deposits_budget_into_lp_staking_pool() {
+        anchor_lang::solana_program::program_stubs::set_syscall_stubs(Box::new(CpiStubs));
+
+        let rift_key = Pubkey::new_unique();
+        let governance_key = Pubkey::new_unique();
+        let rifts_mint_key = Pubkey::new_unique();
+        let staking_pool_key = Pubkey::new_unique();
+        let pool_vault_key = Pubkey::new_unique();
+        let (protocol_config_key, config_bump) = Pubkey::find_program_address(&[b"protocol_config"], &crate::ID);
+        let (rifts_authority, _) = Pubkey::find_program_address(&[b"rift_mint_auth", rift_key.as_ref()], &crate::ID);
+        let (budget_vault_key, _) = Pubkey::find_program_address(&[b"reward_budget_vault", rift_key.as_ref()], &crate::ID);
+        let (reward_authority, _) = Pubkey::find_program_address(&[b"reward_authority", staking_pool_key.as_ref()], &lp_staking::ID);
+
+        let rift_space = 8 + std::mem::size_of::<Rift>();
+        let mut rift: Rift = zeroed(rift_space);
+        rift.rift_mint = Pubkey::new_unique();
+        rift.pending_rewards = 700;
+        rift.rifts_reward_budget = 500;
+
+        let mut governance: governance::Governance = zeroed(governance::Governance::INIT_SPACE);
+        governance.rifts_mint = rifts_mint_key;
+
+        let mut staking_pool: lp_staking::StakingPool = zeroed(lp_staking::StakingPool::INIT_SPACE);
+        staking_pool.rifts_protocol = rifts_authority;
+        staking_pool.reward_token_mint = rifts_mint_key;
+        staking_pool.reward_token_vault = pool_vault_key;
+        staking_pool.total_staked = 1_000;
+
+        let mut mint_data = vec![0u8; anchor_spl::token::spl_token::state::Mint::LEN];
+        anchor_spl::token::spl_token::state::Mint::pack(
+            anchor_spl::token::spl_token::state::Mint {
+                mint_authority: COption::Some(reward_authority),
+                supply: 1_000_000,
+                decimals: 9,
+                is_initialized: true,
+                freeze_authority: COption::None,
+            },
+            &mut mint_data,
+        )
+        .unwrap();
+
+        let mut accounts = vec![
+            TestAccount::new(rift_key, crate::ID, anchor_account(&rift, rift_space)),
+            TestAccount::new(
+                protocol_config_key,
+                crate::ID,
+                anchor_account(
+                    &ProtocolConfig { protocol_admin: Pubkey::new_unique(), governance: governance_key, bump: config_bump },
+                    ProtocolConfig::LEN,
+                ),
+            ),
+            TestAccount::new(governance_key, governance::ID, anchor_account(&governance, governance::Governance::INIT_SPACE)),
+            TestAccount::new(rifts_mint_key, anchor_spl::token::ID, mint_data),
+            TestAccount::new(rifts_authority, System::id(), Vec::new()),
+            TestAccount::new(budget_vault_key, anchor_spl::token::ID, token_account(rifts_mint_key, rifts_authority, 500)),
+            TestAccount::new(staking_pool_key, lp_staking::ID, anchor_account(&staking_pool, lp_staking::StakingPool::INIT_SPACE)),
+            TestAccount::new(pool_vault_key, anchor_spl::token::ID, token_account(rifts_mint_key, reward_authority, 0)),
+            TestAccount::program(lp_staking::ID),
+            TestAccount::program(anchor_spl::token::ID),
+        ];
+        let infos: Vec<AccountInfo> = accounts.iter_mut().map(TestAccount::info).collect();
+
+        let data = anchor_lang::InstructionData::data(&crate::instruction::FlushPendingRewardsToStakingPool {});
+        crate::entry(&crate::ID, &infos, &data).unwrap();
+
+        // Only the funded budget is deposited; the uncovered remainder stays pending
+        let rift = Rift::try_deserialize(&mut &infos[0].data.borrow()[..]).unwrap();
+        assert_eq!(rift.pending_rewards, 200);
+        assert_eq!(rift.rifts_reward_budget, 0);
+        assert_eq!(token_amount(&infos[5].data.borrow()), 0);
+        assert_eq!(token_amount(&infos[7].data.borrow()), 500);
+
+        let staking_pool = lp_staking::StakingPool::try_deserialize(&mut &infos[6].data.borrow()[..]).unwrap();
+        assert_eq!(staking_pool.total_rewards_available, 500);
+        assert!(staking_pool.accumulated_rewards_per_share > 0);
+
+        // A second flush with an empty budget deposits nothing
+        assert!(crate::entry(&crate::ID, &infos, &data).is_err());
+    }
+
+    #[test]
+    fn legacy_rift_migrates_to_current_layout() {
+        let mut head: LegacyRiftHead = zeroed(512);
+        head.creator = Pubkey::new_unique();
+        head.rift_mint = Pubkey::new_unique();
+        head.partner_wallet = Some(Pubkey::new_unique());
+        head.total_underlying_wrapped = 5_000;
+        head.backing_ratio = 10_000;
+        let mut prices = [LegacyPriceData { price: 0, confidence: 0, timestamp: 0 }; 10];
+        prices[3] = LegacyPriceData { price: 1_250_000, confidence: 900, timestamp: 42 };
+        let mut tail: LegacyRiftTail = zeroed(1024);
+        tail.price_index = 4;
+        tail.liquidity_pool = Some(Pubkey::new_unique());
+        tail.pending_rewards = 77;
+        tail.last_governance_update = 1_234;
+
+        let mut data = Rift::DISCRIMINATOR.to_vec();
+        head.serialize(&mut data).unwrap();
+        prices.serialize(&mut data).unwrap();
+        tail.serialize(&mut data).unwrap();
+        // The current layout no longer decodes legacy accounts
+        assert!(Rift::try_deserialize(&mut &data[..]).is_err());
+
+        let rift = migrate_legacy_rift(&data, 1_700_000_000).unwrap();
+        assert_eq!(rift.creator, head.creator);
+        assert_eq!(rift.rift_mint, head.rift_mint);
+        assert_eq!(rift.partner_wallet, head.partner_wallet);
+        assert_eq!(rift.total_underlying_wrapped, 5_000);
+        assert_eq!(rift.oracle_prices[3].price, 1_250_000);
+        assert_eq!(rift.oracle_prices[3].timestamp, 42);
+        assert!(rift.oracle_prices[3].source.is_none());
+        assert_eq!(rift.price_index, 4);
+        assert_eq!(rift.liquidity_pool, tail.liquidity_pool);
+        assert_eq!(rift.pending_rewards, 77);
+        assert_eq!(rift.last_governance_update, 1_234);
+
+        // Appended fields take the creation defaults
+        assert_eq!(rift.treasury_fee_bps, DEFAULT_TREASURY_FEE_BPS);
+        assert_eq!(rift.min_wrap_amount, DEFAULT_MIN_WRAP_AMOUNT);
+        assert_eq!(rift.backing_ratio_high_water, 10_000);
+        assert_eq!(rift.volume_window_start, 1_700_000_000);
+        assert_eq!(rift.fee_vault, Pubkey::default());
+
+        // Re-encoded data round-trips in the current layout
+        let mut current = Vec::new();
+        rift.try_serialize(&mut current).unwrap();
+        assert_eq!(Rift::try_deserialize(&mut &current[..]).unwrap().pending_rewards, 77);
+    }
+}